@@ -0,0 +1,337 @@
+/// How an option is shown and edited, text values are written in quotes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Kind {
+    Bool,
+    Int,
+    Text,
+}
+
+/// Extra validation of a raw value, returns why it was rejected
+pub type Check = fn(&str) -> Result<(), String>;
+
+/// Everything known about an option besides its value
+#[derive(Clone)]
+pub struct OptionInfo {
+    pub name: &'static str,
+    /// Table the option is in in the config file, empty for the top level
+    pub section: &'static str,
+    pub kind: Kind,
+    /// Comment lines written above the option in the config file
+    pub comment: Vec<String>,
+    /// Description shown in the options menu
+    pub menu: Vec<String>,
+    /// Cycled through with left and right in the options menu instead of typed in
+    pub cycle: bool,
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+    /// Accepted values, empty for any value
+    pub choices: Vec<String>,
+    /// Extra validation of the raw value
+    pub check: Option<Check>,
+}
+impl OptionInfo {
+    /// Name of the option inside its section, "proc_sorting" is "sorting" in [proc]
+    pub fn key(&self) -> &'static str {
+        self.name
+            .strip_prefix(self.section)
+            .and_then(|key| key.strip_prefix('_'))
+            .unwrap_or(self.name)
+    }
+
+    /// Keep a number inside the range of the option
+    pub fn clamp(&self, n: i64) -> i64 {
+        let n: i64 = self.min.map_or(n, |min| n.max(min));
+        self.max.map_or(n, |max| n.min(max))
+    }
+
+    /// Parse and validate a value from the config file or the options menu
+    pub fn parse<T: ConfigValue>(&self, value: &str) -> Result<T, String> {
+        let error = |reason: String| -> String {
+            format!("Config key {:?} {} (was {:?})!", self.name, reason, value)
+        };
+        let parsed: T = T::parse_value(value).map_err(error)?;
+        if !self.choices.is_empty() && !self.choices.iter().any(|c| c == value) {
+            return Err(error(format!(
+                "should be one of {}",
+                self.choices.join(", ")
+            )));
+        }
+        if let Some(n) = parsed.as_number() {
+            if self.clamp(n) != n {
+                return Err(error(match (self.min, self.max) {
+                    (Some(min), Some(max)) => format!("should be between {} and {}", min, max),
+                    (Some(min), None) => format!("should be at least {}", min),
+                    (_, max) => format!("should be at most {}", max.unwrap_or_default()),
+                }));
+            }
+        }
+        if let Some(check) = self.check {
+            check(value).map_err(error)?;
+        }
+        Ok(parsed)
+    }
+}
+
+/// A type an option can have, errors say what the value should have been
+pub trait ConfigValue: Sized {
+    const KIND: Kind;
+    fn parse_value(s: &str) -> Result<Self, String>;
+    fn write_value(&self) -> String;
+    /// The value as a number for range checks
+    fn as_number(&self) -> Option<i64> {
+        None
+    }
+}
+
+impl ConfigValue for String {
+    const KIND: Kind = Kind::Text;
+    fn parse_value(s: &str) -> Result<Self, String> {
+        Ok(s.to_owned())
+    }
+    fn write_value(&self) -> String {
+        self.clone()
+    }
+}
+
+impl ConfigValue for bool {
+    const KIND: Kind = Kind::Bool;
+    fn parse_value(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "true" | "yes" | "on" | "1" => Ok(true),
+            "false" | "no" | "off" | "0" => Ok(false),
+            _ => Err("should be True or False".to_owned()),
+        }
+    }
+    fn write_value(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ConfigValue for i64 {
+    const KIND: Kind = Kind::Int;
+    fn parse_value(s: &str) -> Result<Self, String> {
+        s.parse::<i64>()
+            .map_err(|_| "should be an integer".to_owned())
+    }
+    fn write_value(&self) -> String {
+        self.to_string()
+    }
+    fn as_number(&self) -> Option<i64> {
+        Some(*self)
+    }
+}
+
+impl ConfigValue for i32 {
+    const KIND: Kind = Kind::Int;
+    fn parse_value(s: &str) -> Result<Self, String> {
+        s.parse::<i32>()
+            .map_err(|_| "should be an integer".to_owned())
+    }
+    fn write_value(&self) -> String {
+        self.to_string()
+    }
+    fn as_number(&self) -> Option<i64> {
+        Some(*self as i64)
+    }
+}
+
+/// Generated by #[derive(ConfigSchema)] for the fields with an #[option(...)] attribute
+pub trait ConfigSchema {
+    /// Every option in field order
+    fn options() -> Vec<OptionInfo>;
+    /// The value of an option as written to the config file, without quotes
+    fn get_value(&self, name: &str) -> Option<String>;
+    /// Parse, validate and set an option, the old value is kept on errors
+    fn set_value(&mut self, name: &str, value: &str) -> Result<(), String>;
+
+    fn option(name: &str) -> Option<OptionInfo> {
+        Self::options().into_iter().find(|info| info.name == name)
+    }
+}
+
+pub use config_schema_derive::*;
+
+// * Lets the tests below use the derive, which refers to this crate by name
+#[cfg(test)]
+extern crate self as config_schema;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(name: &'static str, section: &'static str) -> OptionInfo {
+        OptionInfo {
+            name,
+            section,
+            kind: Kind::Int,
+            comment: vec![],
+            menu: vec![],
+            cycle: false,
+            min: None,
+            max: None,
+            choices: vec![],
+            check: None,
+        }
+    }
+
+    fn no_spaces(s: &str) -> Result<(), String> {
+        if s.contains(' ') {
+            Err("should not contain spaces".to_owned())
+        } else {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn keys_drop_the_section_prefix() {
+        assert_eq!(info("proc_sorting", "proc").key(), "sorting");
+        assert_eq!(info("update_ms", "").key(), "update_ms");
+        // * Only a whole section name followed by _ is a prefix
+        assert_eq!(info("process", "proc").key(), "process");
+        assert_eq!(info("net_download", "cpu").key(), "net_download");
+    }
+
+    #[test]
+    fn clamps_to_the_range() {
+        let mut option: OptionInfo = info("update_ms", "");
+        assert_eq!(option.clamp(-5), -5);
+        option.min = Some(100);
+        assert_eq!(option.clamp(5), 100);
+        assert_eq!(option.clamp(5000), 5000);
+        option.max = Some(1000);
+        assert_eq!(option.clamp(5000), 1000);
+        assert_eq!(option.clamp(500), 500);
+        option.min = None;
+        assert_eq!(option.clamp(-5), -5);
+    }
+
+    #[test]
+    fn parses_and_validates_values() {
+        let mut option: OptionInfo = info("update_ms", "");
+        option.min = Some(100);
+        option.max = Some(1000);
+        assert_eq!(option.parse::<i64>("500"), Ok(500));
+        assert_eq!(option.parse::<i32>("100"), Ok(100));
+        assert_eq!(
+            option.parse::<i64>("50"),
+            Err("Config key \"update_ms\" should be between 100 and 1000 (was \"50\")!".to_owned())
+        );
+        assert!(option.parse::<i64>("1001").is_err());
+        assert_eq!(
+            option.parse::<i64>("fast"),
+            Err("Config key \"update_ms\" should be an integer (was \"fast\")!".to_owned())
+        );
+        assert!(option.parse::<i32>("99999999999").is_err());
+        option.max = None;
+        assert!(option
+            .parse::<i64>("50")
+            .unwrap_err()
+            .contains("should be at least 100"));
+        option.min = None;
+        option.max = Some(10);
+        assert!(option
+            .parse::<i64>("50")
+            .unwrap_err()
+            .contains("should be at most 10"));
+
+        assert_eq!(option.parse::<bool>("On"), Ok(true));
+        assert_eq!(option.parse::<bool>("0"), Ok(false));
+        assert!(option.parse::<bool>("maybe").is_err());
+
+        option.choices = vec!["full".to_owned(), "proc".to_owned()];
+        assert_eq!(option.parse::<String>("proc"), Ok("proc".to_owned()));
+        assert!(option
+            .parse::<String>("stat")
+            .unwrap_err()
+            .contains("should be one of full, proc"));
+
+        option.choices = vec![];
+        option.check = Some(no_spaces);
+        assert_eq!(option.parse::<String>("a,b"), Ok("a,b".to_owned()));
+        assert!(option
+            .parse::<String>("a b")
+            .unwrap_err()
+            .contains("should not contain spaces"));
+    }
+
+    const MENU: [&str; 2] = ["Update time.", "In milliseconds."];
+
+    #[derive(Debug, ConfigSchema)]
+    struct Small {
+        #[option(default = 2000, min = 100, max = 10000, menu = MENU)]
+        update_ms: i64,
+        #[option(default = "cpu lazy", section = "proc", cycle, choices = ["cpu lazy", "memory"])]
+        proc_sorting: String,
+        #[option(default = true, section = "proc", comment = ["Show the tree."])]
+        proc_tree: bool,
+        #[option(default = "", check = no_spaces)]
+        name: String,
+        scratch: Vec<u8>,
+    }
+
+    #[test]
+    fn derives_options_and_defaults() {
+        let small: Small = Small::default();
+        assert_eq!(small.update_ms, 2000);
+        assert_eq!(small.proc_sorting, "cpu lazy");
+        assert!(small.proc_tree);
+        assert_eq!(small.name, "");
+        assert!(small.scratch.is_empty());
+
+        let options: Vec<OptionInfo> = Small::options();
+        assert_eq!(
+            options.iter().map(|o| o.name).collect::<Vec<&str>>(),
+            vec!["update_ms", "proc_sorting", "proc_tree", "name"]
+        );
+        let update: OptionInfo = Small::option("update_ms").unwrap();
+        assert_eq!(update.kind, Kind::Int);
+        assert_eq!((update.min, update.max), (Some(100), Some(10000)));
+        assert_eq!(update.menu, vec!["Update time.", "In milliseconds."]);
+        let sorting: OptionInfo = Small::option("proc_sorting").unwrap();
+        assert_eq!(sorting.kind, Kind::Text);
+        assert_eq!(sorting.key(), "sorting");
+        assert!(sorting.cycle);
+        assert_eq!(sorting.choices, vec!["cpu lazy", "memory"]);
+        let tree: OptionInfo = Small::option("proc_tree").unwrap();
+        assert_eq!(tree.kind, Kind::Bool);
+        assert_eq!(tree.comment, vec!["Show the tree."]);
+        assert!(Small::option("name").unwrap().check.is_some());
+        assert!(Small::option("scratch").is_none());
+    }
+
+    #[test]
+    fn gets_and_sets_values() {
+        let mut small: Small = Small::default();
+        assert_eq!(small.get_value("update_ms"), Some("2000".to_owned()));
+        assert_eq!(small.get_value("proc_tree"), Some("true".to_owned()));
+        assert_eq!(small.get_value("scratch"), None);
+        assert_eq!(small.get_value("missing"), None);
+
+        assert_eq!(small.set_value("update_ms", "500"), Ok(()));
+        assert_eq!(small.update_ms, 500);
+        assert_eq!(small.set_value("proc_tree", "off"), Ok(()));
+        assert!(!small.proc_tree);
+        assert_eq!(small.set_value("proc_sorting", "memory"), Ok(()));
+        assert_eq!(small.get_value("proc_sorting"), Some("memory".to_owned()));
+
+        // * Rejected values keep the old value
+        assert!(small.set_value("update_ms", "50").is_err());
+        assert!(small.set_value("update_ms", "100000").is_err());
+        assert!(small.set_value("update_ms", "soon").is_err());
+        assert_eq!(small.update_ms, 500);
+        assert!(small.set_value("proc_tree", "sometimes").is_err());
+        assert!(!small.proc_tree);
+        assert!(small.set_value("proc_sorting", "pid").is_err());
+        assert_eq!(small.proc_sorting, "memory");
+        assert!(small.set_value("name", "a b").is_err());
+        assert_eq!(small.name, "");
+        assert_eq!(
+            small.set_value("scratch", "1"),
+            Err("Unknown config key \"scratch\"!".to_owned())
+        );
+        assert_eq!(
+            small.set_value("missing", "1"),
+            Err("Unknown config key \"missing\"!".to_owned())
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    DeriveInput, Token,
+};
+
+/// One `name = value` or bare `name` inside #[option(...)]
+struct OptionArg {
+    name: syn::Ident,
+    value: Option<syn::Expr>,
+}
+impl Parse for OptionArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: syn::Ident = input.parse()?;
+        let value: Option<syn::Expr> = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(OptionArg { name, value })
+    }
+}
+
+/// A field with an #[option(...)] attribute
+struct OptionField {
+    ident: syn::Ident,
+    ty: syn::Type,
+    section: Option<syn::Expr>,
+    default: syn::Expr,
+    comment: Option<syn::Expr>,
+    menu: Option<syn::Expr>,
+    cycle: bool,
+    min: Option<syn::Expr>,
+    max: Option<syn::Expr>,
+    choices: Option<syn::Expr>,
+    check: Option<syn::Expr>,
+}
+
+fn option_field(field: &syn::Field) -> Option<OptionField> {
+    let attr = field
+        .attrs
+        .iter()
+        .find(|attr| attr.path.is_ident("option"))?;
+    let ident: syn::Ident = field.ident.clone().unwrap();
+    let args = attr
+        .parse_args_with(Punctuated::<OptionArg, Token![,]>::parse_terminated)
+        .unwrap_or_else(|e| panic!("Malformed option attribute on {}: {}", ident, e));
+
+    let mut default: Option<syn::Expr> = None;
+    let mut option = OptionField {
+        ident: ident.clone(),
+        ty: field.ty.clone(),
+        section: None,
+        default: syn::parse_quote!(::std::default::Default::default()),
+        comment: None,
+        menu: None,
+        cycle: false,
+        min: None,
+        max: None,
+        choices: None,
+        check: None,
+    };
+    for OptionArg { name, value } in args {
+        match (name.to_string().as_str(), value) {
+            ("default", value @ Some(_)) => default = value,
+            ("section", value @ Some(_)) => option.section = value,
+            ("comment", value @ Some(_)) => option.comment = value,
+            ("menu", value @ Some(_)) => option.menu = value,
+            ("min", value @ Some(_)) => option.min = value,
+            ("max", value @ Some(_)) => option.max = value,
+            ("choices", value @ Some(_)) => option.choices = value,
+            ("check", value @ Some(_)) => option.check = value,
+            ("cycle", None) => option.cycle = true,
+            (other, _) => panic!(
+                "Unknown or malformed option argument {:?} on {}",
+                other, ident
+            ),
+        }
+    }
+    option.default = default.unwrap_or_else(|| panic!("Option {} needs a default", ident));
+    Some(option)
+}
+
+fn get_fields(item: &syn::DeriveInput) -> Vec<syn::Field> {
+    match item.data.clone() {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(syn::FieldsNamed { named, .. }),
+            ..
+        }) => named.into_iter().collect(),
+        _ => panic!("ConfigSchema can only be derived for structs with named fields"),
+    }
+}
+
+/// Lines from an array or a constant as a Vec<String>
+fn lines(expr: &Option<syn::Expr>) -> TokenStream2 {
+    match expr {
+        Some(expr) => quote! { (#expr).iter().map(|s| s.to_string()).collect() },
+        None => quote! { ::std::vec::Vec::new() },
+    }
+}
+
+fn optional(expr: &Option<syn::Expr>) -> TokenStream2 {
+    match expr {
+        Some(expr) => quote! { ::std::option::Option::Some(#expr) },
+        None => quote! { ::std::option::Option::None },
+    }
+}
+
+#[proc_macro_derive(ConfigSchema, attributes(option))]
+pub fn derive_config_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let struct_ident = &input.ident;
+    let fields: Vec<syn::Field> = get_fields(&input);
+    let options: Vec<OptionField> = fields.iter().filter_map(option_field).collect();
+
+    let infos = options.iter().map(|option| {
+        let ty = &option.ty;
+        let name: String = option.ident.to_string();
+        let section = match &option.section {
+            Some(section) => quote! { #section },
+            None => quote! { "" },
+        };
+        let comment = lines(&option.comment);
+        let menu = lines(&option.menu);
+        let cycle = option.cycle;
+        let min = optional(&option.min);
+        let max = optional(&option.max);
+        let choices = lines(&option.choices);
+        let check = match &option.check {
+            Some(check) => quote! {
+                ::std::option::Option::Some(#check as fn(&str) -> ::std::result::Result<(), ::std::string::String>)
+            },
+            None => quote! { ::std::option::Option::None },
+        };
+        quote! {
+            ::config_schema::OptionInfo {
+                name: #name,
+                section: #section,
+                kind: <#ty as ::config_schema::ConfigValue>::KIND,
+                comment: #comment,
+                menu: #menu,
+                cycle: #cycle,
+                min: #min,
+                max: #max,
+                choices: #choices,
+                check: #check,
+            }
+        }
+    });
+
+    let getters = options.iter().map(|option| {
+        let ident = &option.ident;
+        let name: String = ident.to_string();
+        quote! {
+            #name => ::std::option::Option::Some(::config_schema::ConfigValue::write_value(&self.#ident)),
+        }
+    });
+
+    let setters = options.iter().map(|option| {
+        let ident = &option.ident;
+        let name: String = ident.to_string();
+        quote! {
+            #name => self.#ident = info.parse(value)?,
+        }
+    });
+
+    // * Options start at their default and every other field at its Default
+    let defaults = fields.iter().map(|field| {
+        let ident = field.ident.as_ref().unwrap();
+        match options.iter().find(|option| &option.ident == ident) {
+            Some(option) => {
+                let default = &option.default;
+                quote! { #ident: (#default).into(), }
+            }
+            None => quote! { #ident: ::std::default::Default::default(), },
+        }
+    });
+
+    let gen = quote! {
+        impl ::config_schema::ConfigSchema for #struct_ident {
+            fn options() -> ::std::vec::Vec<::config_schema::OptionInfo> {
+                vec![#(#infos,)*]
+            }
+
+            fn get_value(&self, name: &str) -> ::std::option::Option<::std::string::String> {
+                match name {
+                    #(#getters)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+
+            fn set_value(
+                &mut self,
+                name: &str,
+                value: &str,
+            ) -> ::std::result::Result<(), ::std::string::String> {
+                let info: ::config_schema::OptionInfo = match <Self as ::config_schema::ConfigSchema>::option(name) {
+                    ::std::option::Option::Some(info) => info,
+                    ::std::option::Option::None => {
+                        return ::std::result::Result::Err(format!("Unknown config key {:?}!", name))
+                    }
+                };
+                match name {
+                    #(#setters)*
+                    _ => (),
+                }
+                ::std::result::Result::Ok(())
+            }
+        }
+        impl ::std::default::Default for #struct_ident {
+            fn default() -> Self {
+                Self {
+                    #(#defaults)*
+                }
+            }
+        }
+    };
+    gen.into()
+}
@@ -9,6 +9,8 @@ pub trait FromMapDefault: Default {
     type Value: Default + Copy + From<String>;
     fn from_map_default(map: HashMap<String, Self::Value>) -> Self;
     fn default_map() -> HashMap<String, Self::Value>;
+    /// Field names in the order they are declared
+    fn keys() -> Vec<&'static str>;
 }
 
 pub use from_map_derive::*;
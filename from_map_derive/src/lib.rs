@@ -154,6 +154,9 @@ pub fn derive_from_map_default(input: TokenStream) -> TokenStream {
                     #body
                 }
             }
+            fn keys() -> Vec<&'static str> {
+                vec![#(stringify!(#items)),*]
+            }
             fn default_map() -> HashMap<String, <Self as FromMapDefault>::Value> {
                 let map = vec![
                     #default_map
@@ -1,14 +1,255 @@
+use std::collections::HashMap;
+
+/// A color of a gradient at a position from 0.0 to 1.0, the color is a hex string and empty if unset
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stop {
+    pub position: f64,
+    pub color: String,
+}
+
+/// Color space the colors between two stops are mixed in
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Space {
+    Rgb,
+    Oklab,
+    /// Lightness, chroma and hue, the hue goes the short way around the color wheel
+    Oklch,
+    /// Like Oklch with the hue going the long way around
+    OklchLong,
+}
+
+/// Names of the color spaces as used in the config
+pub const SPACES: [&str; 4] = ["rgb", "oklab", "oklch", "oklch-long"];
+
+impl Space {
+    pub fn from_config(s: &str) -> Self {
+        match s {
+            "oklab" => Space::Oklab,
+            "oklch" => Space::Oklch,
+            "oklch-long" => Space::OklchLong,
+            _ => Space::Rgb,
+        }
+    }
+}
+
 pub trait Gradient {
-    fn gradient(
-        &self,
-    ) -> ::std::collections::HashMap<
-        ::std::string::String,
-        (
-            ::std::string::String,
-            ::std::string::String,
-            ::std::string::String,
+    /// The stops of every gradient by name, sorted by position
+    fn gradient(&self) -> HashMap<String, Vec<Stop>>;
+}
+
+/// Colors at `steps` evenly spaced positions from 0.0 to 1.0, stops before the first or after
+/// the last stop repeat its color. `stops` must be sorted by position and not be empty
+pub fn interpolate(stops: &[(f64, (u8, u8, u8))], space: Space, steps: usize) -> Vec<(u8, u8, u8)> {
+    let mut out: Vec<(u8, u8, u8)> = Vec::with_capacity(steps);
+    for i in 0..steps {
+        let at: f64 = if steps > 1 {
+            i as f64 / (steps - 1) as f64
+        } else {
+            0.0
+        };
+        let next: usize = stops
+            .iter()
+            .position(|(p, _)| *p > at)
+            .unwrap_or(stops.len());
+        let color: (u8, u8, u8) = if next == 0 {
+            stops[0].1
+        } else if next == stops.len() {
+            stops[next - 1].1
+        } else {
+            let (p0, c0) = stops[next - 1];
+            let (p1, c1) = stops[next];
+            mix(c0, c1, (at - p0) / (p1 - p0), space)
+        };
+        out.push(color);
+    }
+    out
+}
+
+/// Mix two colors, `t` is 0.0 for the first and 1.0 for the second
+pub fn mix(a: (u8, u8, u8), b: (u8, u8, u8), t: f64, space: Space) -> (u8, u8, u8) {
+    let lerp = |x: f64, y: f64| -> f64 { x + (y - x) * t };
+    match space {
+        Space::Rgb => (
+            lerp(a.0 as f64, b.0 as f64).round() as u8,
+            lerp(a.1 as f64, b.1 as f64).round() as u8,
+            lerp(a.2 as f64, b.2 as f64).round() as u8,
         ),
-    >;
+        Space::Oklab => {
+            let (l0, a0, b0) = to_oklab(a);
+            let (l1, a1, b1) = to_oklab(b);
+            from_oklab((lerp(l0, l1), lerp(a0, a1), lerp(b0, b1)))
+        }
+        Space::Oklch | Space::OklchLong => {
+            let (l0, c0, h0) = to_oklch(a);
+            let (l1, c1, h1) = to_oklch(b);
+            // * Gray has no hue, it takes the hue of the other color so no other hues show up
+            let h0: f64 = if c0 < 1e-4 { h1 } else { h0 };
+            let h1: f64 = if c1 < 1e-4 { h0 } else { h1 };
+            let mut dh: f64 = h1 - h0;
+            if dh > 180.0 {
+                dh -= 360.0;
+            } else if dh < -180.0 {
+                dh += 360.0;
+            }
+            if space == Space::OklchLong && dh != 0.0 {
+                dh -= 360.0 * dh.signum();
+            }
+            from_oklch((lerp(l0, l1), lerp(c0, c1), h0 + dh * t))
+        }
+    }
+}
+
+fn to_linear(c: u8) -> f64 {
+    let c: f64 = c as f64 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn from_linear(c: f64) -> u8 {
+    let c: f64 = if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    (c * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+/// sRGB to OKLab as lightness and the a and b axes
+pub fn to_oklab(c: (u8, u8, u8)) -> (f64, f64, f64) {
+    let (r, g, b) = (to_linear(c.0), to_linear(c.1), to_linear(c.2));
+    let l: f64 = (0.4122214708 * r + 0.5363325363 * g + 0.0514459929 * b).cbrt();
+    let m: f64 = (0.2119034982 * r + 0.6806995451 * g + 0.1073969566 * b).cbrt();
+    let s: f64 = (0.0883024619 * r + 0.2817188376 * g + 0.6299787005 * b).cbrt();
+    (
+        0.2104542553 * l + 0.7936177850 * m - 0.0040720468 * s,
+        1.9779984951 * l - 2.4285922050 * m + 0.4505937099 * s,
+        0.0259040371 * l + 0.7827717662 * m - 0.8086757660 * s,
+    )
+}
+
+/// OKLab to sRGB, colors outside of sRGB are clipped
+pub fn from_oklab(lab: (f64, f64, f64)) -> (u8, u8, u8) {
+    let l: f64 = (lab.0 + 0.3963377774 * lab.1 + 0.2158037573 * lab.2).powi(3);
+    let m: f64 = (lab.0 - 0.1055613458 * lab.1 - 0.0638541728 * lab.2).powi(3);
+    let s: f64 = (lab.0 - 0.0894841775 * lab.1 - 1.2914855480 * lab.2).powi(3);
+    (
+        from_linear(4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s),
+        from_linear(-1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s),
+        from_linear(-0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s),
+    )
+}
+
+fn to_oklch(c: (u8, u8, u8)) -> (f64, f64, f64) {
+    let (l, a, b) = to_oklab(c);
+    (l, (a * a + b * b).sqrt(), b.atan2(a).to_degrees())
+}
+
+fn from_oklch(lch: (f64, f64, f64)) -> (u8, u8, u8) {
+    let h: f64 = lch.2.to_radians();
+    from_oklab((lch.0, lch.1 * h.cos(), lch.1 * h.sin()))
 }
 
 pub use ::gradient_derive::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPACES_ALL: [Space; 4] = [Space::Rgb, Space::Oklab, Space::Oklch, Space::OklchLong];
+    const RED: (u8, u8, u8) = (255, 0, 0);
+    const BLUE: (u8, u8, u8) = (0, 0, 255);
+    const BLACK: (u8, u8, u8) = (0, 0, 0);
+    const WHITE: (u8, u8, u8) = (255, 255, 255);
+
+    #[test]
+    fn reads_spaces_from_config() {
+        for (name, space) in SPACES.iter().zip(SPACES_ALL.iter()) {
+            assert_eq!(Space::from_config(name), *space);
+        }
+        assert_eq!(Space::from_config("hsl"), Space::Rgb);
+    }
+
+    #[test]
+    fn oklab_round_trips() {
+        for c in [
+            BLACK,
+            WHITE,
+            RED,
+            (0, 255, 0),
+            BLUE,
+            (128, 128, 128),
+            (12, 200, 99),
+            (250, 3, 170),
+        ]
+        .iter()
+        {
+            assert_eq!(from_oklab(to_oklab(*c)), *c);
+            assert_eq!(from_oklch(to_oklch(*c)), *c);
+        }
+        let (l, a, b) = to_oklab(WHITE);
+        assert!((l - 1.0).abs() < 1e-4 && a.abs() < 1e-4 && b.abs() < 1e-4);
+        assert_eq!(to_oklab(BLACK), (0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn mix_keeps_the_endpoints() {
+        for space in SPACES_ALL.iter() {
+            assert_eq!(mix(RED, BLUE, 0.0, *space), RED, "{:?}", space);
+            assert_eq!(mix(RED, BLUE, 1.0, *space), BLUE, "{:?}", space);
+            assert_eq!(mix(BLACK, WHITE, 0.0, *space), BLACK, "{:?}", space);
+            assert_eq!(mix(BLACK, WHITE, 1.0, *space), WHITE, "{:?}", space);
+        }
+    }
+
+    #[test]
+    fn mixes_midpoints_in_each_space() {
+        assert_eq!(mix(RED, BLUE, 0.5, Space::Rgb), (128, 0, 128));
+        assert_eq!(mix(BLACK, WHITE, 0.5, Space::Rgb), (128, 128, 128));
+
+        // * OKLab is perceptually even, so the middle gray is lighter than the rgb one
+        assert_eq!(mix(BLACK, WHITE, 0.5, Space::Oklab), (99, 99, 99));
+        assert_eq!(mix(RED, BLUE, 0.5, Space::Oklab), (140, 83, 162));
+
+        // * Gray takes the hue of the other color, so gray to gray stays gray
+        assert_eq!(mix(BLACK, WHITE, 0.5, Space::Oklch), (99, 99, 99));
+        assert_eq!(mix(BLACK, WHITE, 0.5, Space::OklchLong), (99, 99, 99));
+
+        // * Red to blue goes through purple the short way and through green the long way
+        let short: (u8, u8, u8) = mix(RED, BLUE, 0.5, Space::Oklch);
+        assert!(short.0 > short.1 && short.2 > short.1, "{:?}", short);
+        let long: (u8, u8, u8) = mix(RED, BLUE, 0.5, Space::OklchLong);
+        assert!(long.1 > long.0 && long.1 > long.2, "{:?}", long);
+    }
+
+    #[test]
+    fn interpolates_between_stops() {
+        let stops: [(f64, (u8, u8, u8)); 3] = [(0.0, BLACK), (0.5, RED), (1.0, WHITE)];
+        for space in SPACES_ALL.iter() {
+            let colors: Vec<(u8, u8, u8)> = interpolate(&stops, *space, 5);
+            assert_eq!(colors.len(), 5);
+            assert_eq!(colors[0], BLACK, "{:?}", space);
+            assert_eq!(colors[2], RED, "{:?}", space);
+            assert_eq!(colors[4], WHITE, "{:?}", space);
+            assert_eq!(colors[1], mix(BLACK, RED, 0.5, *space), "{:?}", space);
+            assert_eq!(colors[3], mix(RED, WHITE, 0.5, *space), "{:?}", space);
+        }
+        assert_eq!(interpolate(&stops, Space::Rgb, 3), vec![BLACK, RED, WHITE]);
+        assert_eq!(interpolate(&stops, Space::Rgb, 1), vec![BLACK]);
+        assert!(interpolate(&stops, Space::Rgb, 0).is_empty());
+    }
+
+    #[test]
+    fn repeats_the_outer_stops() {
+        let stops: [(f64, (u8, u8, u8)); 2] = [(0.25, RED), (0.75, BLUE)];
+        let colors: Vec<(u8, u8, u8)> = interpolate(&stops, Space::Rgb, 5);
+        assert_eq!(colors[0], RED);
+        assert_eq!(colors[1], RED);
+        assert_eq!(colors[2], (128, 0, 128));
+        assert_eq!(colors[3], BLUE);
+        assert_eq!(colors[4], BLUE);
+        assert_eq!(interpolate(&[(0.5, RED)], Space::Oklab, 3), vec![RED; 3]);
+    }
+}
@@ -3,168 +3,198 @@ use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::{parse_macro_input, DeriveInput};
 
-const PREFIXES: [&str; 9] = [
-    "temp",
-    "cpu",
-    "free",
-    "cached",
-    "available",
-    "used",
-    "download",
-    "upload",
-    "process",
-];
-const SUFFIXES: [&str; 3] = ["start", "mid", "end"];
+/// Positions of the stops named by a field suffix, other stops need a #[stop("name", position)] attribute
+const SUFFIXES: [(&str, f64); 3] = [("_start", 0.0), ("_mid", 0.5), ("_end", 1.0)];
 
-fn get_items(item: &syn::DeriveInput) -> Vec<proc_macro2::Ident> {
-    let mut items = vec![];
-    if let syn::Data::Struct(syn::DataStruct {
-        fields: syn::Fields::Named(syn::FieldsNamed { named, .. }),
-        ..
-    }) = item.data.to_owned()
-    {
-        let mut iter = named.into_iter();
-        while let Some(syn::Field {
-            ident: Some(ident), ..
-        }) = iter.next()
-        {
-            items.push(ident);
-        }
-    }
+/// Each gradient by name with its stop positions and fields
+type Gradients = Vec<(String, Vec<(f64, syn::Ident)>)>;
 
-    items
+/// The gradient name and position of a field, from its stop attribute or else its name
+fn field_stop(field: &syn::Field) -> syn::Result<Option<(String, f64)>> {
+    if let Some(attr) = field.attrs.iter().find(|attr| attr.path.is_ident("stop")) {
+        let args = attr.parse_args_with(
+            syn::punctuated::Punctuated::<syn::Lit, syn::Token![,]>::parse_terminated,
+        )?;
+        return match (args.iter().next(), args.iter().nth(1), args.len()) {
+            (Some(syn::Lit::Str(name)), Some(syn::Lit::Float(position)), 2) => {
+                let value: f64 = position.base10_parse::<f64>()?;
+                if !value.is_finite() || !(0.0..=1.0).contains(&value) {
+                    return Err(syn::Error::new_spanned(
+                        position,
+                        "A stop position should be between 0.0 and 1.0",
+                    ));
+                }
+                Ok(Some((name.value(), value)))
+            }
+            _ => Err(syn::Error::new_spanned(
+                attr,
+                "A stop attribute should be #[stop(\"name\", position)]",
+            )),
+        };
+    }
+    let name: String = match field.ident.as_ref() {
+        Some(ident) => ident.to_string(),
+        None => return Ok(None),
+    };
+    Ok(SUFFIXES.iter().find_map(|(suffix, position)| {
+        name.strip_suffix(suffix)
+            .map(|prefix| (prefix.to_owned(), *position))
+    }))
 }
 
-fn get_defaults(item: &syn::DeriveInput) -> Vec<(proc_macro2::Ident, Option<String>)> {
-    match item.data.clone() {
+/// Fields of each gradient in order of position, every gradient needs at least two stops
+fn gradient_stops(input: &DeriveInput) -> syn::Result<Gradients> {
+    let fields = match &input.data {
         syn::Data::Struct(syn::DataStruct {
             fields: syn::Fields::Named(syn::FieldsNamed { named, .. }),
             ..
-        }) => named
-            .into_iter()
-            .map(|field| {
-                (
-                    field.ident.unwrap(),
-                    field.attrs.get(0).map(attr_str).flatten(),
-                )
-            })
-            .collect(),
-        _ => unreachable!(),
-    }
-}
+        }) => named,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "Gradient can only be derived for structs with named fields",
+            ))
+        }
+    };
 
-fn attr_ident(attr: &syn::Attribute) -> Option<&syn::Ident> {
-    let syn::Path { segments, .. } = &attr.path;
-    if let Some(syn::PathSegment { ident, .. }) = segments.iter().next() {
-        Some(ident)
-    } else {
-        None
+    let mut gradients: Gradients = vec![];
+    for field in fields {
+        if let Some((name, position)) = field_stop(field)? {
+            let ident: syn::Ident = field.ident.clone().unwrap();
+            match gradients.iter_mut().find(|(n, _)| *n == name) {
+                Some((_, stops)) => stops.push((position, ident)),
+                None => gradients.push((name, vec![(position, ident)])),
+            }
+        }
     }
-}
-fn attr_str(attr: &syn::Attribute) -> Option<String> {
-    format!(
-        "{}",
-        syn::parse::<TokenStream2>(TokenStream::from(attr.tokens.clone())).unwrap()
-    )
-    .split('"')
-    .nth(1)
-    .map(<_ as ToString>::to_string)
-}
-fn attr_lit(attr: &syn::Attribute) -> Option<proc_macro2::Literal> {
-    match attr.tokens.clone().into_iter().nth(1) {
-        Some(proc_macro2::TokenTree::Literal(lit)) => Some(lit),
-        _ => None,
+    for (name, stops) in gradients.iter_mut() {
+        if stops.len() < 2 {
+            return Err(syn::Error::new_spanned(
+                &stops[0].1,
+                format!("Gradient {:?} needs at least two stops", name),
+            ));
+        }
+        // * Positions are checked to be finite above, so they always compare
+        stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
     }
+    Ok(gradients)
 }
 
-fn literal_to_ident(lit: proc_macro2::Literal) -> syn::Ident {
-    let mut s = format!("{}", lit);
-    s.pop();
-    s.remove(0);
-    syn::Ident::new(&s, proc_macro2::Span::call_site())
-}
+#[proc_macro_derive(Gradient, attributes(stop))]
+pub fn derive_gradient(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let struct_ident = &input.ident;
 
-fn get_value_ty(item: &syn::DeriveInput) -> Option<syn::Ident> {
-    item.attrs
-        .iter()
-        .map(|attr| (attr_ident(attr).unwrap(), attr_lit(attr).unwrap()))
-        .find(|(ident, _)| ident == &&syn::Ident::new("value_type", proc_macro2::Span::call_site()))
-        .map(|optional| literal_to_ident(optional.1))
-}
+    let gradients = match gradient_stops(&input) {
+        Ok(g) => g,
+        Err(e) => return e.to_compile_error().into(),
+    };
 
-fn gradient_fields() -> Vec<String> {
-    PREFIXES
+    // Each gradient is its stops with the colors as hex strings, empty for unset colors
+    let body = gradients
         .iter()
-        .map(|prefix| {
-            SUFFIXES.iter().fold(vec![], |mut acc, suffix| {
-                acc.push(format!("{}_{}", prefix, suffix));
-                acc
-            })
+        .map(|(name, stops)| {
+            let positions = stops.iter().map(|(position, _)| position);
+            let fields = stops.iter().map(|(_, field)| field);
+            quote! {
+                gradient_map.insert(
+                    #name.to_string(),
+                    vec![
+                        #(::gradient::Stop {
+                            position: #positions,
+                            color: format!("{:X}", self.#fields),
+                        },)*
+                    ],
+                );
+            }
         })
-        .flatten()
-        .collect()
-}
+        .fold(TokenStream2::new(), |mut acc, cur| {
+            acc.extend(cur);
+            acc
+        });
 
-fn has_all_gradient_fields(s: &syn::Data) -> Result<(), Vec<String>> {
-    let field_names: Vec<String> = match s {
-        syn::Data::Struct(ds) => {
-            match ds.fields.clone() {
-                syn::Fields::Named(syn::FieldsNamed { named: fields, .. }) => fields
-                    .iter()
-                    // Filter shouldn't lose any, because named exists, but what can ya do :^)
-                    .filter_map(|field| Some(field.ident.clone()?.to_string()))
-                    .collect(),
-                _ => {
-                    panic!("Unnamed fields are not supported >:^(")
-                }
+    let gen = quote! {
+        impl ::gradient::Gradient for #struct_ident {
+            fn gradient(
+                &self,
+            ) -> ::std::collections::HashMap<::std::string::String, ::std::vec::Vec<::gradient::Stop>> {
+                let mut gradient_map = ::std::collections::HashMap::new();
+                #body
+                gradient_map
             }
         }
-        _ => panic!("Don't be an ass, Jake"),
     };
+    gen.into()
+}
 
-    let missing: Vec<String> = gradient_fields()
-        .iter()
-        .filter(|field_name| !field_names.contains(field_name))
-        .map(<_ as ToString>::to_string)
-        .collect();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if missing.is_empty() {
-        Ok(())
-    } else {
-        Err(missing)
-    }
-}
+    type Named = Vec<(String, Vec<(f64, String)>)>;
 
-#[proc_macro_derive(Gradient)]
-pub fn derive_gradient(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
-    let items = get_items(&input);
-    match has_all_gradient_fields(&input.data) {
-        Ok(_) => {}
-        Err(missing) => {
-            panic!(format!("Missing fields: {:#?}", missing))
-        }
+    fn stops(source: &str) -> syn::Result<Named> {
+        let input: DeriveInput = syn::parse_str(source).unwrap();
+        gradient_stops(&input).map(|gradients| {
+            gradients
+                .into_iter()
+                .map(|(name, stops)| {
+                    (
+                        name,
+                        stops
+                            .into_iter()
+                            .map(|(position, ident)| (position, ident.to_string()))
+                            .collect(),
+                    )
+                })
+                .collect()
+        })
     }
-    let struct_ident = &input.ident;
-
-    let gen = quote! {
-        impl ::gradient::Gradient for #struct_ident {
-            fn gradient(
-                &self,
-            ) -> ::std::collections::HashMap<
-                ::std::string::String,
-                (
-                    ::std::string::String,
-                    ::std::string::String,
-                    ::std::string::String,
-                ),
-            > {
-                let mut gradient_map = ::std::collections::HashMap::new();
 
+    #[test]
+    fn orders_stops_by_position() {
+        let gradients = stops(
+            "struct T { temp_end: u32, #[stop(\"temp\", 0.25)] low: u32, temp_start: u32, other: u32 }",
+        )
+        .unwrap();
+        assert_eq!(
+            gradients,
+            vec![(
+                "temp".to_owned(),
+                vec![
+                    (0.0, "temp_start".to_owned()),
+                    (0.25, "low".to_owned()),
+                    (1.0, "temp_end".to_owned()),
+                ]
+            )]
+        );
+    }
 
-                gradient_map
+    #[test]
+    fn rejects_bad_stops() {
+        for (source, error) in [
+            (
+                "struct T { a_start: u32, #[stop(\"a\", 1.5)] b: u32 }",
+                "between 0.0 and 1.0",
+            ),
+            (
+                "struct T { a_start: u32, #[stop(\"a\", 1e400)] b: u32 }",
+                "between 0.0 and 1.0",
+            ),
+            ("struct T { a_start: u32, #[stop(\"a\", -0.5)] b: u32 }", ""),
+            (
+                "struct T { a_start: u32, #[stop(\"a\")] b: u32 }",
+                "should be #[stop",
+            ),
+            ("struct T { a_start: u32 }", "needs at least two stops"),
+            ("struct T(u32);", "named fields"),
+        ]
+        .iter()
+        {
+            match stops(source) {
+                Ok(_) => panic!("{} should not derive", source),
+                Err(e) => assert!(e.to_string().contains(error), "{}: {}", source, e),
             }
         }
-    };
-    gen.into()
+    }
 }
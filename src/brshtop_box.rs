@@ -7,6 +7,7 @@ use {
         error::*,
         fx,
         key::Key,
+        layout::{parse_sizes, Layout, LayoutBorder, DEFAULT_SIZES},
         membox::MemBox,
         menu::Menu,
         mv,
@@ -23,7 +24,7 @@ use {
         collections::HashMap,
         convert::TryFrom,
         sync::{Mutex, MutexGuard},
-        time::SystemTime,
+        time::{Duration, SystemTime},
     },
     uname::uname,
 };
@@ -54,6 +55,15 @@ pub struct BrshtopBox {
     height: u32,
     proc_mode: bool,
     stat_mode: bool,
+    hidden: bool,
+    layout_rect: Option<(u32, u32, u32, u32)>,
+    focused: String,
+    zoomed: String,
+    sizes: (u32, u32, u32),
+    /// The layouts option with the borders dragged this run, saved to the config if save_sizes is set
+    layouts: String,
+    dragging: String,
+    drag_border: Option<LayoutBorder>,
     out: String,
     bg: String,
     _b_cpu_h: i32,
@@ -65,6 +75,8 @@ pub struct BrshtopBox {
     clock_len: u32,
     resized: bool,
     clock_custom_format: HashMap<String, String>,
+    /// Shown in place of the clock until the time it expires
+    message: Option<(String, SystemTime)>,
 }
 impl BrshtopBox {
     pub fn new(config: &Config, ARG_MODE: ViewMode) -> Self {
@@ -106,6 +118,14 @@ impl BrshtopBox {
             height: 0,
             proc_mode: proc_mode_mut,
             stat_mode: false,
+            hidden: false,
+            layout_rect: None,
+            focused: String::from("proc"),
+            zoomed: String::from(""),
+            sizes: parse_sizes(&config.box_sizes).unwrap_or(DEFAULT_SIZES),
+            layouts: config.layouts.clone(),
+            dragging: String::from(""),
+            drag_border: None,
             out: String::from(""),
             bg: String::from(""),
             _b_cpu_h: 0,
@@ -117,6 +137,7 @@ impl BrshtopBox {
             clock_len: 0,
             resized: false,
             clock_custom_format: HashMap::<String, String>::new(),
+            message: None,
         }
     }
 
@@ -131,6 +152,38 @@ impl BrshtopBox {
         net_box: &mut NetBox,
         proc_box: &mut ProcBox,
     ) {
+        // * A zoomed box takes the whole terminal, otherwise a layout from the config replaces the view mode
+        // * Boxes without a rectangle are hidden
+        let rects: Option<HashMap<String, (u32, u32, u32, u32)>> = if self.zoomed.len() > 0 {
+            let mut zoom_rect = HashMap::<String, (u32, u32, u32, u32)>::new();
+            zoom_rect.insert(
+                self.zoomed.clone(),
+                (1, 1, term.get_width() as u32, term.get_height() as u32),
+            );
+            Some(zoom_rect)
+        } else {
+            match Layout::find(&self.layouts, &CONFIG.layout) {
+                Some(layout) => {
+                    Some(layout.rects(1, 1, term.get_width() as u32, term.get_height() as u32))
+                }
+                None => None,
+            }
+        };
+        // * Without a layout the boxes are sized by the percentages in sizes
+        let (cpu_h, mem_h, side_w) = self.sizes;
+        cpu_box.set_parent_height_p(cpu_h);
+        mem_box.set_parent_height_p(mem_h);
+        mem_box.set_parent_width_p(side_w);
+        net_box.set_parent_height_p(100 - cpu_h - mem_h);
+        net_box.set_parent_width_p(side_w);
+        proc_box.set_parent_height_p(100 - cpu_h);
+        proc_box.set_parent_width_p(100 - side_w);
+
+        cpu_box.set_parent(self.layout_parent(cpu_box.get_parent(), &rects));
+        mem_box.set_parent(self.layout_parent(mem_box.get_parent(), &rects));
+        net_box.set_parent(self.layout_parent(net_box.get_parent(), &rects));
+        proc_box.set_parent(self.layout_parent(proc_box.get_parent(), &rects));
+
         for sub in boxes {
             match sub {
                 Boxes::BrshtopBox => (),
@@ -158,6 +211,208 @@ impl BrshtopBox {
         }
     }
 
+    /// Copy the view mode to a box parent and give it its layout rectangle, if a layout is active
+    fn layout_parent(
+        &self,
+        parent: BrshtopBox,
+        rects: &Option<HashMap<String, (u32, u32, u32, u32)>>,
+    ) -> BrshtopBox {
+        let mut parent = parent;
+        parent.set_focused(self.get_focused());
+        match rects {
+            Some(r) => {
+                parent.set_proc_mode(false);
+                parent.set_stat_mode(false);
+                parent.set_layout_rect(r.get(&parent.get_name()).copied());
+                parent.set_hidden(parent.get_layout_rect().is_none());
+            }
+            None => {
+                parent.set_proc_mode(self.get_proc_mode());
+                parent.set_stat_mode(self.get_stat_mode());
+                parent.set_layout_rect(None);
+                parent.set_hidden(false);
+            }
+        }
+        parent
+    }
+
+    /// Zoom the named box to full screen and focus it, or zoom out if it is already zoomed
+    pub fn toggle_zoom(&mut self, name: String) {
+        if self.zoomed == name {
+            self.zoomed = String::default();
+        } else {
+            self.zoomed = name.clone();
+        }
+        self.focused = name;
+    }
+
+    /// Move focus to the next or previous box that is shown, in the order cpu, mem, net, proc
+    pub fn cycle_focus(&mut self, forward: bool, parents: Vec<BrshtopBox>) {
+        let shown: Vec<String> = parents
+            .iter()
+            .filter(|p| p.get_shown())
+            .map(|p| p.get_name())
+            .collect();
+        if shown.len() == 0 {
+            return;
+        }
+        let i: usize = match shown.iter().position(|n| *n == self.focused) {
+            Some(i) if forward => (i + 1) % shown.len(),
+            Some(i) => (i + shown.len() - 1) % shown.len(),
+            None => 0,
+        };
+        self.focused = shown[i].clone();
+    }
+
+    /// Start dragging the border between boxes under pos, returns false if there is none
+    pub fn start_drag(
+        &mut self,
+        pos: (i32, i32),
+        term: &Term,
+        CONFIG: &Config,
+        parents: Vec<BrshtopBox>,
+    ) -> bool {
+        self.dragging = String::default();
+        self.drag_border = None;
+        if self.zoomed.len() > 0 {
+            return false;
+        }
+
+        // * A border can be grabbed on either of the two lines where neighbouring boxes meet
+        if let Some(layout) = Layout::find(&self.layouts, &CONFIG.layout) {
+            self.drag_border = layout
+                .borders(1, 1, term.get_width() as u32, term.get_height() as u32)
+                .into_iter()
+                .find(|b| {
+                    let (across, along) = if b.vertical { pos } else { (pos.1, pos.0) };
+                    (across == b.pos as i32 || across == b.pos as i32 - 1)
+                        && along >= b.from as i32
+                        && along <= b.to as i32
+                });
+            if self.drag_border.is_some() {
+                self.dragging = "layout".to_owned();
+            }
+            return self.drag_border.is_some();
+        }
+
+        let shown: HashMap<String, BrshtopBox> = parents
+            .into_iter()
+            .filter(|p| p.get_shown())
+            .map(|p| (p.get_name(), p))
+            .collect();
+        if let (Some(proc), true) = (
+            shown.get("proc"),
+            shown.contains_key("mem") || shown.contains_key("net"),
+        ) {
+            if (pos.0 == proc.get_x() as i32 || pos.0 == proc.get_x() as i32 - 1)
+                && pos.1 >= proc.get_y() as i32
+            {
+                self.dragging = "side_w".to_owned();
+            }
+        }
+        if let (Some(mem), Some(net), true) =
+            (shown.get("mem"), shown.get("net"), self.dragging.len() == 0)
+        {
+            if (pos.1 == net.get_y() as i32 || pos.1 == net.get_y() as i32 - 1)
+                && pos.0 < (mem.get_x() + mem.get_width()) as i32
+            {
+                self.dragging = "mem_h".to_owned();
+            }
+        }
+        if let (Some(cpu), true) = (shown.get("cpu"), self.dragging.len() == 0) {
+            let bottom: i32 = (cpu.get_y() + cpu.get_height()) as i32;
+            if shown.len() > 1 && (pos.1 == bottom || pos.1 == bottom - 1) {
+                self.dragging = "cpu_h".to_owned();
+            }
+        }
+        self.dragging.len() > 0
+    }
+
+    /// Move the dragged border to pos, the boxes are resized on the next refresh
+    pub fn drag(&mut self, pos: (i32, i32), term: &Term, CONFIG: &Config) {
+        let (width, height) = (term.get_width() as u32, term.get_height() as u32);
+        let (x, y) = (pos.0.max(1) as u32, pos.1.max(1) as u32);
+        let (cpu_h, mem_h, side_w) = self.sizes;
+        match self.dragging.as_str() {
+            "layout" => {
+                if let (Some(mut layout), Some(border)) = (
+                    Layout::find(&self.layouts, &CONFIG.layout),
+                    self.drag_border.clone(),
+                ) {
+                    layout.drag(
+                        &border,
+                        if border.vertical { x } else { y },
+                        1,
+                        1,
+                        width,
+                        height,
+                    );
+                    self.layouts = layout.replace_in(&self.layouts);
+                }
+            }
+            "cpu_h" => {
+                // * The mem and net boxes keep their share of what is left
+                let new_cpu_h: u32 = (y * 100 / height).max(10).min(80);
+                let new_mem_h: u32 = (mem_h * (100 - new_cpu_h) / (100 - cpu_h))
+                    .max(10)
+                    .min(90 - new_cpu_h);
+                self.sizes = (new_cpu_h, new_mem_h, side_w);
+            }
+            "mem_h" => {
+                let new_mem_h: u32 = (y * 100 / height).max(cpu_h + 10).min(90) - cpu_h;
+                self.sizes = (cpu_h, new_mem_h, side_w);
+            }
+            "side_w" => {
+                self.sizes = (cpu_h, mem_h, (x * 100 / width).max(10).min(90));
+            }
+            _ => (),
+        }
+    }
+
+    /// Stop dragging and save the new sizes if save_sizes is set
+    pub fn end_drag(&mut self, CONFIG: &mut Config) {
+        if CONFIG.save_sizes && self.dragging.len() > 0 {
+            if self.dragging == "layout".to_owned() {
+                CONFIG.layouts = self.layouts.clone();
+                CONFIG.mark_changed("layouts");
+            } else {
+                CONFIG.box_sizes = format!("{},{},{}", self.sizes.0, self.sizes.1, self.sizes.2);
+                CONFIG.mark_changed("box_sizes");
+            }
+            if let Err(e) = CONFIG.save_config() {
+                errlog(format!("Unable to save the box sizes: {}", e));
+            }
+        }
+        self.dragging = String::default();
+        self.drag_border = None;
+    }
+
+    /// Name of the shown box parent with x, y inside its border, empty if there is none
+    pub fn box_at(pos: (i32, i32), parents: Vec<BrshtopBox>) -> String {
+        for p in parents.iter().filter(|p| p.get_shown()) {
+            if pos.0 >= p.get_x() as i32
+                && pos.0 < (p.get_x() + p.get_width()) as i32
+                && pos.1 >= p.get_y() as i32
+                && pos.1 < (p.get_y() + p.get_height()) as i32
+            {
+                return p.get_name();
+            }
+        }
+        String::default()
+    }
+
+    /// If this box parent is drawn in the current view mode or layout
+    pub fn get_shown(&self) -> bool {
+        !self.hidden
+            && !(self.proc_mode && (self.name == "mem" || self.name == "net"))
+            && !(self.stat_mode && self.name == "proc")
+    }
+
+    /// If this box parent is the one keys are routed to
+    pub fn is_focused(&self) -> bool {
+        self.focused == self.name
+    }
+
     /// Defaults now = true
     pub fn draw_update_ms(
         &mut self,
@@ -170,6 +425,11 @@ impl BrshtopBox {
         theme: &Theme,
         term: &Term,
     ) {
+        // * The update time and clock live in the cpu box border
+        if cpu_box.get_parent().get_hidden() {
+            return;
+        }
+
         let mut update_string: String = format!("{}ms", config.update_ms);
         let xpos: u32 = cpu_box.get_parent().get_x() + cpu_box.get_parent().get_width()
             - (update_string.len() as u32)
@@ -208,7 +468,7 @@ impl BrshtopBox {
                     "{}{}{}{} ",
                     mv::to(cpu_box.get_parent().get_y(), xpos),
                     theme.colors.cpu_box.call(
-                        format!("{}{}", symbol::h_line.repeat(7), symbol::title_left),
+                        format!("{}{}", symbol::h_line().repeat(7), symbol::title_left()),
                         term
                     ),
                     fx::b,
@@ -222,7 +482,7 @@ impl BrshtopBox {
                     theme
                         .colors
                         .cpu_box
-                        .call(symbol::title_right.to_owned(), term)
+                        .call(symbol::title_right().to_owned(), term)
                 ),
             ],
             false,
@@ -263,30 +523,65 @@ impl BrshtopBox {
         draw: &mut Draw,
         key: &mut Key,
     ) {
+        if cpu_box.get_parent().get_hidden() {
+            return;
+        }
+
         let mut out: String = String::default();
 
         let system_time = SystemTime::now();
         let datetime: DateTime<Local> = system_time.into();
 
+        if self
+            .message
+            .as_ref()
+            .map_or(false, |(_, until)| system_time > *until)
+        {
+            self.message = None;
+            self.clock = String::default();
+            // * Without a clock the message is covered with the box border again
+            if !self.get_clock_on() {
+                draw.buffer(
+                    "clock".to_owned(),
+                    vec![format!(
+                        "{}{}{}{}",
+                        mv::to(
+                            cpu_box.get_parent().get_y(),
+                            (cpu_box.get_parent().get_width() / 2) as u32 - self.clock_len / 2
+                        ),
+                        fx::ub,
+                        theme.colors.cpu_box,
+                        symbol::h_line().repeat(self.clock_len as usize)
+                    )],
+                    false,
+                    false,
+                    100,
+                    menu.active,
+                    false,
+                    true,
+                    key,
+                );
+                self.clock_len = 0;
+                return;
+            }
+        }
+        let text: String = match &self.message {
+            Some((message, _)) => message.clone(),
+            None => datetime
+                .format(config.draw_clock.clone().as_str())
+                .to_string(),
+        };
+
         if !force
-            && (!self.get_clock_on()
+            && ((!self.get_clock_on() && self.message.is_none())
                 || term.get_resized()
-                || datetime
-                    .format(config.draw_clock.clone().as_str())
-                    .to_string()
-                    == self.get_clock())
+                || text == self.get_clock())
         {
             return;
         }
 
-        let mut clock_string: String = datetime
-            .format(config.draw_clock.clone().as_str())
-            .to_string()
-            .clone();
-        self.clock = datetime
-            .format(config.draw_clock.clone().as_str())
-            .to_string()
-            .clone();
+        let mut clock_string: String = text.clone();
+        self.clock = text;
         for (custom, value) in self.clock_custom_format.clone() {
             if clock_string.contains(custom.as_str()) {
                 clock_string = clock_string.replace(custom.as_str(), value.as_str())
@@ -312,7 +607,7 @@ impl BrshtopBox {
                 ),
                 fx::ub,
                 theme.colors.cpu_box,
-                symbol::h_line.repeat(self.clock_len as usize)
+                symbol::h_line().repeat(self.clock_len as usize)
             );
         }
         self.clock_len = clock_len.clone() as u32;
@@ -328,7 +623,7 @@ impl BrshtopBox {
                 ),
                 fx::ub,
                 theme.colors.cpu_box,
-                symbol::title_left,
+                symbol::title_left(),
                 fx::b,
                 theme
                     .colors
@@ -336,7 +631,7 @@ impl BrshtopBox {
                     .call(clock_string[..clock_len as usize].to_string(), term),
                 fx::ub,
                 theme.colors.cpu_box,
-                symbol::title_right,
+                symbol::title_right(),
                 inserter
             )
             .as_str(),
@@ -384,6 +679,23 @@ impl BrshtopBox {
         theme: &Theme,
         term: &Term,
     ) {
+        // * Clicking a box title zooms that box
+        for parent in vec![
+            cpu_box.get_parent(),
+            mem_box.get_parent(),
+            net_box.get_parent(),
+            proc_box.get_parent(),
+        ] {
+            let zoom_key: String = format!("zoom_{}", parent.get_name());
+            if parent.get_hidden() || key.mouse.contains_key(&zoom_key) {
+                continue;
+            }
+            let mut top: Vec<Vec<i32>> = Vec::<Vec<i32>>::new();
+            for i in 0..parent.get_name().len() as u32 {
+                top.push(vec![(parent.get_x() + 3 + i) as i32, parent.get_y() as i32]);
+            }
+            key.mouse.insert(zoom_key, top);
+        }
 
         let cpu_bg: String = cpu_box.draw_bg(key, theme, term, config);
         let mem_bg: String = mem_box.draw_bg(theme, config, term);
@@ -492,6 +804,54 @@ impl BrshtopBox {
         self.stat_mode = stat_mode.clone()
     }
 
+    pub fn get_focused(&self) -> String {
+        self.focused.clone()
+    }
+
+    pub fn set_focused(&mut self, focused: String) {
+        self.focused = focused.clone()
+    }
+
+    pub fn get_zoomed(&self) -> String {
+        self.zoomed.clone()
+    }
+
+    pub fn set_zoomed(&mut self, zoomed: String) {
+        self.zoomed = zoomed.clone()
+    }
+
+    pub fn get_sizes(&self) -> (u32, u32, u32) {
+        self.sizes.clone()
+    }
+
+    pub fn set_sizes(&mut self, sizes: (u32, u32, u32)) {
+        self.sizes = sizes.clone()
+    }
+
+    pub fn set_layouts(&mut self, layouts: String) {
+        self.layouts = layouts.clone()
+    }
+
+    pub fn get_dragging(&self) -> String {
+        self.dragging.clone()
+    }
+
+    pub fn get_hidden(&self) -> bool {
+        self.hidden.clone()
+    }
+
+    pub fn set_hidden(&mut self, hidden: bool) {
+        self.hidden = hidden.clone()
+    }
+
+    pub fn get_layout_rect(&self) -> Option<(u32, u32, u32, u32)> {
+        self.layout_rect.clone()
+    }
+
+    pub fn set_layout_rect(&mut self, layout_rect: Option<(u32, u32, u32, u32)>) {
+        self.layout_rect = layout_rect.clone()
+    }
+
     pub fn get_out(&self) -> String {
         self.out.clone()
     }
@@ -587,6 +947,16 @@ impl BrshtopBox {
         self.clock_len = clock_len.clone()
     }
 
+    /// Show a message in place of the clock for a few seconds, it is cut like the clock so only
+    /// ascii characters are kept
+    pub fn set_message(&mut self, message: String) {
+        let message: String = message
+            .chars()
+            .filter(|c| c.is_ascii() && !c.is_control())
+            .collect();
+        self.message = Some((message, SystemTime::now() + Duration::from_secs(10)))
+    }
+
     pub fn get_resized(&self) -> bool {
         self.resized.clone()
     }
@@ -1,98 +1,33 @@
 use {
     crate::{
         error::{errlog, throw_error},
+        keybindings::{ACTIONS, PRESETS},
+        layout::{parse_sizes, Layout},
+        symbol::SYMBOL_SETS,
+        theme::COLOR_DEPTHS,
+        tomlconf::{self, Entry, Value},
         VERSION,
     },
-    expanduser::expanduser,
-    lenient_bool::LenientBool,
+    config_schema::{ConfigSchema, ConfigValue, Kind, OptionInfo},
+    gradient::SPACES,
+    lazy_static::lazy_static,
     psutil::sensors::*,
     std::{
         collections::*,
         fmt::{self, Debug, Display, Formatter},
-        fs::{write, File},
+        fs::{read_to_string, rename, write, File},
         io::{prelude::*, BufReader},
         path::*,
     },
+    uname::uname,
 };
 
-// TODO : Fix macro scope
-#[derive(Clone, Debug, PartialEq)]
-pub enum ConfigItem {
-    Str(String),
-    Int(i64),
-    Bool(bool),
-    ViewMode(ViewMode),
-    LogLevel(LogLevel),
-    SortingOption(SortingOption),
-    Error,
-}
-impl Display for ConfigItem {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        match self {
-            ConfigItem::Str(s) => write!(f, "{:?}", s),
-            ConfigItem::Int(i) => write!(f, "{:?}", i),
-            ConfigItem::Bool(b) => write!(f, "{:?}", b),
-            ConfigItem::Error => write!(f, "{:?}", "_error_"),
-            ConfigItem::ViewMode(v) => write!(f, "{:?}", v),
-            ConfigItem::LogLevel(l) => write!(f, "{:?}", l),
-            ConfigItem::SortingOption(s) => write!(f, "{:?}", s),
-        }
-    }
-}
-impl ConfigItem {
-    fn sorting_option(s: &String) -> Result<Self, String> {
-        Ok(ConfigItem::SortingOption(match s.to_string().as_str() {
-            "pid" => SortingOption::Pid,
-            "program" => SortingOption::Program,
-            "arguments" => SortingOption::Arguments,
-            "threads" => SortingOption::Threads,
-            "user" => SortingOption::User,
-            "memory" => SortingOption::Memory,
-            "cpu" => SortingOption::Cpu { lazy: false },
-            "cpu lazy" => SortingOption::Cpu { lazy: true },
-            bad => {
-                return Err(format!(
-                    r#"Config key "proc_sorted" had an unknown value: {}"#,
-                    bad
-                ));
-            }
-        }))
-    }
-
-    fn log_level(s: &String) -> Result<Self, String> {
-        Ok(match s.to_string().as_str() {
-            "error" => ConfigItem::LogLevel(LogLevel::Error),
-            "warning" => ConfigItem::LogLevel(LogLevel::Warning),
-            "info" => ConfigItem::LogLevel(LogLevel::Info),
-            "debug" => ConfigItem::LogLevel(LogLevel::Debug),
-            bad => {
-                return Err(format!(
-                    r#"Config key "log_level" had an unknown value: {}"#,
-                    bad
-                ));
-            }
-        })
-    }
-
-    fn view_mode(s: &String) -> Result<Self, String> {
-        Ok(match s.to_string().as_str() {
-            "full" => ConfigItem::ViewMode(ViewMode {
-                t: ViewModeEnum::Full,
-            }),
-            "proc" => ConfigItem::ViewMode(ViewMode {
-                t: ViewModeEnum::Proc,
-            }),
-            "stat" => ConfigItem::ViewMode(ViewMode {
-                t: ViewModeEnum::Stat,
-            }),
-            bad => {
-                return Err(format!(
-                    r#"Config key "view_mode" had an unknown value: {}"#,
-                    bad
-                ));
-            }
-        })
-    }
+lazy_static! {
+    /// Name of this host, selects a [host."name"] section
+    static ref HOSTNAME: String = match uname() {
+        Ok(info) => info.nodename,
+        Err(_) => String::default(),
+    };
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -180,7 +115,7 @@ impl From<String> for SortingOption {
             "threads" => SortingOption::Threads,
             "user" => SortingOption::User,
             "memory" => SortingOption::Memory,
-            "cpu" => SortingOption::Cpu { lazy: false },
+            "cpu" | "cpu responsive" => SortingOption::Cpu { lazy: false },
             "cpu lazy" => SortingOption::Cpu { lazy: true },
             _ => {
                 errlog(format!(
@@ -193,66 +128,914 @@ impl From<String> for SortingOption {
     }
 }
 
+impl ConfigValue for ViewMode {
+    const KIND: Kind = Kind::Text;
+    fn parse_value(s: &str) -> Result<Self, String> {
+        Ok(ViewMode {
+            t: match s {
+                "full" => ViewModeEnum::Full,
+                "proc" => ViewModeEnum::Proc,
+                "stat" => ViewModeEnum::Stat,
+                _ => return Err("should be one of full, proc, stat".to_owned()),
+            },
+        })
+    }
+    fn write_value(&self) -> String {
+        self.t.to_string().trim_matches('"').to_owned()
+    }
+}
+
+impl ConfigValue for LogLevel {
+    const KIND: Kind = Kind::Text;
+    fn parse_value(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "error" => Ok(LogLevel::Error),
+            "warning" => Ok(LogLevel::Warning),
+            "info" => Ok(LogLevel::Info),
+            "debug" => Ok(LogLevel::Debug),
+            _ => Err("should be one of error, warning, info, debug".to_owned()),
+        }
+    }
+    fn write_value(&self) -> String {
+        self.to_string().trim_matches('"').to_owned()
+    }
+}
+
+impl ConfigValue for SortingOption {
+    const KIND: Kind = Kind::Text;
+    fn parse_value(s: &str) -> Result<Self, String> {
+        match s {
+            "pid" => Ok(SortingOption::Pid),
+            "program" => Ok(SortingOption::Program),
+            "arguments" => Ok(SortingOption::Arguments),
+            "threads" => Ok(SortingOption::Threads),
+            "user" => Ok(SortingOption::User),
+            "memory" => Ok(SortingOption::Memory),
+            // * "cpu responsive" is the name bpytop configs use
+            "cpu" | "cpu responsive" => Ok(SortingOption::Cpu { lazy: false }),
+            "cpu lazy" => Ok(SortingOption::Cpu { lazy: true }),
+            _ => Err(
+                "should be one of pid, program, arguments, threads, user, memory, cpu lazy, cpu responsive"
+                    .to_owned(),
+            ),
+        }
+    }
+    fn write_value(&self) -> String {
+        self.to_string().trim_matches('"').to_owned()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum ConfigAttr {
     String(String),
     Bool(bool),
-    ViewMode(ViewMode),
     Int64(i64),
-    SortingOption(SortingOption),
-    LogLevel(LogLevel),
 }
 impl Display for ConfigAttr {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
             ConfigAttr::Bool(b) => write!(f, "{}", b),
             ConfigAttr::Int64(i) => write!(f, "{}", i),
-            ConfigAttr::LogLevel(l) => write!(f, "{}", l.to_string()),
-            ConfigAttr::SortingOption(s) => write!(f, "{}", s.to_string()),
             ConfigAttr::String(s) => write!(f, "{}", s),
-            ConfigAttr::ViewMode(v) => write!(f, "{}", v.to_string()),
         }
     }
 }
 
-#[derive(Clone)]
+fn check_layouts(s: &str) -> Result<(), String> {
+    match Layout::parse_all(&s.to_owned()) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("is malformed: {}", e)),
+    }
+}
+
+fn check_box_sizes(s: &str) -> Result<(), String> {
+    match parse_sizes(&s.to_owned()) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(format!("is malformed: {}", e)),
+    }
+}
+
+/// Network speeds like "10M" or "100mbit" start with a number
+fn check_net_speed(s: &str) -> Result<(), String> {
+    match s.chars().next() {
+        Some(c) if c.is_numeric() => Ok(()),
+        _ => Err("didn't get an acceptable value".to_owned()),
+    }
+}
+
+/// Options are declared once here, #[option(...)] gives the default, the range or choices,
+/// the comment written to the config file and the description in the options menu
+#[derive(Clone, ConfigSchema)]
 pub struct Config {
-    pub keys: Vec<String>,
-    pub conf_dict: HashMap<String, ConfigItem>,
-    pub attr: HashMap<String, ConfigItem>,
+    #[option(
+        default = "Default",
+        cycle,
+        comment = [
+            "Color theme, looks for a .theme file in \"/usr/[local/]share/bpytop/themes\" and \"~/.config/bpytop/themes\", \"Default\" for builtin default theme.",
+            "Prefix name by a plus sign (+) for a theme located in user themes folder, i.e. color_theme = \"+monokai\"",
+        ],
+        menu = [
+            "Set color theme.",
+            "",
+            "Choose from all theme files in",
+            "\"/usr/[local/]share/bpytop/themes\" and",
+            "\"~/.config/bpytop/themes\".",
+            "",
+            "\"Default\" for builtin default theme.",
+            "User themes are prefixed by a plus sign \"+\".",
+            "",
+            "Press enter to edit the colors of the theme",
+            "and save them as a new user theme.",
+            "Press i in the editor to import a btop theme,",
+            "a terminal color scheme or \"terminal\" colors.",
+            "",
+            "For theme updates see:",
+            "https://github.com/aristocratos/bpytop",
+        ],
+    )]
     pub color_theme: String,
+    #[option(
+        default = "",
+        comment = [
+            "Theme used instead of color_theme when the terminal background is light, color_theme is used on dark backgrounds.",
+            "The background is asked from the terminal at start and followed when the terminal reports a change, empty to always use color_theme.",
+            "Example: color_theme_light = \"Default Light\"",
+        ],
+        menu = [
+            "Theme for a light terminal background.",
+            "",
+            "Used instead of color_theme when the",
+            "terminal background is light. It is asked",
+            "from the terminal at start and followed",
+            "when the terminal reports a change.",
+            "",
+            "Empty to always use color_theme and",
+            "not ask the terminal, set for example",
+            "\"Default Light\".",
+        ],
+    )]
+    pub color_theme_light: String,
+    #[option(
+        default = true,
+        comment = [
+            "If the theme set background should be shown, set to False if you want terminal background transparency",
+        ],
+        menu = [
+            "If the theme set background should be shown.",
+            "",
+            "Set to False if you want terminal background",
+            "transparency.",
+        ],
+    )]
     pub theme_background: bool,
+    #[option(
+        default = "auto",
+        choices = COLOR_DEPTHS,
+        comment = [
+            "Colors the terminal can show, \"auto\" detects it from COLORTERM, TERM and terminfo, \"truecolor\", \"256\" or \"16\" to override.",
+            "Theme colors are changed to the nearest color the terminal has.",
+        ],
+        menu = [
+            "Colors the terminal can show.",
+            "",
+            "\"auto\" detects it from COLORTERM, TERM",
+            "and the terminfo colors capability.",
+            "",
+            "\"truecolor\", \"256\" or \"16\" to override,",
+            "theme colors are changed to the nearest",
+            "color the terminal has.",
+        ],
+    )]
+    pub color_depth: String,
+    #[option(
+        default = "rgb",
+        choices = SPACES,
+        comment = [
+            "Color space the colors of gradients are mixed in, \"rgb\", \"oklab\" for even perceived steps without muddy middles,",
+            "\"oklch\" to mix through the hues between the colors or \"oklch-long\" to go the other way around the color wheel.",
+        ],
+        menu = [
+            "Color space gradients are mixed in.",
+            "",
+            "\"rgb\" mixes the red, green and blue values.",
+            "",
+            "\"oklab\" gives steps that look even and",
+            "avoids muddy colors in the middle.",
+            "",
+            "\"oklch\" mixes through the hues between",
+            "the colors, \"oklch-long\" goes the other",
+            "way around the color wheel.",
+        ],
+    )]
+    pub gradient_space: String,
+    #[option(
+        default = "braille",
+        choices = SYMBOL_SETS,
+        comment = [
+            "Characters used for graphs, \"braille\", \"block\" for block elements or \"ascii\" for fonts and consoles without either.",
+            "\"ascii\" also draws box outlines and meters with plain ascii characters.",
+        ],
+        menu = [
+            "Characters used for graphs.",
+            "",
+            "\"braille\", \"block\" for block elements",
+            "or \"ascii\".",
+            "",
+            "\"ascii\" also draws box outlines and",
+            "meters with plain ascii characters, for",
+            "serial consoles and limited fonts.",
+        ],
+    )]
+    pub graph_symbol: String,
+    #[option(
+        default = ViewMode { t: ViewModeEnum::Full },
+        cycle,
+        comment = [
+            "Set bpytop view mode, \"full\" for everything shown, \"proc\" for cpu stats and processes, \"stat\" for cpu, mem, disks and net stats shown.",
+        ],
+        menu = [
+            "Set bpytop view mode.",
+            "",
+            "\"full\" for everything shown.",
+            "\"proc\" for cpu stats and processes.",
+            "\"stat\" for cpu, mem, disks and net stats shown.",
+        ],
+    )]
+    pub view_mode: ViewMode,
+    #[option(
+        default = "proc_heavy=v[cpu:1,h[v[mem:1,proc:3]:4,net:1]:3]",
+        check = check_layouts,
+        comment = [
+            "Named box layouts separated by \";\", as name=tree. A tree is a box (cpu, mem, net or proc), h[...] for boxes side by side or v[...] for stacked boxes.",
+            "Add :N after a box or split to set its relative size, boxes left out of a layout are hidden, i.e. \"proc_heavy=v[cpu:1,h[v[mem:1,proc:3]:4,net:1]:3]\"",
+        ],
+        menu = [
+            "Named box layouts separated by \";\".",
+            "",
+            "Written as name=tree, where a tree is a box",
+            "(cpu, mem, net, proc), h[...] for boxes side",
+            "by side or v[...] for stacked boxes.",
+            "",
+            "Add :N to set a relative size, boxes left",
+            "out of a layout are hidden.",
+            "",
+            "i.e. \"small=h[cpu,proc:2]\"",
+        ],
+    )]
+    pub layouts: String,
+    #[option(
+        default = "",
+        comment = [
+            "Name of the layout from \"layouts\" to use instead of view_mode, empty to use view_mode. Press l to cycle layouts.",
+        ],
+        menu = [
+            "Layout from \"layouts\" to use.",
+            "",
+            "Replaces view_mode when set, leave empty",
+            "to use view_mode.",
+            "",
+            "Cycle layouts with the l key.",
+        ],
+    )]
+    pub layout: String,
+    #[option(
+        default = "",
+        check = check_box_sizes,
+        comment = [
+            "Box sizes without a layout as \"cpu height,mem height,mem and net width\" in percent of the terminal, empty for the defaults \"32,38,45\".",
+        ],
+        menu = [
+            "Box sizes when no layout is used.",
+            "",
+            "Written as \"cpu height,mem height,mem",
+            "and net width\" in percent of the",
+            "terminal, empty for the defaults.",
+            "",
+            "Borders can also be dragged with the mouse.",
+        ],
+    )]
+    pub box_sizes: String,
+    #[option(
+        default = false,
+        comment = [
+            "Save box sizes and layouts changed by dragging box borders with the mouse, True or False.",
+        ],
+        menu = [
+            "Save dragged box sizes.",
+            "",
+            "True or False.",
+            "",
+            "Box sizes and layouts changed by dragging",
+            "a border with the mouse are written to",
+            "the config file.",
+        ],
+    )]
+    pub save_sizes: bool,
+    #[option(
+        default = "default",
+        choices = PRESETS,
+        comment = [
+            "Key bindings to start from, \"default\", \"vim\" or \"htop\". Single actions can be rebound in a [keys] section at the end of this file.",
+        ],
+        menu = [
+            "Set of key bindings to start from.",
+            "",
+            "\"default\", \"vim\" or \"htop\".",
+            "",
+            "Single actions can be rebound in the",
+            "[keys] section of the config file,",
+            "i.e. kill = \"d d|k\".",
+            "",
+            "Conflicts are shown in the help menu.",
+        ],
+    )]
+    pub key_preset: String,
+    #[option(
+        default = 2000,
+        min = 100,
+        max = 86399900,
+        comment = [
+            "Update time in milliseconds, increases automatically if set below internal loops processing time, recommended 2000 ms or above for better sample times for graphs.",
+        ],
+        menu = [
+            "Update time in milliseconds.",
+            "",
+            "Recommended 2000 ms or above for better sample",
+            "times for graphs.",
+            "",
+            "Min value: 100 ms",
+            "Max value: 86399900 ms = 24 hours.",
+        ],
+    )]
     pub update_ms: i64,
+    #[option(
+        default = SortingOption::Cpu { lazy: true },
+        section = "proc",
+        cycle,
+        comment = [
+            "Processes sorting, \"pid\" \"program\" \"arguments\" \"threads\" \"user\" \"memory\" \"cpu lazy\" \"cpu responsive\",",
+            "\"cpu lazy\" updates top process over time, \"cpu responsive\" updates top process directly.",
+        ],
+        menu = [
+            "Processes sorting option.",
+            "",
+            "Possible values: \"pid\", \"program\", \"arguments\",",
+            "\"threads\", \"user\", \"memory\", \"cpu lazy\" and",
+            "\"cpu responsive\".",
+            "",
+            "\"cpu lazy\" updates top process over time,",
+            "\"cpu responsive\" updates top process directly.",
+        ],
+    )]
     pub proc_sorting: SortingOption,
+    #[option(
+        default = false,
+        section = "proc",
+        comment = [
+            "Reverse sorting order, True or False.",
+        ],
+        menu = [
+            "Reverse processes sorting order.",
+            "",
+            "True or False.",
+        ],
+    )]
     pub proc_reversed: bool,
+    #[option(
+        default = false,
+        section = "proc",
+        comment = [
+            "Show processes as a tree",
+        ],
+        menu = [
+            "Processes tree view.",
+            "",
+            "Set true to show processes grouped by parents,",
+            "with lines drawn between parent and child",
+            "process.",
+        ],
+    )]
     pub proc_tree: bool,
+    #[option(
+        default = 3,
+        section = "proc",
+        min = 0,
+        comment = [
+            "Which depth the tree view should auto collapse processes at",
+        ],
+        menu = [
+            "Process tree auto collapse depth.",
+            "",
+            "Sets the depth were the tree view will auto",
+            "collapse processes at.",
+        ],
+    )]
     pub tree_depth: i32,
+    #[option(
+        default = true,
+        section = "proc",
+        comment = [
+            "Use the cpu graph colors in the process list.",
+        ],
+        menu = [
+            "Enable colors in process view.",
+            "",
+            "Uses the cpu graph gradient colors.",
+        ],
+    )]
     pub proc_colors: bool,
+    #[option(
+        default = true,
+        section = "proc",
+        comment = [
+            "Use a darkening gradient in the process list.",
+        ],
+        menu = [
+            "Enable process view gradient fade.",
+            "",
+            "Fades from top or current selection.",
+            "Max fade value is equal to current themes",
+            "\"inactive_fg\" color value.",
+        ],
+    )]
     pub proc_gradient: bool,
+    #[option(
+        default = false,
+        section = "proc",
+        comment = [
+            "If process cpu usage should be of the core it's running on or usage of the total available cpu power.",
+        ],
+        menu = [
+            "Process usage per core.",
+            "",
+            "If process cpu usage should be of the core",
+            "it\'s running on or usage of the total",
+            "available cpu power.",
+            "",
+            "If true and process is multithreaded",
+            "cpu usage can reach over 100%.",
+        ],
+    )]
     pub proc_per_core: bool,
+    #[option(
+        default = true,
+        section = "proc",
+        comment = [
+            "Show process memory as bytes instead of percent",
+        ],
+        menu = [
+            "Show memory as bytes in process list.",
+            " ",
+            "True or False.",
+        ],
+    )]
     pub proc_mem_bytes: bool,
+    #[option(
+        default = true,
+        section = "cpu",
+        comment = [
+            "Check cpu temperature, needs \"osx-cpu-temp\" on MacOS X.",
+        ],
+        menu = [
+            "Enable cpu temperature reporting.",
+            "",
+            "True or False.",
+        ],
+    )]
     pub check_temp: bool,
+    #[option(
+        default = "Auto",
+        section = "cpu",
+        cycle,
+        comment = [
+            "Which sensor to use for cpu temperature, use options menu to select from list of available sensors.",
+        ],
+        menu = [
+            "Cpu temperature sensor",
+            "",
+            "Select the sensor that corresponds to",
+            "your cpu temperature.",
+            "Set to \"Auto\" for auto detection.",
+        ],
+    )]
     pub cpu_sensor: String,
+    #[option(
+        default = true,
+        section = "cpu",
+        comment = [
+            "Show temperatures for cpu cores also if check_temp is True and sensors has been found",
+        ],
+        menu = [
+            "Show temperatures for cpu cores.",
+            "",
+            "Only works if check_temp is True and",
+            "the system is reporting core temps.",
+        ],
+    )]
     pub show_coretemp: bool,
+    #[option(
+        default = "%X",
+        comment = [
+            "Draw a clock at top of screen, formatting according to strftime, empty string to disable.",
+        ],
+        menu = [
+            "Draw a clock at top of screen.",
+            "",
+            "Formatting according to strftime, empty",
+            "string to disable.",
+            "",
+            "Custom formatting options:",
+            "\"/host\" = hostname",
+            "\"/user\" = username",
+            "",
+            "Examples of strftime formats:",
+            "\"%X\" = locale HH:MM:SS",
+            "\"%H\" = 24h hour, \"%I\" = 12h hour",
+            "\"%M\" = minute, \"%S\" = second",
+            "\"%d\" = day, \"%m\" = month, \"%y\" = year",
+        ],
+    )]
     pub draw_clock: String,
+    #[option(
+        default = true,
+        comment = [
+            "Update main ui in background when menus are showing, set this to false if the menus is flickering too much for comfort.",
+        ],
+        menu = [
+            "Update main ui when menus are showing.",
+            "",
+            "True or False.",
+            "",
+            "Set this to false if the menus is flickering",
+            "too much for a comfortable experience.",
+        ],
+    )]
     pub background_update: bool,
+    #[option(
+        default = "",
+        section = "cpu",
+        comment = [
+            "Custom cpu model name, empty string to disable.",
+        ],
+        menu = [
+            "Custom cpu model name in cpu percentage box.",
+            "",
+            "Empty string to disable.",
+        ],
+    )]
     pub custom_cpu_name: String,
+    #[option(
+        default = "",
+        section = "mem",
+        comment = [
+            "Optional filter for shown disks, should be last folder in path of a mountpoint, \"root\" replaces \"/\", separate multiple values with comma.",
+            "Begin line with \"exclude=\" to change to exclude filter, oterwise defaults to \"most include\" filter. Example: filter = \"exclude=boot, home\"",
+        ],
+        menu = [
+            "Optional filter for shown disks.",
+            "",
+            "Should be last folder in path of a mountpoint,",
+            "\"root\" replaces \"/\", separate multiple values",
+            "with a comma.",
+            "Begin line with \"exclude=\" to change to exclude",
+            "filter.",
+            "Oterwise defaults to \"most include\" filter.",
+            "",
+            "Example: filter = \"exclude=boot, home\"",
+        ],
+    )]
     pub disks_filter: String,
-    pub update_check: bool,
+    #[option(
+        default = true,
+        section = "mem",
+        comment = [
+            "Show graphs instead of meters for memory values.",
+        ],
+        menu = [
+            "Show graphs for memory values.",
+            "",
+            "True or False.",
+        ],
+    )]
     pub mem_graphs: bool,
+    #[option(
+        default = true,
+        section = "mem",
+        comment = [
+            "If swap memory should be shown in memory box.",
+        ],
+        menu = [
+            "If swap memory should be shown in memory box.",
+            "",
+            "True or False.",
+        ],
+    )]
     pub show_swap: bool,
+    #[option(
+        default = true,
+        section = "mem",
+        comment = [
+            "Show swap as a disk, ignores show_swap value above, inserts itself after first disk.",
+        ],
+        menu = [
+            "Show swap as a disk.",
+            "",
+            "Ignores show_swap value above.",
+            "Inserts itself after first disk.",
+        ],
+    )]
     pub swap_disk: bool,
+    #[option(
+        default = true,
+        section = "mem",
+        comment = [
+            "If mem box should be split to also show disks info.",
+        ],
+        menu = [
+            "Split memory box to also show disks.",
+            "",
+            "True or False.",
+        ],
+    )]
     pub show_disks: bool,
+    #[option(
+        default = 90,
+        section = "alerts",
+        min = 0,
+        max = 100,
+        comment = [
+            "Mark a disk with a warning when used space or used inodes reach this percentage, 0 to disable. Read-only disks are always marked.",
+        ],
+        menu = [
+            "Disk space warning threshold.",
+            "",
+            "Marks a disk with a warning when its used",
+            "space reaches this percentage.",
+            "Read-only disks are always marked.",
+            "",
+            "0 to disable, max value: 100",
+        ],
+    )]
+    pub disks_warn_used: i64,
+    #[option(
+        default = 90,
+        section = "alerts",
+        min = 0,
+        max = 100,
+        menu = [
+            "Disk inode warning threshold.",
+            "",
+            "Marks a disk with a warning when its used",
+            "inodes reaches this percentage.",
+            "A disk can run out of inodes long before",
+            "it runs out of space.",
+            "",
+            "0 to disable, max value: 100",
+        ],
+    )]
+    pub disks_warn_inodes: i64,
+    #[option(
+        default = "10M",
+        section = "net",
+        check = check_net_speed,
+        comment = [
+            "Set fixed values for network graphs, default \"10M\" = 10 Mibibytes, possible units \"K\", \"M\", \"G\", append with \"bit\" for bits instead of bytes, i.e \"100mbit\". Interfaces reporting a link speed use that instead",
+        ],
+        menu = [
+            "Fixed network graph download value.",
+            "",
+            "Default \"10M\" = 10 MibiBytes.",
+            "Possible units:",
+            "\"K\" (KiB), \"M\" (MiB), \"G\" (GiB).",
+            "",
+            "Append \"bit\" for bits instead of bytes,",
+            "i.e \"100Mbit\"",
+            "",
+            "Not used for interfaces that report",
+            "their link speed.",
+            "",
+            "Can be toggled with auto button.",
+        ],
+    )]
     pub net_download: String,
+    #[option(
+        default = "10M",
+        section = "net",
+        check = check_net_speed,
+        menu = [
+            "Fixed network graph upload value.",
+            "",
+            "Default \"10M\" = 10 MibiBytes.",
+            "Possible units:",
+            "\"K\" (KiB), \"M\" (MiB), \"G\" (GiB).",
+            "",
+            "Append \"bit\" for bits instead of bytes,",
+            "i.e \"100Mbit\"",
+            "",
+            "Not used for interfaces that report",
+            "their link speed.",
+            "",
+            "Can be toggled with auto button.",
+        ],
+    )]
     pub net_upload: String,
-    pub net_color_fixed: bool,
+    #[option(
+        default = true,
+        section = "net",
+        comment = [
+            "Start in network graphs auto rescaling mode, ignores any values set above and rescales down to 10 Kibibytes at the lowest.",
+        ],
+        menu = [
+            "Start in network graphs auto rescaling mode.",
+            "",
+            "Ignores any values set above at start and",
+            "rescales down to 10KibiBytes at the lowest.",
+            "",
+            "True or False.",
+        ],
+    )]
     pub net_auto: bool,
+    #[option(
+        default = false,
+        section = "net",
+        comment = [
+            "Sync the scaling for download and upload to whichever currently has the highest scale",
+        ],
+        menu = [
+            "Network scale sync.",
+            "",
+            "Syncs the scaling for download and upload to",
+            "whichever currently has the highest scale.",
+            "",
+            "True or False.",
+        ],
+    )]
     pub net_sync: bool,
+    #[option(
+        default = false,
+        section = "net",
+        comment = [
+            "If the network graphs color gradient should scale to bandwith usage or auto scale, bandwith usage is based on \"net_download\" and \"net_upload\" values",
+        ],
+        menu = [
+            "Set network graphs color gradient to fixed.",
+            "",
+            "If True the network graphs color is based",
+            "on the total bandwidth usage instead of",
+            "the current autoscaling.",
+            "",
+            "The bandwidth usage is based on the",
+            "\"net_download\" and \"net_upload\" values set",
+            "above.",
+        ],
+    )]
+    pub net_color_fixed: bool,
+    #[option(
+        default = false,
+        section = "net",
+        comment = [
+            "Show a compact graph pair for several interfaces at once instead of a single interface",
+        ],
+        menu = [
+            "Show several interfaces at once.",
+            "",
+            "Gives every interface in \"net_interfaces\"",
+            "its own compact download/upload graph",
+            "pair instead of the single large graphs.",
+            "",
+            "True or False.",
+        ],
+    )]
+    pub net_multi: bool,
+    #[option(
+        default = "",
+        section = "net",
+        comment = [
+            "Comma separated interfaces to show when net_multi is True, in order, \"all\" is the sum of all interfaces that are up. Empty shows all interfaces that are up.",
+        ],
+        menu = [
+            "Interfaces shown when net_multi is set.",
+            "",
+            "Comma separated, shown in the given order.",
+            "\"all\" is the sum of all interfaces that",
+            "are up.",
+            "",
+            "Empty shows \"all\" followed by every",
+            "interface that is up.",
+            "",
+            "i.e \"all,eth0,wlan0\"",
+        ],
+    )]
+    pub net_interfaces: String,
+    #[option(
+        default = true,
+        section = "net",
+        comment = [
+            "Show tcp retransmits and interface errors and drops as graphs next to the network graphs, press x for the connection table",
+        ],
+        menu = [
+            "Show protocol statistics graphs.",
+            "",
+            "Graphs tcp retransmits and the errors and",
+            "drops of the current interface next to",
+            "the network graphs.",
+            "",
+            "The connection table is shown with (x).",
+            "",
+            "True or False.",
+        ],
+    )]
+    pub net_proto: bool,
+    #[option(
+        default = false,
+        section = "net",
+        comment = [
+            "Show packets per second instead of bytes per second in the network graphs and stats, packet graphs are always auto scaled",
+        ],
+        menu = [
+            "Show packets instead of bytes.",
+            "",
+            "Network graphs and stats show packets per",
+            "second, useful for spotting floods of small",
+            "packets. Always auto scaled.",
+            "",
+            "Can be toggled with (p).",
+            "",
+            "True or False.",
+        ],
+    )]
+    pub net_packets: bool,
+    #[option(
+        default = true,
+        comment = [
+            "Show battery stats in top right if battery is present",
+        ],
+        menu = [
+            "Show battery stats.",
+            "",
+            "Show battery stats in the top right corner",
+            "if a battery is present.",
+        ],
+    )]
     pub show_battery: bool,
+    #[option(
+        default = true,
+        comment = [
+            "Show init screen at startup, the init screen is purely cosmetical",
+        ],
+        menu = [
+            "Show init screen at startup.",
+            "",
+            "The init screen is purely cosmetical and",
+            "slows down start to show status messages.",
+        ],
+    )]
     pub show_init: bool,
-    pub view_mode: ViewMode,
+    #[option(
+        default = true,
+        comment = [
+            "Enable check for new version from github.com/aristocratos/bpytop at start.",
+        ],
+        menu = [
+            "Check for updates at start.",
+            "",
+            "Checks for latest version from:",
+            "https://github.com/aristocratos/bpytop",
+        ],
+    )]
+    pub update_check: bool,
+    #[option(
+        default = LogLevel::Warning,
+        cycle,
+        comment = [
+            "Set loglevel for \"~/.config/bpytop/error.log\" levels are: \"ERROR\" \"WARNING\" \"INFO\" \"DEBUG\".",
+            "The level set includes all lower levels, i.e. \"DEBUG\" will show all logging info.",
+        ],
+        menu = [
+            "Set loglevel for error.log",
+            "",
+            "Levels are: \"ERROR\" \"WARNING\" \"INFO\" \"DEBUG\".",
+            "The level set includes all lower levels,",
+            "i.e. \"DEBUG\" will show all logging info.",
+        ],
+    )]
     pub log_level: LogLevel,
+
+    /// Action to keys overrides from the [keys] section
+    pub key_bindings: HashMap<String, String>,
+    /// Name given with --profile, selects a [profile."name"] section
+    pub profile: String,
+    /// "key=value" overrides given with --set, applied after the config file
+    pub overrides: Vec<String>,
+    /// Entries of the [host."name"] and [profile."name"] sections, written back as they were on save
+    pub layer_entries: Vec<Entry>,
+    /// The config before and after the host and profile sections and overrides were applied
+    pub layered: Option<Box<(Config, Config)>>,
+    /// Options changed while running, saved with their new value over the host and profile sections
+    pub changed_options: Vec<String>,
     pub warnings: Vec<String>,
+    /// Lines of the config file that don't parse, invalid values and --set overrides that aren't key=value
+    pub errors: Vec<String>,
     pub info: Vec<String>,
     pub changed: bool,
     pub config_file: PathBuf,
@@ -265,7 +1048,56 @@ pub struct Config {
     pub _initialized: bool,
 }
 impl Config {
-    pub fn new(path: PathBuf) -> Result<Self, &'static str> {
+    pub fn new(
+        path: PathBuf,
+        profile: String,
+        overrides: Vec<String>,
+    ) -> Result<Self, &'static str> {
+        let mut initializing_config: Config = Config::blank(path, profile, overrides);
+        let version: Option<String> = Config::load_config(&mut initializing_config)?;
+
+        match version {
+            Some(s) => {
+                if s != VERSION.to_owned() {
+                    initializing_config.recreate = true;
+                    initializing_config.warnings.push("Config file version and brshtop version mismatch, will be recreated on exit!".to_owned())
+                }
+            }
+            None => {
+                initializing_config.recreate = true;
+                initializing_config
+                    .warnings
+                    .push("Config file is or missing, will be recreated on exit!".to_owned())
+            }
+        }
+
+        initializing_config.check_layout();
+        initializing_config.check_sensor();
+        initializing_config._initialized = true;
+
+        Ok(initializing_config)
+    }
+
+    /// Load a config file for check-config, nothing is migrated or saved and files ending in
+    /// .conf are read as legacy configs
+    pub fn check(
+        path: PathBuf,
+        profile: String,
+        overrides: Vec<String>,
+    ) -> Result<Self, &'static str> {
+        let mut config: Config = Config::blank(path.clone(), profile, overrides);
+        if path.extension().map_or(false, |e| e == "conf") {
+            config.load_legacy(&path)?;
+        } else {
+            config.load_toml(&path)?;
+        }
+        // * The sensors are the ones of the machine running the check, so cpu_sensor isn't checked
+        config.check_layout();
+        Ok(config)
+    }
+
+    /// Default options with the sensors of this machine, before a config file is loaded
+    fn blank(path: PathBuf, profile: String, overrides: Vec<String>) -> Self {
         let mut cpu_sensors_mut: Vec<String> = vec!["Auto".into()];
         let mut num = 1;
         for res in temperatures() {
@@ -283,81 +1115,7 @@ impl Config {
             };
         }
 
-        let keys_unconverted = vec![
-            "color_theme",
-            "update_ms",
-            "proc_sorting",
-            "proc_reversed",
-            "proc_tree",
-            "check_temp",
-            "draw_clock",
-            "background_update",
-            "custom_cpu_name",
-            "proc_colors",
-            "proc_gradient",
-            "proc_per_core",
-            "proc_mem_bytes",
-            "disks_filter",
-            "update_check",
-            "log_level",
-            "mem_graphs",
-            "show_swap",
-            "swap_disk",
-            "show_disks",
-            "net_download",
-            "net_upload",
-            "net_auto",
-            "net_color_fixed",
-            "show_init",
-            "view_mode",
-            "theme_background",
-            "net_sync",
-            "show_battery",
-            "tree_depth",
-            "cpu_sensor",
-            "show_coretemp",
-        ];
-
-        let mut initializing_config = Config {
-            keys: keys_unconverted.iter().map(|s| s.to_string()).collect(),
-            conf_dict: HashMap::<String, ConfigItem>::new(),
-            attr: HashMap::<String, ConfigItem>::new(),
-            color_theme: "Default".to_string(),
-            theme_background: true,
-            update_ms: 2000,
-            proc_sorting: SortingOption::Cpu { lazy: true },
-            proc_reversed: false,
-            proc_tree: false,
-            tree_depth: 3,
-            proc_colors: true,
-            proc_gradient: true,
-            proc_per_core: false,
-            proc_mem_bytes: true,
-            check_temp: true,
-            cpu_sensor: "Auto".to_string(),
-            show_coretemp: true,
-            draw_clock: "%X".to_string(),
-            background_update: true,
-            custom_cpu_name: "".to_string(),
-            disks_filter: "".to_string(),
-            update_check: true,
-            mem_graphs: true,
-            show_swap: true,
-            swap_disk: true,
-            show_disks: true,
-            net_download: "10M".to_string(),
-            net_upload: "10M".to_string(),
-            net_color_fixed: false,
-            net_auto: true,
-            net_sync: false,
-            show_battery: true,
-            show_init: true,
-            view_mode: ViewMode {
-                t: ViewModeEnum::Full,
-            },
-            log_level: LogLevel::Warning,
-            warnings: Vec::<String>::new(),
-            info: Vec::<String>::new(),
+        Config {
             // TODO: We probably don't need these
             sorting_options: vec![
                 SortingOption::Pid,
@@ -372,7 +1130,7 @@ impl Config {
             log_levels: vec![
                 LogLevel::Error,
                 LogLevel::Warning,
-                LogLevel::Error,
+                LogLevel::Info,
                 LogLevel::Debug,
             ],
             view_modes: vec![
@@ -387,107 +1145,338 @@ impl Config {
                 },
             ],
             cpu_sensors: cpu_sensors_mut,
-            changed: false,
-            recreate: false,
             config_file: path,
-            _initialized: false,
+            profile,
+            overrides,
+            ..Config::default()
+        }
+    }
+
+    /// An option naming a layout that doesn't exist is reset
+    fn check_layout(&mut self) {
+        if self.layout.len() > 0 && !Layout::names(&self.layouts).contains(&self.layout) {
+            self.errors.push(format!(
+                "Config key \"layout\" names a layout not found in \"layouts\": {}",
+                self.layout
+            ));
+            self.layout = String::default();
+            self.recreate = true;
+        }
+    }
+
+    /// A cpu_sensor this machine doesn't have is reset to "Auto"
+    fn check_sensor(&mut self) {
+        if !self.cpu_sensors.contains(&self.cpu_sensor) {
+            self.warnings
+                .push("Config key \"cpu_sensor\" does not contain an available sensor!".to_owned());
+            self.cpu_sensor = "Auto".to_owned();
+            self.recreate = true;
+        }
+    }
+
+    /// Read the config file again after it changed and return the names of the options that
+    /// changed, "keys" for the key bindings. Nothing is changed if the file doesn't parse
+    pub fn reload(&mut self) -> Result<Vec<String>, String> {
+        let s: String = match read_to_string(&self.config_file) {
+            Ok(s) => s,
+            Err(e) => {
+                return Err(format!(
+                    "Unable to read {} ({})",
+                    self.config_file.display(),
+                    e
+                ))
+            }
         };
+        // * The watcher also sees the writes of save_config, a file brshtop wrote itself changes nothing
+        if s == self.render() {
+            return Ok(Vec::<String>::new());
+        }
+        let (entries, errors) = tomlconf::parse(&s);
+        if let Some(error) = errors.first() {
+            return Err(format!("{}: {}", self.config_file.display(), error));
+        }
 
-        let conf = match Config::load_config(&mut initializing_config) {
-            Ok(d) => d,
-            Err(e) => return Err(e),
+        // * Options left out of the file go back to their defaults, like on a restart
+        let defaults: Config = Config::default();
+        let mut loaded: Config = self.clone();
+        for info in Config::options() {
+            if let Some(value) = defaults.get_value(info.name) {
+                loaded.set_value(info.name, value.as_str())?;
+            }
+        }
+        loaded.key_bindings = HashMap::<String, String>::new();
+        loaded.warnings = Vec::<String>::new();
+        loaded.errors = Vec::<String>::new();
+        loaded.changed_options = Vec::<String>::new();
+        loaded.apply_entries(&entries);
+        loaded.check_layout();
+        loaded.check_sensor();
+
+        let mut changed: Vec<String> = Config::options()
+            .iter()
+            .filter(|info| loaded.get_value(info.name) != self.get_value(info.name))
+            .map(|info| info.name.to_owned())
+            .collect();
+        if loaded.key_bindings != self.key_bindings {
+            changed.push("keys".to_owned());
+        }
+        *self = loaded;
+        Ok(changed)
+    }
+
+    /// Set the options from the configuration file and return its version. A legacy brshtop.conf
+    /// next to the config file is migrated to TOML on first run and kept as brshtop.conf.bak
+    pub fn load_config(&mut self) -> Result<Option<String>, &'static str> {
+        let path: PathBuf = match Config::find_file(&self.config_file) {
+            Some(p) => p,
+            None => return Err("Could not find config file."),
         };
+        if path == self.config_file {
+            self.load_toml(&path)
+        } else if path == self.config_file.with_extension("conf") {
+            self.migrate(&path)
+        } else if path.extension().map_or(false, |e| e == "conf") {
+            self.load_legacy(&path)
+        } else {
+            self.load_toml(&path)
+        }
+    }
 
-        match conf.get(&"version".to_owned()) {
-            Some(ConfigItem::Str(s)) => {
-                if s.clone() != VERSION.to_owned() {
-                    initializing_config.recreate = true;
-                    initializing_config.warnings.push("Config file version and brshtop version mismatch, will be recreated on exit!".to_owned())
+    /// The file the config is read from: config_file, a legacy brshtop.conf next to it or the
+    /// system wide config in /etc
+    pub fn find_file(config_file: &PathBuf) -> Option<PathBuf> {
+        vec![
+            config_file.clone(),
+            config_file.with_extension("conf"),
+            PathBuf::from("/etc/brshtop.toml"),
+            PathBuf::from("/etc/brshtop.conf"),
+        ]
+        .into_iter()
+        .find(|path| path.is_file())
+    }
+
+    /// Load a TOML config, problems are warnings with the line and column and don't recreate the file
+    pub fn load_toml(&mut self, path: &PathBuf) -> Result<Option<String>, &'static str> {
+        let s: String = match read_to_string(path) {
+            Ok(s) => s,
+            Err(e) => return Err("Unable to read config file."),
+        };
+        let version: Option<String> = s
+            .lines()
+            .map(|l| l.trim())
+            .find(|l| l.starts_with("#? Config"))
+            .and_then(|l| l.find("v. ").map(|i| l[i + 3..].to_owned()));
+
+        let (entries, errors) = tomlconf::parse(&s);
+        for error in errors {
+            self.errors.push(error.to_string());
+        }
+        self.apply_entries(&entries);
+        Ok(version)
+    }
+
+    /// Set options from the entries of a config file, then from the [host."name"] section for this
+    /// host, the [profile."name"] section for the profile and the --set overrides
+    pub fn apply_entries(&mut self, entries: &Vec<Entry>) {
+        let hostname: &String = &HOSTNAME;
+        let mut base: Vec<Entry> = Vec::<Entry>::new();
+        let mut host: Vec<Entry> = Vec::<Entry>::new();
+        let mut profile: Vec<Entry> = Vec::<Entry>::new();
+        let mut others: Vec<Entry> = Vec::<Entry>::new();
+        self.layer_entries = Vec::<Entry>::new();
+        self.layered = None;
+
+        for entry in entries.iter() {
+            let (kind, name) = match (entry.table.get(0), entry.table.get(1)) {
+                (Some(kind), Some(name)) if kind == "host" || kind == "profile" => (kind, name),
+                _ => {
+                    base.push(entry.clone());
+                    continue;
                 }
+            };
+            self.layer_entries.push(entry.clone());
+            let mut inner: Entry = entry.clone();
+            inner.table = entry.table[2..].to_vec();
+            // * A host matches by its full name or the name up to the first dot
+            if kind == "host"
+                && (name == hostname || Some(name.as_str()) == hostname.split('.').next())
+            {
+                host.push(inner);
+            } else if kind == "profile" && *name == self.profile {
+                profile.push(inner);
+            } else {
+                others.push(inner);
             }
-            _ => {
-                initializing_config.recreate = true;
-                initializing_config
-                    .warnings
-                    .push("Config file is or missing, will be recreated on exit!".to_owned())
+        }
+
+        let mut overrides: Vec<Entry> = Vec::<Entry>::new();
+        for assignment in self.overrides.clone() {
+            let (mut table, value) = match tomlconf::parse_assignment(assignment.as_str()) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    self.errors.push(format!("--set {}: {}", assignment, e));
+                    continue;
+                }
+            };
+            let mut key: String = table.pop().unwrap();
+            // * Options can be given by their name as well as by their key in the file, i.e. net_download or net.download
+            if let (true, Some(info)) = (table.len() == 0, Config::option(key.as_str())) {
+                table = info
+                    .section
+                    .split('.')
+                    .filter(|s| s.len() > 0)
+                    .map(|s| s.to_owned())
+                    .collect();
+                key = info.key().to_owned();
             }
+            overrides.push(Entry {
+                table,
+                key,
+                value,
+                line: 0,
+                column: 0,
+                source: format!("--set {}", assignment),
+            });
         }
 
-        for key in initializing_config.keys.clone() {
-            if conf.contains_key(&key) {
-                match conf.get(&key).unwrap() {
-                    ConfigItem::Error => {
-                        initializing_config.recreate = true;
+        self.apply_layer(&base);
+        if host.len() + profile.len() + overrides.len() > 0 {
+            let before: Config = self.clone();
+            self.apply_layer(&host);
+            self.apply_layer(&profile);
+            self.apply_layer(&overrides);
+            self.layered = Some(Box::new((before, self.clone())));
+        }
 
-                        let sender = match initializing_config
-                            .attr
-                            .get(&key.clone())
-                            .unwrap_or_else(|| {
-                                errlog(format!(
-                                    "Attempted to find attribute {} in CONFIG initialization",
-                                    key.clone()
-                                ));
-                                &ConfigItem::Error
-                            }) {
-                            ConfigItem::Str(s) => ConfigItem::Str(String::from(s)),
-                            ConfigItem::Int(i) => ConfigItem::Int(*i),
-                            ConfigItem::Bool(b) => ConfigItem::Bool(*b),
-                            ConfigItem::ViewMode(v) => ConfigItem::ViewMode(*v),
-                            ConfigItem::LogLevel(l) => ConfigItem::LogLevel(*l),
-                            ConfigItem::SortingOption(s) => ConfigItem::SortingOption(*s),
-                            ConfigItem::Error => ConfigItem::Error,
-                        };
+        // * Sections for other hosts and profiles are only checked for mistakes
+        let mut unused: Config = self.clone();
+        unused.warnings = Vec::<String>::new();
+        unused.apply_layer(&others);
+        self.warnings.append(&mut unused.warnings);
+    }
 
-                        initializing_config.conf_dict.insert(key, sender);
-                    }
-                    _ => {
-                        let sender = match conf.get(&key).unwrap_or_else(|| {
-                            errlog(format!(
-                                "Attempted to find attribute {} in CONFIG initialization",
-                                key.clone()
-                            ));
-                            &ConfigItem::Error
-                        }) {
-                            ConfigItem::Str(s) => ConfigItem::Str(String::from(s)),
-                            ConfigItem::Int(i) => ConfigItem::Int(*i),
-                            ConfigItem::Bool(b) => ConfigItem::Bool(*b),
-                            ConfigItem::ViewMode(v) => ConfigItem::ViewMode(*v),
-                            ConfigItem::LogLevel(l) => ConfigItem::LogLevel(*l),
-                            ConfigItem::SortingOption(s) => ConfigItem::SortingOption(*s),
-                            ConfigItem::Error => ConfigItem::Error,
-                        };
+    /// Set options, layouts and key bindings from TOML entries
+    fn apply_layer(&mut self, entries: &Vec<Entry>) {
+        let options: Vec<OptionInfo> = Config::options();
+        let mut layouts: Vec<String> = Vec::<String>::new();
+        let mut layouts_entry: Option<&Entry> = None;
 
-                        initializing_config.setattr(key, sender);
+        for entry in entries.iter() {
+            let section: String = entry.table.join(".");
+            if section == "keys" {
+                match &entry.value {
+                    Value::String(keys) if ACTIONS.iter().any(|(a, _, _)| *a == entry.key) => {
+                        self.key_bindings.insert(entry.key.clone(), keys.clone());
                     }
-                };
+                    Value::String(_) => self.warnings.push(format!(
+                        "{}: Unknown action {:?} in [keys]!",
+                        entry.position(),
+                        entry.key
+                    )),
+                    _ => self.errors.push(format!(
+                        "{}: Keys for {:?} should be a string!",
+                        entry.position(),
+                        entry.key
+                    )),
+                }
+                continue;
+            } else if section == "layouts" {
+                // * The layouts are joined to the "name=tree;name=tree" form of the layouts option
+                layouts.push(format!("{}={}", entry.key, entry.value.to_config_string()));
+                layouts_entry.get_or_insert(entry);
+                continue;
+            }
+
+            let info: &OptionInfo = match options
+                .iter()
+                .find(|info| info.section == section && info.key() == entry.key)
+            {
+                Some(info) => info,
+                None => {
+                    self.warnings.push(format!(
+                        "{}: Unknown config key {:?} in {}!",
+                        entry.position(),
+                        entry.key,
+                        if section.len() == 0 {
+                            "the top level".to_owned()
+                        } else {
+                            format!("[{}]", section)
+                        }
+                    ));
+                    continue;
+                }
+            };
+            let expected: &str = match (info.kind, &entry.value) {
+                (Kind::Bool, Value::Boolean(_))
+                | (Kind::Int, Value::Integer(_))
+                | (Kind::Text, Value::String(_))
+                | (Kind::Text, Value::Array(_)) => "",
+                (Kind::Bool, _) => "true or false",
+                (Kind::Int, _) => "an integer",
+                (Kind::Text, _) => "a string",
+            };
+            let result: Result<(), String> = if expected.len() > 0 {
+                Err(format!(
+                    "Config key {:?} should be {}!",
+                    entry.key, expected
+                ))
+            } else {
+                self.set_value(info.name, entry.value.to_config_string().as_str())
+            };
+            if let Err(e) = result {
+                self.errors.push(format!("{}: {}", entry.position(), e));
             }
         }
-        initializing_config._initialized = true;
 
-        Ok(initializing_config)
+        if let Some(entry) = layouts_entry {
+            if let Err(e) = self.set_value("layouts", layouts.join(";").as_str()) {
+                self.errors.push(format!("{}: {}", entry.position(), e));
+            }
+        }
     }
 
-    /// Returns a HashMap<String, ConfigItem> from the configuration file
-    pub fn load_config(&mut self) -> Result<HashMap<String, ConfigItem>, &'static str> {
-        let mut new_config = HashMap::<String, ConfigItem>::new();
+    /// Load a legacy config and write it as TOML to config_file, the legacy file is only
+    /// moved to a backup once the new file is written
+    fn migrate(&mut self, legacy: &PathBuf) -> Result<Option<String>, &'static str> {
+        let version: Option<String> = self.load_legacy(legacy)?;
+        let backup: PathBuf = legacy.with_extension("conf.bak");
+        self.recreate = true;
+        match self.save_config().and_then(|_| rename(legacy, &backup)) {
+            Ok(_) => {
+                self.recreate = false;
+                self.info.push(format!(
+                    "Migrated {} to {}, the old config was moved to {}",
+                    legacy.display(),
+                    self.config_file.display(),
+                    backup.display()
+                ));
+                Ok(Some(VERSION.to_owned()))
+            }
+            Err(e) => {
+                self.warnings.push(format!(
+                    "Unable to migrate {} to {}: {}",
+                    legacy.display(),
+                    self.config_file.display(),
+                    e
+                ));
+                Ok(version)
+            }
+        }
+    }
 
-        let conf_file = if self.config_file.is_file() {
-            self.config_file.clone()
-        } else if PathBuf::from("/etc/brshtop.conf").is_file() {
-            PathBuf::from("/etc/brshtop.conf")
-        } else if PathBuf::from(expanduser("~").unwrap().to_str().unwrap().to_owned() + "/.config/brshtop/brshtop.conf").is_file() {
-            PathBuf::from(expanduser("~").unwrap().to_str().unwrap().to_owned() + "/.config/brshtop/brshtop.conf")
-        } else {
-            return Err("Could not find config file.");
-        };
+    /// Load a bpytop style key=value config, values that don't parse or validate keep their
+    /// default and make the file be recreated
+    pub fn load_legacy(&mut self, path: &PathBuf) -> Result<Option<String>, &'static str> {
+        let mut version: Option<String> = None;
 
-        let file = match File::open(conf_file) {
+        let file = match File::open(path) {
             Ok(f) => f,
             Err(e) => return Err("Unable to read config file."),
         };
         let buf_reader = BufReader::new(file);
+        let mut in_keys: bool = false;
 
-        for line in buf_reader.lines() {
+        for (n, line) in buf_reader.lines().enumerate() {
             match line {
                 Ok(l) => {
                     // TODO: split into a separate function please and thank you @me
@@ -499,529 +1488,248 @@ impl Config {
                             None => return Err("Malformed configuration file."),
                         };
 
-                        new_config.insert(
-                            String::from("version"),
-                            ConfigItem::Str(stripped.chars().skip(index_of_version + 3).collect()),
-                        );
+                        version = Some(stripped.chars().skip(index_of_version + 3).collect());
                         continue;
                     }
 
-                    for key in &self.keys {
-                        let mut l_stripped: String = stripped.to_owned();
-                        if l_stripped.starts_with(key) {
-                            l_stripped = l_stripped.to_owned().replace(&(key.clone() + "="), "");
-                            if l_stripped.starts_with('"') {
-                                l_stripped.to_owned().retain(|c| c != '"');
-                            }
-
-                            type ConversionFunction = fn(&String) -> Result<ConfigItem, String>;
-
-                            let conversion_function: Option<ConversionFunction> = match key.as_str()
-                            {
-                                "proc_sorting" => Some(ConfigItem::sorting_option),
-                                "log_level" => Some(ConfigItem::log_level),
-                                "view_mode" => Some(ConfigItem::view_mode),
-                                _ => None,
-                            };
-
-                            if let Some(f) = conversion_function {
-                                let config_item = match f(&(l_stripped.to_owned())) {
-                                    Ok(item) => item,
-                                    Err(e) => {
-                                        self.warnings.push(e);
-                                        ConfigItem::Error
-                                    }
-                                };
-
-                                new_config.insert(key.clone(), config_item);
-                            } else if !l_stripped.chars().all(char::is_numeric) {
-                                let i = match l_stripped.parse::<i64>() {
-                                    Ok(i) => i,
-                                    Err(_e) => {
-                                        self.warnings.push(format!(
-                                            "Config key {:?} should be an integer (was {:?})!",
-                                            key, l_stripped
-                                        ));
-                                        continue;
-                                    }
-                                };
-
-                                if key == "update_ms" && i < 100 {
-                                    self.warnings.push(
-                                        "Config key \"update_ms\" can\'t be lower than 100!"
-                                            .to_owned(),
-                                    );
-                                    new_config.insert(key.to_owned(), ConfigItem::Int(100));
-                                    continue;
-                                }
-
-                                new_config.insert(key.to_owned(), ConfigItem::Int(i));
-                                continue;
-                            } else {
-                                match l_stripped.parse::<LenientBool>() {
-                                    Ok(b) => {
-                                        new_config
-                                            .insert(key.to_owned(), ConfigItem::Bool(b.into()));
-                                        continue;
-                                    }
-                                    Err(e) => (),
-                                };
-
-                                new_config
-                                    .insert(key.to_owned(), ConfigItem::Str(l_stripped.to_owned()));
-                            }
-                        }
+                    // * Everything after a [keys] line is action="keys"
+                    if stripped == "[keys]" {
+                        in_keys = true;
+                        continue;
                     }
-                }
-                Err(e) => return Err("Unable to read config file."),
-            };
-        }
-
-        for net_name in ["net_download", "net_upload"]
-            .iter()
-            .map(|s| s.to_owned().to_owned())
-            .collect::<Vec<String>>()
-        {
-            if new_config.contains_key(&net_name.clone()) {
-                match new_config.get(&net_name.clone()).unwrap() {
-                    ConfigItem::Str(s) => {
-                        match s.chars().next() {
-                            Some(c) => {
-                                if !c.is_numeric() {
-                                    new_config.insert(net_name.clone(), ConfigItem::Error);
-                                    self.warnings.push(format!(
-                                        "Config key \"{}\" didn\'t get an acceptable value!",
-                                        net_name.clone()
-                                    ));
-                                }
-                            }
+                    if in_keys {
+                        if stripped.len() == 0 || stripped.starts_with('#') {
+                            continue;
+                        }
+                        let (action, keys) = match stripped.find('=') {
+                            Some(i) => (stripped[..i].trim(), stripped[i + 1..].trim()),
                             None => {
-                                new_config.insert(net_name.clone(), ConfigItem::Error);
-                                self.warnings.push(format!(
-                                    "Config key \"{}\" didn\'t get an acceptable value!",
-                                    net_name.clone()
+                                self.errors.push(format!(
+                                    "Line {:?} in [keys] should be action=\"keys\"!",
+                                    stripped
                                 ));
+                                continue;
                             }
                         };
+                        if ACTIONS.iter().any(|(a, _, _)| *a == action) {
+                            self.key_bindings
+                                .insert(action.to_owned(), keys.trim_matches('"').to_owned());
+                        } else {
+                            self.warnings
+                                .push(format!("Unknown action {:?} in [keys]!", action));
+                        }
+                        continue;
                     }
-                    _ => {
-                        new_config.insert(net_name.clone(), ConfigItem::Error);
-                        self.warnings.push(format!(
-                            "Config key \"{}\" didn\'t get an acceptable value!",
-                            net_name.clone()
-                        ));
+
+                    if stripped.starts_with('#') {
+                        continue;
+                    }
+                    let (key, value, column) = match l.find('=') {
+                        Some(i) => (l[..i].trim(), l[i + 1..].trim(), i + 2),
+                        None => continue,
+                    };
+                    let position: String = format!("Line {}, column {}", n + 1, column);
+                    if Config::option(key).is_none() {
+                        self.warnings
+                            .push(format!("{}: Unknown config key {:?}!", position, key));
+                        self.recreate = true;
+                    } else if let Err(e) = self.set_value(key, value.trim_matches('"')) {
+                        self.errors.push(format!("{}: {}", position, e));
+                        self.recreate = true;
                     }
                 }
-            }
+                Err(e) => return Err("Unable to read config file."),
+            };
         }
 
-        match new_config.get("cpu_sensor") {
-            Some(c) => match c {
-                ConfigItem::Str(s) => {
-                    if !self.cpu_sensor.contains(s) {
-                        new_config.insert("cpu_sensor".to_owned(), ConfigItem::Error);
-                        self.warnings.push(format!(
-                            "Config key \"cpu_sensor\" does not contain an available sensor!"
-                        ));
-                    }
-                }
-                _ => {
-                    new_config.insert("cpu_sensor".to_owned(), ConfigItem::Error);
-                    self.warnings
-                        .push(format!("Config key \"cpu_sensor\" has a malformed value!"));
-                }
-            },
+        // * Legacy configs have no host or profile sections, but --set still applies
+        self.apply_entries(&Vec::<Entry>::new());
+        return Ok(version);
+    }
+
+    /// The value of an option as shown and edited in the options menu
+    pub fn getattr(&self, attr: String) -> ConfigAttr {
+        let value: String = match self.get_value(attr.as_str()) {
+            Some(v) => v,
             None => {
-                new_config.insert("cpu_sensor".to_owned(), ConfigItem::Error);
-                self.warnings.push(format!(
-                    "Config key \"cpu_sensor\" has a malformed value or does not exist!"
+                errlog(format!(
+                    "Malformed attr {} found in getattr, defaulting...",
+                    attr.clone()
                 ));
+                return ConfigAttr::String(String::default());
             }
         };
+        match Config::option(attr.as_str()).map(|info| info.kind) {
+            Some(Kind::Bool) => ConfigAttr::Bool(value == "true"),
+            Some(Kind::Int) => ConfigAttr::Int64(value.parse::<i64>().unwrap_or_default()),
+            _ => ConfigAttr::String(value),
+        }
+    }
 
-        return Ok(new_config);
+    pub fn setattr_configattr(&mut self, attr: String, to_set: ConfigAttr) {
+        if let Err(e) = self.set_value(attr.as_str(), to_set.to_string().as_str()) {
+            throw_error(format!("Illegal attribute set in CONFIG: {}", e).as_str());
+            return;
+        }
+        self.mark_changed(attr.as_str());
     }
 
-    pub fn setattr(&mut self, name: String, value: ConfigItem) {
+    /// Note an option changed while running, so it is saved with its new value
+    pub fn mark_changed(&mut self, name: &str) {
+        if !self.changed_options.iter().any(|n| n == name) {
+            self.changed_options.push(name.to_owned());
+        }
         if self._initialized {
             self.changed = true;
         }
+    }
 
-        self.attr.insert(name.clone(), value.clone());
-
-        let test_values = vec!["_initialized", "recreate", "changed"];
-        let test_values_converted: Vec<String> =
-            test_values.iter().map(<_ as ToString>::to_string).collect();
+    /// Names of the themes the config uses, also the ones from host and profile sections
+    pub fn theme_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = vec![self.color_theme.clone(), self.color_theme_light.clone()];
+        if let Some(layered) = &self.layered {
+            names.push(layered.0.color_theme.clone());
+            names.push(layered.0.color_theme_light.clone());
+        }
+        for entry in self.layer_entries.iter() {
+            if let (2, "color_theme", Value::String(name))
+            | (2, "color_theme_light", Value::String(name)) =
+                (entry.table.len(), entry.key.as_str(), &entry.value)
+            {
+                names.push(name.clone());
+            }
+        }
+        names.retain(|n| n.len() > 0);
+        names.sort();
+        names.dedup();
+        names
+    }
 
-        if test_values_converted.contains(&name) {
-            self.conf_dict.insert(name.clone(), value.clone());
+    /// Theme to use on a light or dark terminal background, color_theme unless a light theme is set
+    pub fn theme_name(&self, light: bool) -> String {
+        if light && self.color_theme_light.len() > 0 {
+            self.color_theme_light.clone()
+        } else {
+            self.color_theme.clone()
         }
     }
 
-    pub fn getattr(&self, attr: String) -> ConfigAttr {
-        match attr.clone().as_str() {
-            "color_theme" => ConfigAttr::String(self.color_theme.clone()),
-            "theme_background" => ConfigAttr::Bool(self.theme_background),
-            "view_mode" => ConfigAttr::ViewMode(self.view_mode),
-            "update_ms" => ConfigAttr::Int64(self.update_ms),
-            "proc_sorting" => ConfigAttr::SortingOption(self.proc_sorting),
-            "proc_reversed" => ConfigAttr::Bool(self.proc_reversed),
-            "proc_tree" => ConfigAttr::Bool(self.proc_tree),
-            "tree_depth" => ConfigAttr::Int64(self.tree_depth as i64),
-            "proc_colors" => ConfigAttr::Bool(self.proc_colors),
-            "proc_gradient" => ConfigAttr::Bool(self.proc_gradient),
-            "proc_per_core" => ConfigAttr::Bool(self.proc_per_core),
-            "proc_mem_bytes" => ConfigAttr::Bool(self.proc_mem_bytes),
-            "check_temp" => ConfigAttr::Bool(self.check_temp),
-            "cpu_sensor" => ConfigAttr::String(self.cpu_sensor.clone()),
-            "show_coretemp" => ConfigAttr::Bool(self.show_coretemp),
-            "draw_clock" => ConfigAttr::String(self.draw_clock.clone()),
-            "background_update" => ConfigAttr::Bool(self.background_update),
-            "custom_cpu_name" => ConfigAttr::String(self.custom_cpu_name.clone()),
-            "disks_filter" => ConfigAttr::String(self.disks_filter.clone()),
-            "mem_graphs" => ConfigAttr::Bool(self.mem_graphs),
-            "show_swap" => ConfigAttr::Bool(self.show_swap),
-            "swap_disk" => ConfigAttr::Bool(self.swap_disk),
-            "show_disks" => ConfigAttr::Bool(self.show_disks),
-            "net_download" => ConfigAttr::String(self.net_download.clone()),
-            "net_upload" => ConfigAttr::String(self.net_upload.clone()),
-            "net_auto" => ConfigAttr::Bool(self.net_auto),
-            "net_sync" => ConfigAttr::Bool(self.net_sync),
-            "net_color_fixed" => ConfigAttr::Bool(self.net_color_fixed),
-            "show_battery" => ConfigAttr::Bool(self.show_battery),
-            "show_init" => ConfigAttr::Bool(self.show_init),
-            "update_check" => ConfigAttr::Bool(self.update_check),
-            "log_level" => ConfigAttr::LogLevel(self.log_level),
-            _ => {
-                errlog(format!(
-                    "Malformed attr {} found in getattr, defaulting...",
-                    attr.clone()
-                ));
-                ConfigAttr::String(String::default())
+    /// The value an option is saved with, values from the host and profile sections or --set
+    /// are saved as they were in the rest of the file unless the option was changed since
+    fn saved_value(&self, name: &str) -> String {
+        match &self.layered {
+            Some(layered) if !self.changed_options.iter().any(|n| n == name) => {
+                layered.0.get_value(name)
             }
+            _ => self.get_value(name),
         }
+        .unwrap_or_default()
     }
 
-    pub fn setattr_configattr(&mut self, attr: String, to_set: ConfigAttr) {
-        match attr.as_str() {
-            "color_theme" => {
-                self.color_theme = match to_set {
-                    ConfigAttr::String(s) => s.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        String::default()
-                    }
-                }
-            }
-            "theme_background" => {
-                self.theme_background = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
-                }
-            }
-            "view_mode" => {
-                self.view_mode = match to_set {
-                    ConfigAttr::ViewMode(v) => v.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        ViewMode {
-                            t: ViewModeEnum::None,
-                        }
-                    }
-                }
-            }
-            "update_ms" => {
-                self.update_ms = match to_set {
-                    ConfigAttr::Int64(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        0
-                    }
-                }
-            }
-            "proc_sorting" => {
-                self.proc_sorting = match to_set {
-                    ConfigAttr::SortingOption(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        SortingOption::Arguments
-                    }
-                }
-            }
-            "proc_reversed" => {
-                self.proc_reversed = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
-                }
-            }
-            "proc_tree" => {
-                self.proc_tree = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
-                }
-            }
-            "tree_depth" => {
-                self.tree_depth = match to_set {
-                    ConfigAttr::Int64(b) => b.clone() as i32,
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        0
-                    }
-                }
-            }
-            "proc_colors" => {
-                self.proc_colors = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
-                }
-            }
-            "proc_gradient" => {
-                self.proc_gradient = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
-                }
-            }
-            "proc_per_core" => {
-                self.proc_per_core = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
-                }
-            }
-            "proc_mem_bytes" => {
-                self.proc_mem_bytes = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
-                }
-            }
-            "check_temp" => {
-                self.check_temp = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
-                }
-            }
-            "cpu_sensor" => {
-                self.cpu_sensor = match to_set {
-                    ConfigAttr::String(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        String::default()
-                    }
-                }
-            }
-            "show_coretemp" => {
-                self.show_coretemp = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
-                }
-            }
-            "draw_clock" => {
-                self.draw_clock = match to_set {
-                    ConfigAttr::String(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        String::default()
-                    }
-                }
-            }
-            "background_update" => {
-                self.background_update = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
-                }
-            }
-            "custom_cpu_name" => {
-                self.custom_cpu_name = match to_set {
-                    ConfigAttr::String(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        String::default()
-                    }
-                }
-            }
-            "disks_filter" => {
-                self.disks_filter = match to_set {
-                    ConfigAttr::String(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        String::default()
-                    }
-                }
-            }
-            "mem_graphs" => {
-                self.mem_graphs = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
-                }
-            }
-            "show_swap" => {
-                self.show_swap = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
-                }
-            }
-            "swap_disk" => {
-                self.swap_disk = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
-                }
-            }
-            "show_disks" => {
-                self.show_disks = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
-                }
-            }
-            "net_download" => {
-                self.net_download = match to_set {
-                    ConfigAttr::String(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        String::default()
-                    }
-                }
-            }
-            "net_upload" => {
-                self.net_upload = match to_set {
-                    ConfigAttr::String(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        String::default()
-                    }
-                }
-            }
-            "net_auto" => {
-                self.net_auto = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
-                }
-            }
-            "net_sync" => {
-                self.net_sync = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
-                }
-            }
-            "net_color_fixed" => {
-                self.net_color_fixed = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
-                }
-            }
-            "show_battery" => {
-                self.show_battery = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
-                }
+    pub fn save_config(&self) -> std::io::Result<String> {
+        if !self.changed && !self.recreate {
+            return Ok("Nothing needs to be changed".into());
+        }
+
+        write(self.config_file.clone(), self.render())?;
+        Ok("Saved Successfully".into())
+    }
+
+    /// The config file as save_config writes it
+    fn render(&self) -> String {
+        let options: Vec<OptionInfo> = Config::options();
+        let mut sections: Vec<&str> = vec![""];
+        for info in options.iter() {
+            if !sections.contains(&info.section) {
+                sections.push(info.section);
             }
-            "show_init" => {
-                self.show_init = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
-                }
+        }
+
+        let mut rendered: String =
+            format!("#? Config file for brshtop v. {}\n", VERSION.to_owned());
+        for section in sections {
+            let mut first: bool = true;
+            if section.len() > 0 {
+                rendered.push_str(format!("\n[{}]\n", section).as_str());
             }
-            "update_check" => {
-                self.update_check = match to_set {
-                    ConfigAttr::Bool(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        false
-                    }
+            // * The layouts option gets a table of its own below
+            for info in options
+                .iter()
+                .filter(|info| info.section == section && info.name != "layouts")
+            {
+                if info.comment.len() > 0 && !first {
+                    rendered.push('\n');
                 }
-            }
-            "log_level" => {
-                self.log_level = match to_set {
-                    ConfigAttr::LogLevel(b) => b.clone(),
-                    _ => {
-                        throw_error("Illegal attribute set in CONFIG");
-                        LogLevel::Debug
-                    }
+                first = false;
+                for line in info.comment.iter() {
+                    rendered.push_str(format!("#* {}\n", line).as_str());
                 }
-            }
-            _ => {
-                throw_error(
-                    format!("Illegal attribute {} to be set on CONFIG", attr.clone()).as_str(),
+                let value: String = self.saved_value(info.name);
+                rendered.push_str(
+                    format!(
+                        "{} = {}\n",
+                        info.key(),
+                        match info.kind {
+                            Kind::Text => tomlconf::quote(&value),
+                            _ => value,
+                        }
+                    )
+                    .as_str(),
                 );
-                return;
             }
         }
-    }
 
-    pub fn save_config(& self) -> std::io::Result<String> {
-        if !self.changed && !self.recreate {
-            return Ok("Nothing needs to be changed".into());
+        rendered.push_str("\n[layouts]\n#* name = \"tree\", a tree is a box (cpu, mem, net or proc), h[...] for boxes side by side or v[...] for stacked boxes.\n#* Add :N after a box or split to set its relative size, boxes left out of a layout are hidden.\n");
+        for layout in Layout::parse_all(&self.saved_value("layouts")).unwrap_or_default() {
+            rendered.push_str(
+                format!(
+                    "{} = {}\n",
+                    tomlconf::dotted_key(&[layout.name.as_str()]),
+                    tomlconf::quote(&layout.root.to_string())
+                )
+                .as_str(),
+            );
         }
 
-        let vals: HashMap<String, String> = self
-            .conf_dict
-            .iter()
-            .map(|(key, value)| (key.clone(), value.clone().to_string()))
-            .collect();
-        write(
-            self.config_file.clone(),
-            crate::DEFAULT_CONF.render(
-                &vals
-                    .iter()
-                    .map(|(key, value)| (key.as_str(), value.as_str()))
-                    .collect(),
-            ),
-        )?;
-        Ok("Saved Successfully".into())
+        // * Key overrides are kept in their own section at the end of the file
+        rendered.push_str("\n[keys]\n#* action = \"keys\", alternatives separated by \"|\" and key sequences by a space, i.e. home = \"g g|home\"\n");
+        let key_bindings: &HashMap<String, String> = match &self.layered {
+            Some(layered) => &layered.0.key_bindings,
+            _ => &self.key_bindings,
+        };
+        let mut overrides: Vec<(&String, &String)> = key_bindings.iter().collect();
+        overrides.sort();
+        for (action, keys) in overrides {
+            rendered.push_str(format!("{} = {}\n", action, tomlconf::quote(keys)).as_str());
+        }
+
+        if self.layer_entries.len() > 0 {
+            rendered.push_str("\n#* Sections applied on top of the options above, [host.\"name\"] on the host with that name\n#* and [profile.\"name\"] when started with --profile name. They take the same sections and keys.\n");
+        }
+        let mut table: Vec<String> = Vec::<String>::new();
+        for entry in self.layer_entries.iter() {
+            if entry.table != table {
+                table = entry.table.clone();
+                rendered.push_str(
+                    format!(
+                        "\n[{}]\n",
+                        tomlconf::dotted_key(
+                            &table.iter().map(|t| t.as_str()).collect::<Vec<&str>>()
+                        )
+                    )
+                    .as_str(),
+                );
+            }
+            rendered.push_str(
+                format!(
+                    "{} = {}\n",
+                    tomlconf::dotted_key(&[entry.key.as_str()]),
+                    entry.value.to_toml()
+                )
+                .as_str(),
+            );
+        }
+
+        rendered
     }
 }
@@ -39,6 +39,7 @@ pub struct CpuBox {
     battery_clear: bool,
     battery_symbols: HashMap<String, String>,
     clock_block: bool,
+    selected_core: usize,
 }
 impl CpuBox {
     pub fn new(brshtop_box: &mut BrshtopBox, config: &Config, ARG_MODE: ViewMode) -> Self {
@@ -64,6 +65,7 @@ impl CpuBox {
             old_battery_len: 0,
             battery_path: Some("".to_owned()),
             battery_clear: false,
+            selected_core: 0,
             battery_symbols: bsm.clone(),
             clock_block: true,
         };
@@ -84,21 +86,34 @@ impl CpuBox {
         };
         let term = term;
 
-        self.set_parent_width(
-            (term.get_width() as u32 * self.get_parent().get_width_p() / 100) as u32,
-        );
-        self.set_parent_height(
-            (term.get_height() as u32 * self.get_parent().get_height_p() / 100) as u32,
-        );
-
-        if self.get_parent().get_height() < 8 {
-            self.set_parent_height(8);
+        match self.get_parent().get_layout_rect() {
+            Some((x, y, w, h)) => {
+                self.set_parent_x(x);
+                self.set_parent_y(y);
+                self.set_parent_width(w);
+                self.set_parent_height(h);
+            }
+            None => {
+                self.set_parent_x(1);
+                self.set_parent_y(1);
+                self.set_parent_width(
+                    (term.get_width() as u32 * self.get_parent().get_width_p() / 100) as u32,
+                );
+                self.set_parent_height(
+                    (term.get_height() as u32 * self.get_parent().get_height_p() / 100) as u32,
+                );
+                if self.get_parent().get_height() < 8 {
+                    self.set_parent_height(8);
+                }
+            }
         }
         let mut set_b_cpu_h = _b_cpu_h.clone();
         set_b_cpu_h = self.get_parent().get_height() as i32;
 
         self.set_sub_box_columns(ceil(
-            (((THREADS.to_owned() + 1) / (self.get_parent().get_height() - 5) as u64) + 1) as f64,
+            (((THREADS.to_owned() + 1)
+                / self.get_parent().get_height().saturating_sub(5).max(1) as u64)
+                + 1) as f64,
             0,
         ) as u32);
 
@@ -153,7 +168,8 @@ impl CpuBox {
 
         self.set_sub_box_x(
             u32::try_from(
-                (self.get_parent().get_width() as i32 - 1) - self.get_sub().get_box_width() as i32,
+                (self.get_parent().get_x() as i32 + self.get_parent().get_width() as i32 - 2)
+                    - self.get_sub().get_box_width() as i32,
             )
             .unwrap_or(0),
         );
@@ -177,6 +193,10 @@ impl CpuBox {
     }
 
     pub fn draw_bg(&self, key: &mut Key, theme: &Theme, term: &Term, config: &Config) -> String {
+        if self.get_parent().get_hidden() {
+            return String::default();
+        }
+
         if !key.mouse.contains_key(&"M".to_owned()) {
             let mut top: Vec<Vec<i32>> = Vec::<Vec<i32>>::new();
             for i in 0..6 {
@@ -197,7 +217,11 @@ impl CpuBox {
                 0,
                 Some(String::default()),
                 Some(String::default()),
-                Some(theme.colors.cpu_box),
+                Some(if self.get_parent().is_focused() {
+                    theme.colors.hi_fg
+                } else {
+                    theme.colors.cpu_box
+                }),
                 None,
                 true,
                 Some(Boxes::CpuBox),
@@ -213,7 +237,7 @@ impl CpuBox {
             theme
                 .colors
                 .cpu_box
-                .call(symbol::title_left.to_owned(), term),
+                .call(symbol::title_left().to_owned(), term),
             fx::b,
             theme.colors.hi_fg.call("M".to_owned(), term),
             theme.colors.title.call("enu".to_owned(), term),
@@ -221,7 +245,7 @@ impl CpuBox {
             theme
                 .colors
                 .cpu_box
-                .call(symbol::title_right.to_owned(), term),
+                .call(symbol::title_right().to_owned(), term),
             create_box(
                 self.get_sub().get_box_x(),
                 self.get_sub().get_box_y(),
@@ -405,6 +429,10 @@ impl CpuBox {
         menu: &Menu,
         THEME: &Theme,
     ) {
+        if self.get_parent().get_hidden() {
+            return;
+        }
+
         if cpu.get_parent().get_redraw() {
             self.set_redraw(true);
         }
@@ -454,7 +482,7 @@ impl CpuBox {
                 theme
                     .colors
                     .cpu_box
-                    .call(symbol::title_left.to_owned(), term),
+                    .call(symbol::title_left().to_owned(), term),
                 fx::b,
                 theme.colors.hi_fg.call("m".to_owned(), term),
                 theme.colors.title,
@@ -463,7 +491,7 @@ impl CpuBox {
                 theme
                     .colors
                     .cpu_box
-                    .call(symbol::title_right.to_owned(), term)
+                    .call(symbol::title_right().to_owned(), term)
             )
             .as_str();
             graphs.cpu.insert(
@@ -515,18 +543,18 @@ impl CpuBox {
             if sub.get_column_size() > 0 || ct_width > 0 {
                 for n in 0..THREADS.to_owned() as usize {
                     graphs.cores[n] = Graph::new(
-                        5,
+                        (5 * sub.get_column_size() + ct_width) as i32,
                         1,
                         None,
-                        cpu.get_cpu_temp_index(0)
+                        cpu.get_cpu_usage_index(n + 1)
                             .unwrap()
                             .iter()
                             .map(|u| *u as i32)
                             .collect(),
                         term,
                         false,
-                        cpu.get_cpu_temp_crit(),
-                        -23,
+                        0,
+                        0,
                         None,
                     );
                 }
@@ -635,7 +663,7 @@ impl CpuBox {
                         theme
                             .colors
                             .cpu_box
-                            .call(symbol::h_line.repeat(self.old_battery_len + 4), term)
+                            .call(symbol::h_line().repeat(self.old_battery_len + 4), term)
                     )
                     .as_str(),
                 );
@@ -651,7 +679,7 @@ impl CpuBox {
                     theme
                         .colors
                         .cpu_box
-                        .call(symbol::title_left.to_owned(), term),
+                        .call(symbol::title_left().to_owned(), term),
                     fx::b,
                     theme.colors.title,
                     battery_symbol,
@@ -674,7 +702,7 @@ impl CpuBox {
                     theme
                         .colors
                         .cpu_box
-                        .call(symbol::title_right.to_owned(), term),
+                        .call(symbol::title_right().to_owned(), term),
                 )
                 .as_str(),
             );
@@ -698,7 +726,7 @@ impl CpuBox {
                     theme
                         .colors
                         .cpu_box
-                        .call(symbol::h_line.repeat(self.old_battery_len + 4), term),
+                        .call(symbol::h_line().repeat(self.old_battery_len + 4), term),
                 )
                 .as_str(),
             );
@@ -732,19 +760,25 @@ impl CpuBox {
                     theme
                         .colors
                         .div_line
-                        .call(symbol::title_left.to_owned(), term),
+                        .call(symbol::title_left().to_owned(), term),
                     fx::b,
                     theme.colors.title.call(freq, term),
                     fx::ub,
                     theme
                         .colors
                         .div_line
-                        .call(symbol::title_right.to_owned(), term),
+                        .call(symbol::title_right().to_owned(), term),
                 )
                 .as_str(),
             );
         }
 
+        if let Some(graph) = graphs.cpu.get_mut(&"up".to_owned()) {
+            graph.place(x, y);
+        }
+        if let Some(graph) = graphs.cpu.get_mut(&"down".to_owned()) {
+            graph.place(x, y + hh as u32);
+        }
         out.push_str(
             format!(
                 "{}{}{}{}{}{}{}{}{}{}{}{:>4}{}%",
@@ -762,7 +796,7 @@ impl CpuBox {
                     term
                 ),
                 mv::to(y + hh as u32, x),
-                graphs.cpu.get_mut(&"up".to_owned()).unwrap().call(
+                graphs.cpu.get_mut(&"down".to_owned()).unwrap().call(
                     if self.get_parent().get_resized() {
                         None
                     } else {
@@ -832,23 +866,44 @@ impl CpuBox {
 
         cy += 1;
         for n in 1..(THREADS.to_owned() + 1) as usize {
+            // * Clicking a core label focuses the cpu box with that core selected
+            key.mouse.insert(
+                format!("core_{}", n),
+                (0..n.to_string().len() as u32 + 1)
+                    .map(|i| vec![(bx + cx + i) as i32, (by + cy) as i32])
+                    .collect(),
+            );
             out.push_str(
                 format!(
                     "{}{}{}{:<width$}",
-                    theme.colors.main_fg,
+                    if n == self.selected_core {
+                        theme.colors.hi_fg
+                    } else {
+                        theme.colors.main_fg
+                    },
                     mv::to(by + cy, bx + cx),
                     fx::b.to_owned() + "C" + if THREADS.to_owned() < 100 { fx::ub } else { "" },
-                    if self.get_sub().get_column_size() == 0 {
+                    n,
+                    width = if self.get_sub().get_column_size() == 0 {
                         2
                     } else {
                         3
                     },
-                    width = n,
                 )
                 .as_str(),
             );
 
             if self.get_sub().get_column_size() > 0 || ct_width > 0 {
+                // * The graph follows the "C" and the core number
+                graphs.cores[n - 1].place(
+                    bx + cx
+                        + if self.get_sub().get_column_size() == 0 {
+                            3
+                        } else {
+                            4
+                        },
+                    by + cy,
+                );
                 out.push_str(
                     format!(
                         "{}{}{}{}{}",
@@ -863,8 +918,8 @@ impl CpuBox {
                                 None
                             } else {
                                 Some(
-                                    cpu.get_cpu_temp_index(n).unwrap()
-                                        [cpu.get_cpu_temp_index(n).unwrap().len() - 2]
+                                    cpu.get_cpu_usage_index(n).unwrap()
+                                        [cpu.get_cpu_usage_index(n).unwrap().len() - 2]
                                         as i32,
                                 )
                             },
@@ -972,7 +1027,7 @@ impl CpuBox {
                 theme
                     .colors
                     .div_line
-                    .call(symbol::v_line.to_owned(), term)
+                    .call(symbol::v_line().to_owned(), term)
                     .to_string()
                     .as_str(),
             );
@@ -1034,7 +1089,10 @@ impl CpuBox {
                     mv::to(by + cy, bx + cx),
                     theme.colors.main_fg,
                     lavg,
-                    theme.colors.div_line.call(symbol::v_line.to_owned(), term)
+                    theme
+                        .colors
+                        .div_line
+                        .call(symbol::v_line().to_owned(), term)
                 )
                 .as_str(),
             );
@@ -1143,6 +1201,40 @@ impl CpuBox {
         self.redraw.clone()
     }
 
+    pub fn get_selected_core(&self) -> usize {
+        self.selected_core.clone()
+    }
+
+    pub fn set_selected_core(&mut self, selected_core: usize) {
+        self.selected_core = selected_core.clone()
+    }
+
+    /// Move the core selection when the cpu box has focus, 0 is no selection
+    pub fn select_core(&mut self, key: String) {
+        let threads: usize = THREADS.to_owned() as usize;
+        self.selected_core = match key.as_str() {
+            "up" | "left" => {
+                if self.selected_core <= 1 {
+                    threads
+                } else {
+                    self.selected_core - 1
+                }
+            }
+            "down" | "right" => {
+                if self.selected_core >= threads {
+                    1
+                } else {
+                    self.selected_core + 1
+                }
+            }
+            "home" | "page_up" => 1,
+            "end" | "page_down" => threads,
+            // * Keys that don't move the selection keep it as is
+            _ => return,
+        };
+        self.redraw = true;
+    }
+
     pub fn set_redraw(&mut self, redraw: bool) {
         self.redraw = redraw.clone()
     }
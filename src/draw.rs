@@ -3,6 +3,7 @@ use {
         error::errlog,
         event::{Event, EventEnum},
         key::Key,
+        screen::Screen,
     },
     std::{
         collections::HashMap,
@@ -18,6 +19,7 @@ pub struct Draw {
     pub save: HashMap<String, bool>,
     pub once: HashMap<String, bool>,
     pub idle: Event,
+    pub screen: Screen,
 }
 impl Draw {
     pub fn new() -> Self {
@@ -30,6 +32,7 @@ impl Draw {
             idle: Event {
                 t: EventEnum::Flag(true),
             },
+            screen: Screen::new(),
         }
     }
 
@@ -48,11 +51,10 @@ impl Draw {
 
         self.idle.replace_self(EventEnum::Flag(false));
 
-        print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
-        for s in args.clone() {
-            io::stdout().write_all(s.as_bytes()).unwrap();
-            io::stdout().flush().unwrap();
-        }
+        // * Only the cells that changed since the last write are sent to the terminal
+        let out: String = self.screen.render(&args);
+        io::stdout().write_all(out.as_bytes()).unwrap();
+        io::stdout().flush().unwrap();
 
         self.idle.replace_self(EventEnum::Flag(true));
     }
@@ -64,11 +66,8 @@ impl Draw {
 
         self.idle.replace_self(EventEnum::Flag(false));
 
-        print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
-        for s in args.clone() {
-            print!("{}", s);
-            io::stdout().flush().unwrap();
-        }
+        print!("{}", self.screen.render(&args));
+        io::stdout().flush().unwrap();
 
         self.idle.replace_self(EventEnum::Flag(true));
     }
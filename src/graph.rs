@@ -1,5 +1,6 @@
 use {
     crate::{error::errlog, mv, symbol, term::Term, theme::Color},
+    chrono::{offset::Local, DateTime},
     maplit::hashmap,
     math::round::ceil,
     std::{
@@ -20,6 +21,47 @@ pub struct Graphs {
     pub detailed_cpu: Graph,
     pub detailed_mem: Graph,
     pub pid_cpu: HashMap<u32, Graph>, // TODO: PID type
+    pub hovering: bool,
+}
+impl Graphs {
+    /// Name, value and time of the sample under x, y if it is over a placed graph
+    pub fn hover(&self, x: i32, y: i32) -> Option<(String, i32, Option<DateTime<Local>>)> {
+        let mut placed: Vec<(String, &Graph)> = Vec::<(String, &Graph)>::new();
+        for (name, graph) in self.cpu.iter() {
+            placed.push((format!("cpu {}", name), graph));
+        }
+        for (n, graph) in self.cores.iter().enumerate() {
+            placed.push((format!("core {}", n + 1), graph));
+        }
+        for (name, graph) in self.net.iter() {
+            placed.push((format!("net {}", name), graph));
+        }
+        placed.push(("process cpu".to_owned(), &self.detailed_cpu));
+        placed.push(("process memory".to_owned(), &self.detailed_mem));
+
+        for (name, graph) in placed {
+            if let Some((value, time)) = graph.value_at(x, y) {
+                return Some((name, value, time));
+            }
+        }
+        None
+    }
+
+    /// Forget where every graph was drawn, the boxes place their graphs again on the next draw
+    pub fn unplace(&mut self) {
+        for graph in self.cpu.values_mut() {
+            graph.unplace();
+        }
+        for graph in self.cores.iter_mut() {
+            graph.unplace();
+        }
+        for graph in self.net.values_mut() {
+            graph.unplace();
+        }
+        self.detailed_cpu.unplace();
+        self.detailed_mem.unplace();
+        self.hovering = false;
+    }
 }
 
 pub enum ColorSwitch {
@@ -46,6 +88,10 @@ pub struct Graph {
     pub last: i32,
     pub symbol: HashMap<u32, &'static str>,
     pub _data: Vec<i32>, // TODO: Data type
+    /// Unscaled values with the time they were added, None for values the graph was created with
+    pub history: Vec<(i32, Option<DateTime<Local>>)>,
+    /// Top left corner of the graph on screen, set when the graph is drawn
+    pub pos: Option<(u32, u32)>,
     pub NotImplemented: bool,
 }
 impl Graph {
@@ -134,6 +180,8 @@ impl Graph {
             graphs,
             current: false,
             last: 0,
+            history: data.iter().map(|v| (*v, None)).collect(),
+            pos: None,
             NotImplemented: false,
         };
 
@@ -268,6 +316,8 @@ impl Graph {
             graphs,
             current: false,
             last: 0,
+            history: data.iter().map(|v| (*v, None)).collect(),
+            pos: None,
             NotImplemented: false,
         };
 
@@ -477,6 +527,11 @@ impl Graph {
         if let Some(value) = value {
             self.current = !self.current;
 
+            self.history.push((value, Some(Local::now())));
+            while self.history.len() > self.width as usize * 2 {
+                self.history.remove(0);
+            }
+
             if self.height == 1 {
                 let checker1 = self.graphs.get(&self.current).unwrap().clone();
                 let checker2 = checker1.get(0).unwrap().clone();
@@ -521,6 +576,31 @@ impl Graph {
     pub fn add(&mut self, value: Option<i32>, term: &Term) -> String {
         self.call(value, term)
     }
+
+    pub fn place(&mut self, x: u32, y: u32) {
+        self.pos = Some((x, y));
+    }
+
+    pub fn unplace(&mut self) {
+        self.pos = None;
+    }
+
+    /// The newest value drawn in the column at x, y, every column holds two values
+    pub fn value_at(&self, x: i32, y: i32) -> Option<(i32, Option<DateTime<Local>>)> {
+        let (gx, gy) = self.pos?;
+        if x < gx as i32
+            || x >= (gx + self.width) as i32
+            || y < gy as i32
+            || y >= (gy + self.height) as i32
+        {
+            return None;
+        }
+        let from_end: usize = (gx + self.width - 1 - x as u32) as usize * 2;
+        if from_end >= self.history.len() {
+            return None;
+        }
+        Some(self.history[self.history.len() - 1 - from_end])
+    }
 }
 impl Display for Graph {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
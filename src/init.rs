@@ -117,7 +117,7 @@ impl Init {
                         (term.get_width() as u32 / 2) - 28
                     ),
                     Fx::trans(perc),
-                    symbol::v_line,
+                    symbol::v_line(),
                 )],
                 false,
                 false,
@@ -3,11 +3,13 @@ use {
         draw::Draw,
         error::{errlog, throw_error},
         event::{Event, EventEnum},
+        keybindings::KeyBindings,
         menu::Menu,
         nonblocking::Nonblocking,
         raw::Raw,
         term::Term,
     },
+    lazy_static::lazy_static,
     nix::sys::{
         select::select,
         time::{TimeVal, TimeValLike},
@@ -21,6 +23,11 @@ use {
     },
 };
 
+lazy_static! {
+    /// Held while reading the terminal, so replies to terminal queries are never read as keys
+    pub static ref TTY_READER: Mutex<()> = Mutex::new(());
+}
+
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub enum KeyUnion {
     String(String),
@@ -36,8 +43,16 @@ pub struct Key {
     pub idle: Event,
     pub mouse_move: Event,
     pub mouse_report: bool,
+    /// Set when a press started dragging a box border, motion is only reported while it is set
+    pub dragging: bool,
+    /// The left button is held down
+    pub pressed: bool,
+    /// Text of a bracketed paste, read with take_paste when a "paste" key is received
+    pub paste: String,
+    pub pasting: bool,
     pub stopping: bool,
     pub started: bool,
+    pub bindings: KeyBindings,
 }
 impl Key {
     pub fn new() -> Self {
@@ -83,6 +98,16 @@ impl Key {
         escape_mut.insert(KeyUnion::String("[21".to_owned()), "f10".to_owned());
         escape_mut.insert(KeyUnion::String("[23".to_owned()), "f11".to_owned());
         escape_mut.insert(KeyUnion::String("[24".to_owned()), "f12".to_owned());
+        escape_mut.insert(KeyUnion::String("[I".to_owned()), "focus_in".to_owned());
+        escape_mut.insert(KeyUnion::String("[O".to_owned()), "focus_out".to_owned());
+        escape_mut.insert(
+            KeyUnion::String("[?997;1n".to_owned()),
+            "scheme_dark".to_owned(),
+        );
+        escape_mut.insert(
+            KeyUnion::String("[?997;2n".to_owned()),
+            "scheme_light".to_owned(),
+        );
 
         Key {
             list: Vec::<String>::new(),
@@ -99,8 +124,13 @@ impl Key {
                 t: EventEnum::Flag(false),
             },
             mouse_report: false,
+            dragging: false,
+            pressed: false,
+            paste: String::default(),
+            pasting: false,
             stopping: false,
             started: false,
+            bindings: KeyBindings::new(&"default".to_owned(), &HashMap::<String, String>::new()),
         }
     }
 
@@ -150,6 +180,13 @@ impl Key {
         self.mouse_pos
     }
 
+    /// Pasted text without control characters, the paste buffer is emptied
+    pub fn take_paste(&mut self) -> String {
+        let text: String = self.paste.chars().filter(|c| !c.is_control()).collect();
+        self.paste = String::default();
+        text
+    }
+
     pub fn mouse_moved(&mut self) -> bool {
         if self.mouse_move.is_set() {
             self.mouse_move.replace_self(EventEnum::Flag(false));
@@ -220,6 +257,9 @@ impl Key {
             };
             errlog("Locked all modules in Key::get_key()".to_owned());
 
+            // * Wait out a running Term::query_colors instead of taking its replies
+            let _reading = TTY_READER.lock().unwrap();
+
             let mut raw = Raw::new();
             raw.enter();
 
@@ -253,9 +293,25 @@ impl Key {
                                     self_key.idle.replace_self(EventEnum::Flag(true));
                                 }
 
-                                if input_key == String::from("\x1b") {
+                                if self_key.pasting || input_key.starts_with("\x1b[200~") {
+                                    // * A bracketed paste is one "paste" key, so pasted text never runs actions
+                                    let text: String =
+                                        input_key.trim_start_matches("\x1b[200~").to_owned();
+                                    match text.find("\x1b[201~") {
+                                        Some(end) => {
+                                            self_key.paste.push_str(&text[..end]);
+                                            self_key.pasting = false;
+                                            clean_key = "paste".to_owned();
+                                        }
+                                        None => {
+                                            self_key.paste.push_str(text.as_str());
+                                            self_key.pasting = true;
+                                        }
+                                    }
+                                } else if input_key == String::from("\x1b") {
                                     clean_key = String::from("escape");
                                 } else if input_key.starts_with("\x1b[<0;")
+                                    || input_key.starts_with("\x1b[<32;")
                                     || input_key.starts_with("\x1b[<35;")
                                     || input_key.starts_with("\x1b[<64;")
                                     || input_key.starts_with("\x1b[<65;")
@@ -266,8 +322,7 @@ impl Key {
                                         input_vec[1].parse::<i32>().unwrap(),
                                         input_vec[2]
                                             .to_owned()
-                                            .trim_end_matches("mM")
-                                            .trim_start_matches("mM")
+                                            .trim_end_matches(|c| c == 'm' || c == 'M')
                                             .parse::<i32>()
                                             .unwrap(),
                                     );
@@ -279,10 +334,27 @@ impl Key {
                                         clean_key = "mouse_scroll_up".to_owned();
                                     } else if input_key.starts_with("\x1b[<65;") {
                                         clean_key = "mouse_scroll_down".to_owned();
+                                    } else if input_key.starts_with("\x1b[<32;") {
+                                        // * Motion with the left button held drags a box border, once
+                                        // * the press has started a drag
+                                        if !menu.active && self_key.dragging {
+                                            clean_key = "mouse_drag".to_owned();
+                                        }
+                                    } else if input_key.starts_with("\x1b[<0;")
+                                        && input_key.ends_with("M")
+                                    {
+                                        self_key.pressed = true;
+                                        if !menu.active {
+                                            clean_key = "mouse_down".to_owned();
+                                        }
                                     } else if input_key.starts_with("\x1b[<0;")
                                         && input_key.ends_with("m")
                                     {
-                                        if menu.active {
+                                        self_key.pressed = false;
+                                        if self_key.dragging {
+                                            self_key.dragging = false;
+                                            clean_key = "mouse_release".to_owned();
+                                        } else if menu.active {
                                             clean_key = "mouse_click".to_owned();
                                         } else {
                                             let mut broke: bool = false;
@@ -0,0 +1,356 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// How long a started key sequence waits for its next key
+const SEQUENCE_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// Every bindable action as (action, default keys, help text), in help menu order.
+/// Alternative keys are separated by "|", keys pressed in sequence by a space and "space" is the spacebar
+pub const ACTIONS: [(&str, &str, &str); 38] = [
+    ("menu", "escape|M", "Toggles main menu."),
+    ("options", "f2|o", "Shows options."),
+    ("help", "f1|h", "Shows this window."),
+    ("quit", "q", "Quits program."),
+    (
+        "view_mode",
+        "m",
+        "Change current view mode, order full->proc->stat.",
+    ),
+    (
+        "layout",
+        "l",
+        "Cycle the layouts from the config, then view mode.",
+    ),
+    ("zoom", "Z", "Zoom focused box to full screen or zoom out."),
+    ("focus_next", "tab", "Move focus to the next shown box."),
+    (
+        "focus_prev",
+        "shift_tab",
+        "Move focus to the previous shown box.",
+    ),
+    ("update_add", "+", "Add 100ms to update timer."),
+    ("update_sub", "-", "Subtract 100ms from update timer."),
+    (
+        "select_up",
+        "up",
+        "Select core, disk or nic in the focused box.",
+    ),
+    (
+        "select_down",
+        "down",
+        "Select core, disk or nic in the focused box.",
+    ),
+    ("page_up", "page_up", "Jump 1 page up in process list."),
+    (
+        "page_down",
+        "page_down",
+        "Jump 1 page down in process list.",
+    ),
+    ("home", "home", "Jump to first page in process list."),
+    ("end", "end", "Jump to last page in process list."),
+    ("sort_prev", "left", "Select previous sorting column."),
+    ("sort_next", "right", "Select next sorting column."),
+    (
+        "details",
+        "enter",
+        "Show detailed information for selected process.",
+    ),
+    (
+        "collapse",
+        "space",
+        "Expand/collapse the selected process in tree view.",
+    ),
+    ("net_prev", "b", "Select previous network device."),
+    ("net_next", "n", "Select next network device."),
+    (
+        "net_zero",
+        "z",
+        "Toggle totals reset for current network device",
+    ),
+    (
+        "net_auto",
+        "a",
+        "Toggle auto scaling for the network graphs.",
+    ),
+    (
+        "net_sync",
+        "y",
+        "Toggle synced scaling mode for network graphs.",
+    ),
+    (
+        "net_packets",
+        "p",
+        "Toggle packets/sec for the network graphs.",
+    ),
+    ("netstat", "x", "Shows network connections and sockets."),
+    ("filter", "f", "Input a string to filter processes with."),
+    ("clear_filter", "delete", "Clear any entered filter."),
+    (
+        "proc_per_core",
+        "c",
+        "Toggle per-core cpu usage of processes.",
+    ),
+    (
+        "proc_reversed",
+        "r",
+        "Reverse sorting order in processes box.",
+    ),
+    ("proc_tree", "e", "Toggle processes tree view."),
+    ("mem_graphs", "g", "Toggle memory graphs."),
+    ("swap_disk", "s", "Toggle swap shown as a disk."),
+    (
+        "terminate",
+        "t|T",
+        "Terminate selected process with SIGTERM - 15.",
+    ),
+    ("kill", "k|K", "Kill selected process with SIGKILL - 9."),
+    (
+        "interrupt",
+        "i|I",
+        "Interrupt selected process with SIGINT - 2.",
+    ),
+];
+
+pub const PRESETS: [&str; 3] = ["default", "vim", "htop"];
+
+/// Bindings a preset changes from the defaults
+fn preset_keys(preset: &str) -> Vec<(&'static str, &'static str)> {
+    match preset {
+        "vim" => vec![
+            ("select_up", "k|up"),
+            ("select_down", "j|down"),
+            ("sort_prev", "h|left"),
+            ("sort_next", "l|right"),
+            ("home", "g g|home"),
+            ("end", "G|end"),
+            ("help", "?|f1"),
+            ("layout", "L"),
+            ("filter", "/|f"),
+            ("quit", "q|: q"),
+            ("kill", "d d|K"),
+            ("mem_graphs", "g m"),
+        ],
+        "htop" => vec![
+            ("help", "f1|h|?"),
+            ("filter", "f3|f4|/|f"),
+            ("proc_tree", "f5|t|e"),
+            ("sort_prev", "<|left"),
+            ("sort_next", "f6|>|right"),
+            ("proc_reversed", "I|r"),
+            ("kill", "f9|k|K"),
+            ("quit", "f10|q"),
+            ("terminate", "T"),
+            ("interrupt", "i"),
+        ],
+        _ => vec![],
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct KeyBindings {
+    preset: String,
+    bindings: Vec<(String, Vec<String>)>,
+    lookup: HashMap<String, String>,
+    conflicts: Vec<String>,
+    pending: Vec<String>,
+    pending_at: Instant,
+}
+impl KeyBindings {
+    /// Build the action table from the defaults, a preset and the [keys] section of the config
+    pub fn new(preset: &String, overrides: &HashMap<String, String>) -> Self {
+        let mut bindings: Vec<(String, Vec<String>)> = Vec::<(String, Vec<String>)>::new();
+        let preset_overrides: Vec<(&str, &str)> = preset_keys(preset.as_str());
+
+        for (action, default, _) in ACTIONS.iter() {
+            let keys: String = match overrides.get(*action) {
+                Some(k) => k.clone(),
+                None => match preset_overrides.iter().find(|(a, _)| a == action) {
+                    Some((_, k)) => k.to_string(),
+                    None => default.to_string(),
+                },
+            };
+            bindings.push((
+                action.to_string(),
+                keys.split('|')
+                    .map(|k| KeyBindings::normalize(k))
+                    .filter(|k| k.len() > 0)
+                    .collect(),
+            ));
+        }
+
+        let mut lookup: HashMap<String, String> = HashMap::<String, String>::new();
+        let mut conflicts: Vec<String> = Vec::<String>::new();
+        for (action, keys) in bindings.iter() {
+            for k in keys.iter() {
+                match lookup.get(k) {
+                    Some(first) => conflicts.push(format!(
+                        "\"{}\" is bound to {} and {}, {} is used.",
+                        k, first, action, first
+                    )),
+                    None => {
+                        lookup.insert(k.clone(), action.clone());
+                    }
+                }
+            }
+        }
+        // * A binding that starts with a whole other binding can never be reached
+        for (sequence, action) in lookup.iter() {
+            for (other, other_action) in lookup.iter() {
+                if sequence.starts_with(&format!("{} ", other)) {
+                    conflicts.push(format!(
+                        "\"{}\" ({}) hides \"{}\" ({}).",
+                        other, other_action, sequence, action
+                    ));
+                }
+            }
+        }
+        conflicts.sort();
+
+        KeyBindings {
+            preset: preset.clone(),
+            bindings,
+            lookup,
+            conflicts,
+            pending: Vec::<String>::new(),
+            pending_at: Instant::now(),
+        }
+    }
+
+    /// Key names as used by Key, with "space" for the spacebar and single spaces between sequence keys
+    fn normalize(keys: &str) -> String {
+        keys.split_whitespace()
+            .map(|k| if k == "space" { " " } else { k })
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    /// Feed a pressed key, returns the bound action, an empty string for an unbound key
+    /// or None while the key could still be the start of a key sequence
+    pub fn resolve(&mut self, key: &String) -> Option<String> {
+        self.resolve_at(key, Instant::now())
+    }
+
+    /// Resolve as if the key was pressed at now, a sequence not continued within SEQUENCE_TIMEOUT is dropped
+    fn resolve_at(&mut self, key: &String, now: Instant) -> Option<String> {
+        if now.duration_since(self.pending_at) > SEQUENCE_TIMEOUT {
+            self.pending.clear();
+        }
+        self.pending_at = now;
+        self.pending.push(key.clone());
+        let sequence: String = self.pending.join(" ");
+
+        if let Some(action) = self.lookup.get(&sequence) {
+            self.pending.clear();
+            return Some(action.clone());
+        }
+        if self
+            .lookup
+            .keys()
+            .any(|k| k.starts_with(&format!("{} ", sequence)))
+        {
+            return None;
+        }
+
+        let retry: bool = self.pending.len() > 1;
+        self.pending.clear();
+        if retry {
+            self.resolve_at(key, now)
+        } else {
+            Some(String::default())
+        }
+    }
+
+    /// The keys bound to an action joined for display, i.e. "f1, h"
+    pub fn label(&self, action: &str) -> String {
+        match self.bindings.iter().find(|(a, _)| a == action) {
+            Some((_, keys)) => keys
+                .iter()
+                .map(|k| {
+                    if k == " " {
+                        "space".to_owned()
+                    } else {
+                        k.clone()
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join(", "),
+            None => String::default(),
+        }
+    }
+
+    /// The key name an action stands for in the box selectors, i.e. "select_up" is "up"
+    pub fn nav_key(action: &String) -> String {
+        match action.as_str() {
+            "select_up" => "up",
+            "select_down" => "down",
+            "sort_prev" => "left",
+            "sort_next" => "right",
+            "details" => "enter",
+            "net_prev" => "b",
+            "net_next" => "n",
+            a => a,
+        }
+        .to_owned()
+    }
+
+    pub fn get_preset(&self) -> String {
+        self.preset.clone()
+    }
+
+    pub fn get_conflicts(&self) -> Vec<String> {
+        self.conflicts.clone()
+    }
+
+    pub fn get_pending(&self) -> Vec<String> {
+        self.pending.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bindings(keys: &[(&str, &str)]) -> KeyBindings {
+        let overrides: HashMap<String, String> = keys
+            .iter()
+            .map(|(a, k)| (a.to_string(), k.to_string()))
+            .collect();
+        KeyBindings::new(&"default".to_owned(), &overrides)
+    }
+
+    #[test]
+    fn resolves_key_sequences() {
+        let mut keys: KeyBindings = bindings(&[("quit", "v q")]);
+        let now: Instant = Instant::now();
+        assert_eq!(keys.resolve_at(&"v".to_owned(), now), None);
+        assert_eq!(
+            keys.resolve_at(&"q".to_owned(), now),
+            Some("quit".to_owned())
+        );
+        // * A key that doesn't continue the sequence is resolved on its own
+        assert_eq!(keys.resolve_at(&"v".to_owned(), now), None);
+        assert_eq!(
+            keys.resolve_at(&"h".to_owned(), now),
+            Some("help".to_owned())
+        );
+        assert_eq!(
+            keys.resolve_at(&"w".to_owned(), now),
+            Some(String::default())
+        );
+    }
+
+    #[test]
+    fn started_sequences_time_out() {
+        let mut keys: KeyBindings = bindings(&[("quit", "v q")]);
+        let now: Instant = Instant::now();
+        assert_eq!(keys.resolve_at(&"v".to_owned(), now), None);
+        let later: Instant = now + SEQUENCE_TIMEOUT + Duration::from_millis(1);
+        assert_eq!(
+            keys.resolve_at(&"q".to_owned(), later),
+            Some(String::default())
+        );
+        assert!(keys.get_pending().is_empty());
+    }
+}
@@ -0,0 +1,561 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+};
+
+/// Boxes that can be placed in a layout, a box left out of a layout is hidden
+pub const LAYOUT_BOXES: [&str; 4] = ["cpu", "mem", "net", "proc"];
+
+/// Box sizes without a layout as (cpu height, mem height, mem and net width) in percent
+pub const DEFAULT_SIZES: (u32, u32, u32) = (32, 38, 45);
+
+/// Parse the "box_sizes" option, "cpu height,mem height,mem and net width" in percent or empty for the defaults
+pub fn parse_sizes(sizes: &String) -> Result<(u32, u32, u32), String> {
+    let sizes: &str = sizes.trim().trim_matches('"');
+    if sizes.len() == 0 {
+        return Ok(DEFAULT_SIZES);
+    }
+    let numbers: Vec<u32> = match sizes
+        .split(',')
+        .map(|n| n.trim().parse::<u32>())
+        .collect::<Result<Vec<u32>, _>>()
+    {
+        Ok(n) if n.len() == 3 => n,
+        _ => {
+            return Err(format!(
+                "{:?} should be three numbers separated by \",\"!",
+                sizes
+            ))
+        }
+    };
+    let (cpu, mem, side) = (numbers[0], numbers[1], numbers[2]);
+    if cpu < 10 || mem < 10 || cpu + mem > 90 || side < 10 || side > 90 {
+        return Err(format!("{:?} should leave every box at least 10%!", sizes));
+    }
+    Ok((cpu, mem, side))
+}
+
+/// A border between two neighbouring children of a split, that can be dragged with the mouse
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutBorder {
+    /// Child indices from the root down to the split
+    pub path: Vec<usize>,
+    /// The border is between child index and index + 1
+    pub index: usize,
+    /// A vertical border is the column pos, otherwise the row pos
+    pub vertical: bool,
+    pub pos: u32,
+    /// Rows of a vertical border or columns of a horizontal one
+    pub from: u32,
+    pub to: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LayoutNode {
+    Box {
+        name: String,
+        weight: u32,
+    },
+    /// horizontal places the children side by side, otherwise they are stacked
+    Split {
+        horizontal: bool,
+        weight: u32,
+        children: Vec<LayoutNode>,
+    },
+}
+impl LayoutNode {
+    pub fn get_weight(&self) -> u32 {
+        match self {
+            LayoutNode::Box { weight, .. } => *weight,
+            LayoutNode::Split { weight, .. } => *weight,
+        }
+    }
+
+    pub fn boxes(&self) -> Vec<String> {
+        match self {
+            LayoutNode::Box { name, .. } => vec![name.clone()],
+            LayoutNode::Split { children, .. } => children.iter().flat_map(|c| c.boxes()).collect(),
+        }
+    }
+
+    /// Split total between the children of a split by their weights
+    fn child_sizes(children: &Vec<LayoutNode>, total: u32) -> Vec<u32> {
+        // * Summed as u64 since user supplied weights can add up past u32::MAX
+        let weights: u64 = children.iter().map(|c| c.get_weight() as u64).sum();
+        let mut sizes: Vec<u32> = Vec::<u32>::new();
+        let mut pos: u32 = 0;
+        for (i, child) in children.iter().enumerate() {
+            // * The last child takes what rounding left over
+            let size: u32 = if i == children.len() - 1 {
+                total - pos
+            } else {
+                (total as u64 * child.get_weight() as u64 / weights.max(1)) as u32
+            };
+            sizes.push(size);
+            pos += size;
+        }
+        sizes
+    }
+
+    fn rects(
+        &self,
+        x: u32,
+        y: u32,
+        w: u32,
+        h: u32,
+        out: &mut HashMap<String, (u32, u32, u32, u32)>,
+    ) {
+        match self {
+            LayoutNode::Box { name, .. } => {
+                out.insert(name.clone(), (x, y, w, h));
+            }
+            LayoutNode::Split {
+                horizontal,
+                children,
+                ..
+            } => {
+                let sizes: Vec<u32> =
+                    LayoutNode::child_sizes(children, if *horizontal { w } else { h });
+                let mut pos: u32 = 0;
+                for (child, size) in children.iter().zip(sizes) {
+                    if *horizontal {
+                        child.rects(x + pos, y, size, h, out);
+                    } else {
+                        child.rects(x, y + pos, w, size, out);
+                    }
+                    pos += size;
+                }
+            }
+        }
+    }
+
+    fn borders(
+        &self,
+        x: u32,
+        y: u32,
+        w: u32,
+        h: u32,
+        path: &mut Vec<usize>,
+        out: &mut Vec<LayoutBorder>,
+    ) {
+        if let LayoutNode::Split {
+            horizontal,
+            children,
+            ..
+        } = self
+        {
+            let sizes: Vec<u32> =
+                LayoutNode::child_sizes(children, if *horizontal { w } else { h });
+            let mut pos: u32 = 0;
+            for (i, (child, size)) in children.iter().zip(sizes).enumerate() {
+                path.push(i);
+                if *horizontal {
+                    child.borders(x + pos, y, size, h, path, out);
+                } else {
+                    child.borders(x, y + pos, w, size, path, out);
+                }
+                path.pop();
+                pos += size;
+                if i < children.len() - 1 {
+                    out.push(LayoutBorder {
+                        path: path.clone(),
+                        index: i,
+                        vertical: *horizontal,
+                        pos: if *horizontal { x + pos } else { y + pos },
+                        from: if *horizontal { y } else { x },
+                        to: if *horizontal { y + h - 1 } else { x + w - 1 },
+                    });
+                }
+            }
+        }
+    }
+
+    /// Move a border of the split at path to pos, the weights of the split become the sizes of its children
+    fn drag(&mut self, path: &[usize], index: usize, pos: u32, x: u32, y: u32, w: u32, h: u32) {
+        if let LayoutNode::Split {
+            horizontal,
+            children,
+            ..
+        } = self
+        {
+            let mut sizes: Vec<u32> =
+                LayoutNode::child_sizes(children, if *horizontal { w } else { h });
+            match path.split_first() {
+                Some((first, rest)) => {
+                    let offset: u32 = sizes[..*first].iter().sum();
+                    if *horizontal {
+                        children[*first].drag(rest, index, pos, x + offset, y, sizes[*first], h);
+                    } else {
+                        children[*first].drag(rest, index, pos, x, y + offset, w, sizes[*first]);
+                    }
+                }
+                None => {
+                    let start: u32 =
+                        sizes[..index].iter().sum::<u32>() + if *horizontal { x } else { y };
+                    let pair: u32 = sizes[index] + sizes[index + 1];
+                    // * Every box keeps room for its border and a line of content
+                    if pair < 6 {
+                        return;
+                    }
+                    let first: u32 = (pos.max(start) - start).max(3).min(pair - 3);
+                    sizes[index] = first;
+                    sizes[index + 1] = pair - first;
+                    for (child, size) in children.iter_mut().zip(sizes) {
+                        match child {
+                            LayoutNode::Box { weight, .. } => *weight = size.max(1),
+                            LayoutNode::Split { weight, .. } => *weight = size.max(1),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+impl Display for LayoutNode {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let weight: u32 = self.get_weight();
+        match self {
+            LayoutNode::Box { name, .. } => write!(f, "{}", name)?,
+            LayoutNode::Split {
+                horizontal,
+                children,
+                ..
+            } => write!(
+                f,
+                "{}[{}]",
+                if *horizontal { "h" } else { "v" },
+                children
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<String>>()
+                    .join(",")
+            )?,
+        }
+        if weight != 1 {
+            write!(f, ":{}", weight)?;
+        }
+        Ok(())
+    }
+}
+
+/// A named box layout parsed from the "layouts" config option
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layout {
+    pub name: String,
+    pub root: LayoutNode,
+}
+impl Layout {
+    /// Parse "name=tree;name=tree", where a tree is a box name or h[...] for a side by side split and v[...] for a stacked split,
+    /// any node can be followed by :N to give it a relative size (default 1)
+    pub fn parse_all(layouts: &String) -> Result<Vec<Layout>, String> {
+        let mut out: Vec<Layout> = Vec::<Layout>::new();
+        for definition in layouts.trim().trim_matches('"').split(';') {
+            if definition.trim().len() == 0 {
+                continue;
+            }
+            let (name, tree) = match definition.find('=') {
+                Some(i) => (definition[..i].trim(), &definition[i + 1..]),
+                None => {
+                    return Err(format!(
+                        "Layout {:?} is missing a \"name=\"!",
+                        definition.trim()
+                    ))
+                }
+            };
+            if name.len() == 0
+                || !name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+            {
+                return Err(format!("Layout name {:?} is not a valid name!", name));
+            }
+            if out.iter().any(|l| l.name == name) {
+                return Err(format!("Layout {:?} is defined more than once!", name));
+            }
+
+            let chars: Vec<char> = tree.chars().filter(|c| !c.is_whitespace()).collect();
+            let mut pos: usize = 0;
+            let root = Layout::parse_node(&chars, &mut pos)
+                .map_err(|e| format!("Layout {:?}: {}", name, e))?;
+            if pos != chars.len() {
+                return Err(format!(
+                    "Layout {:?}: unexpected {:?} at position {}!",
+                    name, chars[pos], pos
+                ));
+            }
+
+            let boxes = root.boxes();
+            for b in boxes.iter() {
+                if boxes.iter().filter(|o| *o == b).count() > 1 {
+                    return Err(format!(
+                        "Layout {:?}: box {:?} is placed more than once!",
+                        name, b
+                    ));
+                }
+            }
+
+            out.push(Layout {
+                name: name.to_owned(),
+                root,
+            });
+        }
+        Ok(out)
+    }
+
+    fn parse_node(chars: &Vec<char>, pos: &mut usize) -> Result<LayoutNode, String> {
+        let mut word: String = String::default();
+        while *pos < chars.len() && chars[*pos].is_alphanumeric() {
+            word.push(chars[*pos]);
+            *pos += 1;
+        }
+
+        let mut node: LayoutNode = if *pos < chars.len()
+            && chars[*pos] == '['
+            && (word == "h" || word == "v")
+        {
+            *pos += 1;
+            let mut children: Vec<LayoutNode> = Vec::<LayoutNode>::new();
+            loop {
+                children.push(Layout::parse_node(chars, pos)?);
+                match chars.get(*pos) {
+                    Some(',') => *pos += 1,
+                    Some(']') => {
+                        *pos += 1;
+                        break;
+                    }
+                    Some(c) => return Err(format!("expected \",\" or \"]\" but found {:?}!", c)),
+                    None => return Err("missing a closing \"]\"!".to_owned()),
+                }
+            }
+            LayoutNode::Split {
+                horizontal: word == "h",
+                weight: 1,
+                children,
+            }
+        } else if LAYOUT_BOXES.contains(&word.as_str()) {
+            LayoutNode::Box {
+                name: word,
+                weight: 1,
+            }
+        } else if word.len() == 0 {
+            return Err(format!("expected a box or split at position {}!", pos));
+        } else {
+            return Err(format!(
+                "unknown box {:?}, valid boxes are {}!",
+                word,
+                LAYOUT_BOXES.join(", ")
+            ));
+        };
+
+        if chars.get(*pos) == Some(&':') {
+            *pos += 1;
+            let mut number: String = String::default();
+            while *pos < chars.len() && chars[*pos].is_ascii_digit() {
+                number.push(chars[*pos]);
+                *pos += 1;
+            }
+            let weight: u32 = match number.parse::<u32>() {
+                Ok(n) if n > 0 => n,
+                _ => return Err(format!("size {:?} should be a number above 0!", number)),
+            };
+            match node {
+                LayoutNode::Box {
+                    weight: ref mut w, ..
+                } => *w = weight,
+                LayoutNode::Split {
+                    weight: ref mut w, ..
+                } => *w = weight,
+            }
+        }
+
+        Ok(node)
+    }
+
+    pub fn names(layouts: &String) -> Vec<String> {
+        match Layout::parse_all(layouts) {
+            Ok(l) => l.iter().map(|l| l.name.clone()).collect(),
+            Err(_) => Vec::<String>::new(),
+        }
+    }
+
+    pub fn find(layouts: &String, name: &String) -> Option<Layout> {
+        if name.len() == 0 {
+            return None;
+        }
+        match Layout::parse_all(layouts) {
+            Ok(l) => l.into_iter().find(|l| l.name == *name),
+            Err(_) => None,
+        }
+    }
+
+    pub fn contains(&self, box_name: &str) -> bool {
+        self.root.boxes().contains(&box_name.to_owned())
+    }
+
+    /// Split the area at x, y of size w * h and return the (x, y, width, height) of every shown box
+    pub fn rects(&self, x: u32, y: u32, w: u32, h: u32) -> HashMap<String, (u32, u32, u32, u32)> {
+        let mut out: HashMap<String, (u32, u32, u32, u32)> =
+            HashMap::<String, (u32, u32, u32, u32)>::new();
+        self.root.rects(x, y, w, h, &mut out);
+        out
+    }
+
+    /// Every border between neighbouring boxes or splits when drawn at x, y of size w * h
+    pub fn borders(&self, x: u32, y: u32, w: u32, h: u32) -> Vec<LayoutBorder> {
+        let mut out: Vec<LayoutBorder> = Vec::<LayoutBorder>::new();
+        self.root
+            .borders(x, y, w, h, &mut Vec::<usize>::new(), &mut out);
+        out
+    }
+
+    /// Move a border returned by borders to the row or column pos
+    pub fn drag(&mut self, border: &LayoutBorder, pos: u32, x: u32, y: u32, w: u32, h: u32) {
+        self.root
+            .drag(&border.path[..], border.index, pos, x, y, w, h);
+    }
+
+    /// The "layouts" option with the definition of this layout swapped in
+    pub fn replace_in(&self, layouts: &String) -> String {
+        match Layout::parse_all(layouts) {
+            Ok(all) => all
+                .iter()
+                .map(|l| {
+                    if l.name == self.name {
+                        self.to_string()
+                    } else {
+                        l.to_string()
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join(";"),
+            Err(_) => layouts.clone(),
+        }
+    }
+}
+impl Display for Layout {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}={}", self.name, self.root)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(layouts: &str) -> Result<Vec<Layout>, String> {
+        Layout::parse_all(&layouts.to_owned())
+    }
+
+    fn node(name: &str, weight: u32) -> LayoutNode {
+        LayoutNode::Box {
+            name: name.to_owned(),
+            weight,
+        }
+    }
+
+    #[test]
+    fn parses_nested_splits() {
+        let layouts = parse("main=v[cpu,h[mem,net],proc]").unwrap();
+        assert_eq!(layouts.len(), 1);
+        assert_eq!(
+            layouts[0].root,
+            LayoutNode::Split {
+                horizontal: false,
+                weight: 1,
+                children: vec![
+                    node("cpu", 1),
+                    LayoutNode::Split {
+                        horizontal: true,
+                        weight: 1,
+                        children: vec![node("mem", 1), node("net", 1)],
+                    },
+                    node("proc", 1),
+                ],
+            }
+        );
+        assert_eq!(layouts[0].to_string(), "main=v[cpu,h[mem,net],proc]");
+    }
+
+    #[test]
+    fn parses_several_layouts_and_whitespace() {
+        let layouts = parse("\"a = h[ cpu , proc ]; b=proc;\"").unwrap();
+        assert_eq!(
+            layouts
+                .iter()
+                .map(|l| l.name.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["a", "b"]
+        );
+        assert_eq!(layouts[1].root, node("proc", 1));
+    }
+
+    #[test]
+    fn weights_split_the_space() {
+        let layout = parse("w=v[cpu:1,h[mem:2,net]:3]").unwrap().remove(0);
+        assert_eq!(layout.to_string(), "w=v[cpu,h[mem:2,net]:3]");
+        let rects = layout.rects(1, 1, 90, 40);
+        assert_eq!(rects["cpu"], (1, 1, 90, 10));
+        assert_eq!(rects["mem"], (1, 11, 60, 30));
+        assert_eq!(rects["net"], (61, 11, 30, 30));
+    }
+
+    #[test]
+    fn huge_weights_do_not_overflow() {
+        let layout = parse("big=h[cpu:4294967295,mem:4294967295]")
+            .unwrap()
+            .remove(0);
+        let rects = layout.rects(1, 1, 80, 24);
+        assert_eq!(rects["cpu"], (1, 1, 40, 24));
+        assert_eq!(rects["mem"], (41, 1, 40, 24));
+    }
+
+    #[test]
+    fn boxes_left_out_are_hidden() {
+        let layout = parse("two=h[cpu,proc]").unwrap().remove(0);
+        assert!(layout.contains("cpu"));
+        assert!(!layout.contains("mem"));
+        assert!(!layout.contains("net"));
+        let rects = layout.rects(1, 1, 80, 24);
+        assert_eq!(rects.len(), 2);
+        assert!(!rects.contains_key("mem"));
+    }
+
+    #[test]
+    fn rejects_malformed_layouts() {
+        for bad in [
+            "v[cpu,mem]",
+            "=cpu",
+            "a b=cpu",
+            "a=cpu;a=mem",
+            "a=v[cpu,mem",
+            "a=v[cpu;mem]",
+            "a=v[cpu,,mem]",
+            "a=disk",
+            "a=cpu:0",
+            "a=cpu:x",
+            "a=v[cpu,cpu]",
+            "a=cpu]",
+            "a=x[cpu]",
+        ]
+        .iter()
+        {
+            assert!(parse(bad).is_err(), "{:?} should not parse", bad);
+        }
+        assert_eq!(Layout::names(&"a=v[cpu".to_owned()), Vec::<String>::new());
+    }
+
+    #[test]
+    fn dragging_a_border_sets_the_weights() {
+        let mut layout = parse("d=h[cpu,proc]").unwrap().remove(0);
+        let borders = layout.borders(1, 1, 80, 24);
+        assert_eq!(borders.len(), 1);
+        assert!(borders[0].vertical);
+        assert_eq!(borders[0].pos, 41);
+        layout.drag(&borders[0], 21, 1, 1, 80, 24);
+        assert_eq!(layout.to_string(), "d=h[cpu:20,proc:60]");
+        assert_eq!(
+            layout.replace_in(&"x=mem;d=h[cpu,proc]".to_owned()),
+            "x=mem;d=h[cpu:20,proc:60]"
+        );
+    }
+}
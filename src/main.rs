@@ -15,6 +15,8 @@ mod fx;
 mod graph;
 mod init;
 mod key;
+mod keybindings;
+mod layout;
 mod membox;
 mod memcollector;
 mod menu;
@@ -22,23 +24,29 @@ mod meter;
 mod mv;
 mod netbox;
 mod netcollector;
+mod netstat;
 mod nonblocking;
 mod procbox;
 mod proccollector;
 mod raw;
+mod screen;
 mod subbox;
 mod symbol;
 mod term;
+mod terminfo;
 mod theme;
+mod themeimport;
 mod timeit;
 mod timer;
+mod tomlconf;
 mod updatechecker;
+mod watcher;
 
 use {
     crate::{
         brshtop_box::{Boxes, BrshtopBox},
         collector::{Collector, Collectors},
-        config::{Config, ViewMode, ViewModeEnum},
+        config::{Config, SortingOption, ViewMode, ViewModeEnum},
         cpubox::CpuBox,
         cpucollector::CpuCollector,
         draw::Draw,
@@ -46,6 +54,8 @@ use {
         graph::Graphs,
         init::Init,
         key::Key,
+        keybindings::KeyBindings,
+        layout::Layout,
         membox::MemBox,
         memcollector::MemCollector,
         menu::Menu,
@@ -58,12 +68,14 @@ use {
         timeit::TimeIt,
         timer::Timer,
         updatechecker::UpdateChecker,
+        watcher::Watcher,
     },
     clap::{App, Arg},
     consts::*,
     cpuid,
     error::{errlog, throw_error},
     expanduser::expanduser,
+    gradient::Space,
     lazy_static::lazy_static,
     log::LevelFilter,
     psutil::process::Signal,
@@ -81,8 +93,9 @@ use {
         thread,
         time::{Duration, SystemTime},
     },
+    symbol::SymbolSet,
     terminal_size::{terminal_size, Height, Width},
-    theme::{Color, Theme},
+    theme::{Color, ColorDepth, Theme},
 };
 
 #[macro_use]
@@ -170,6 +183,54 @@ lazy_static! {
     .cloned()
     .map(|(a, b)| (a.to_owned(), b.to_owned()))
     .collect();
+    pub static ref DEFAULT_LIGHT_THEME: HashMap<String, String> = vec![
+        ("main_bg", ""),
+        ("main_fg", "#30"),
+        ("title", "#10"),
+        ("hi_fg", "#c62828"),
+        ("selected_bg", "#f2c4c4"),
+        ("selected_fg", "#10"),
+        ("inactive_fg", "#b0"),
+        ("graph_text", "#80"),
+        ("meter_bg", "#d0"),
+        ("proc_misc", "#128a3c"),
+        ("cpu_box", "#3d7b46"),
+        ("mem_box", "#8a6d0e"),
+        ("net_box", "#423ba5"),
+        ("proc_box", "#923535"),
+        ("div_line", "#c8"),
+        ("temp_start", "#2f6fb0"),
+        ("temp_mid", "#5a3fc0"),
+        ("temp_end", "#d0207e"),
+        ("cpu_start", "#1f9d55"),
+        ("cpu_mid", "#c29b00"),
+        ("cpu_end", "#d42020"),
+        ("free_start", "#cfe6b8"),
+        ("free_mid", "#6aa832"),
+        ("free_end", "#3f7a10"),
+        ("cached_start", "#c2e3f0"),
+        ("cached_mid", "#2a9cc2"),
+        ("cached_end", "#0b6e96"),
+        ("available_start", "#f2e2b8"),
+        ("available_mid", "#d49b1a"),
+        ("available_end", "#a06d00"),
+        ("used_start", "#f0c8c4"),
+        ("used_mid", "#c8404c"),
+        ("used_end", "#9c1026"),
+        ("download_start", "#cdc8ee"),
+        ("download_mid", "#5a4fb8"),
+        ("download_end", "#2d2380"),
+        ("upload_start", "#ecc6ee"),
+        ("upload_mid", "#a04aa4"),
+        ("upload_end", "#6a1a6e"),
+        ("process_start", "#1f9d55"),
+        ("process_mid", "#b08a00"),
+        ("process_end", "#c03030"),
+    ]
+    .iter()
+    .cloned()
+    .map(|(a, b)| (a.to_owned(), b.to_owned()))
+    .collect();
     pub static ref EXECUTE_PATH : PathBuf = match std::env::current_exe() {
         Ok(p) => p.as_path().to_owned(),
         Err(_) => {
@@ -210,24 +271,6 @@ lazy_static! {
 }
 
 pub fn main() {
-    // Setting up error logging
-
-    let error_file = "brshtop.log";
-    let error_dir = CONFIG_DIR.join(PathBuf::from(error_file));
-    let dir = error_dir.to_str().unwrap();
-
-    match simple_logging::log_to_file(dir, LevelFilter::Debug) {
-        Err(e) => throw_error(
-            format!(
-                "ERROR!\nNo permission to write to \"{}\" directory with error {}!",
-                CONFIG_DIR.to_str().unwrap(),
-                e
-            )
-            .as_str(),
-        ),
-        _ => (),
-    };
-
     //Getting system information from env:consts:OS
 
     if SYSTEM.to_string() == "Other".to_owned() {
@@ -279,6 +322,57 @@ pub fn main() {
                 .takes_value(false)
                 .about("Start with loglevel set to DEBUG overriding value set in config"),
         )
+        .arg(
+            Arg::new("Config")
+                .long("config")
+                .takes_value(true)
+                .value_name("path")
+                .about("Config file to use instead of ~/.config/brshtop/brshtop.toml"),
+        )
+        .arg(
+            Arg::new("Profile")
+                .long("profile")
+                .takes_value(true)
+                .value_name("name")
+                .about("Apply the [profile.\"name\"] section of the config"),
+        )
+        .arg(
+            Arg::new("Set")
+                .long("set")
+                .takes_value(true)
+                .multiple_occurrences(true)
+                .value_name("key=value")
+                .about("Override a config option for this run, i.e. --set update_ms=1000 or --set net.download=100M"),
+        )
+        .subcommand(
+            App::new("import-theme")
+                .about("Convert a btop/bashtop theme or a terminal color scheme to a user theme")
+                .arg(Arg::new("source").index(1).required(true).about(
+                    "Theme or color scheme file, \"terminal\" for the colors of this terminal",
+                ))
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .takes_value(true)
+                        .possible_values(&themeimport::IMPORT_FORMATS)
+                        .about("Format of the source file [default: auto]"),
+                )
+                .arg(
+                    Arg::new("name")
+                        .long("name")
+                        .takes_value(true)
+                        .about("Name of the saved theme [default: the file name]"),
+                ),
+        )
+        .subcommand(
+            App::new("check-config")
+                .about("Check a config file and the themes it uses, exits with 1 on errors like invalid values or unreadable themes")
+                .arg(
+                    Arg::new("path")
+                        .index(1)
+                        .about("Config file to check [default: the config file in use]"),
+                ),
+        )
         .get_matches();
 
     let mut ARG_MODE_raw: ViewMode = ViewMode {
@@ -314,6 +408,58 @@ pub fn main() {
 
     // Variables
 
+    let CONFIG_FILE: PathBuf = match matches.value_of("Config") {
+        Some(path) => PathBuf::from(path),
+        None => CONFIG_DIR.join("brshtop.toml"),
+    };
+    let arg_profile: String = matches.value_of("Profile").unwrap_or("").to_owned();
+    let arg_set: Vec<String> = match matches.values_of("Set") {
+        Some(values) => values.map(|v| v.to_owned()).collect(),
+        None => Vec::<String>::new(),
+    };
+
+    if let Some(check_matches) = matches.subcommand_matches("check-config") {
+        // * Without a path the file is found like on startup, including a legacy brshtop.conf
+        let path: PathBuf = match check_matches.value_of("path") {
+            Some(p) => PathBuf::from(p),
+            None => Config::find_file(&CONFIG_FILE).unwrap_or(CONFIG_FILE.clone()),
+        };
+        let config: Config = match Config::check(path.clone(), arg_profile, arg_set) {
+            Ok(c) => c,
+            Err(e) => {
+                println!("ERROR: {} ({})", e, path.display());
+                std::process::exit(1);
+            }
+        };
+        // * Lines that don't parse, invalid values and themes that can't be read are errors,
+        // * unknown keys and theme warnings don't change what brshtop does
+        let mut errors: Vec<String> = config.errors.clone();
+        let mut warnings: Vec<String> = config.warnings.clone();
+        for name in config.theme_names() {
+            match Theme::check(&name) {
+                Ok(theme_warnings) => {
+                    for warning in theme_warnings {
+                        warnings.push(format!("Theme \"{}\": {}", name, warning));
+                    }
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+        for error in errors.iter() {
+            println!("ERROR: {}", error);
+        }
+        for warning in warnings.iter() {
+            println!("WARNING: {}", warning);
+        }
+        println!(
+            "{}: {} errors, {} warnings",
+            path.display(),
+            errors.len(),
+            warnings.len()
+        );
+        std::process::exit(if errors.len() > 0 { 1 } else { 0 });
+    }
+
     if !CONFIG_DIR.exists() {
         match fs::create_dir(CONFIG_DIR.to_path_buf()) {
             Err(_) => throw_error(
@@ -337,7 +483,52 @@ pub fn main() {
         }
     }
 
-    let CONFIG_FILE = CONFIG_DIR.join("brshtop.conf");
+    // Setting up error logging, after check-config so it leaves the config directory alone
+
+    let error_file = "brshtop.log";
+    let error_dir = CONFIG_DIR.join(PathBuf::from(error_file));
+    let dir = error_dir.to_str().unwrap();
+
+    match simple_logging::log_to_file(dir, LevelFilter::Debug) {
+        Err(e) => throw_error(
+            format!(
+                "ERROR!\nNo permission to write to \"{}\" directory with error {}!",
+                CONFIG_DIR.to_str().unwrap(),
+                e
+            )
+            .as_str(),
+        ),
+        _ => (),
+    };
+
+    if let Some(import_matches) = matches.subcommand_matches("import-theme") {
+        let source: String = import_matches.value_of("source").unwrap().to_owned();
+        let format: &str = import_matches.value_of("format").unwrap_or("auto");
+        let name: String = match import_matches.value_of("name") {
+            Some(n) => n.to_owned(),
+            None => themeimport::theme_name(&source),
+        };
+        match themeimport::import(&source, format) {
+            Ok((tdict, warnings)) => {
+                for warning in warnings {
+                    println!("Warning: {}", warning);
+                }
+                match Theme::save(&name, &tdict) {
+                    Ok(path) => {
+                        println!(
+                            "Saved theme to {}, select it as \"+{}\"",
+                            path.display(),
+                            name
+                        );
+                        std::process::exit(0);
+                    }
+                    Err(e) => println!("ERROR: {}", e),
+                }
+            }
+            Err(e) => println!("ERROR: {}", e),
+        }
+        std::process::exit(1);
+    }
 
     let mut MENUS = HashMap::new();
 
@@ -413,7 +604,7 @@ pub fn main() {
             .collect::<Vec<String>>(),
     );
 
-    let CONFIG_raw: Config = match Config::new(CONFIG_FILE.clone()) {
+    let CONFIG_raw: Config = match Config::new(CONFIG_FILE.clone(), arg_profile, arg_set) {
         Ok(c) => c,
         Err(e) => {
             throw_error(e);
@@ -430,6 +621,11 @@ pub fn main() {
             errlog(s);
         }
     }
+    if CONFIG_raw.errors.len() > 0 {
+        for s in CONFIG_raw.errors.clone() {
+            errlog(s);
+        }
+    }
     let CONFIG_parent: Arc<Mutex<Config>> = Arc::new(Mutex::new(CONFIG_raw));
     let CONFIG_mutex: Arc<Mutex<Config>> = Arc::clone(&CONFIG_parent);
     let CONFIG: MutexGuard<Config> = CONFIG_mutex.lock().unwrap();
@@ -453,6 +649,10 @@ pub fn main() {
     let mut b = brshtop::Brshtop::new();
     b._init();
 
+    ColorDepth::set(ColorDepth::from_config(&CONFIG.color_depth));
+    Theme::set_gradient_space(Space::from_config(&CONFIG.gradient_space));
+    SymbolSet::set(SymbolSet::from_config(&CONFIG.graph_symbol));
+
     let THEME_raw: Theme = match Theme::from_file(CONFIG.color_theme.clone()) {
         Ok(r) => match r {
             Ok(t) => t,
@@ -486,7 +686,11 @@ pub fn main() {
     let term_mutex: Arc<Mutex<Term>> = Arc::clone(&term_parent);
     let mut term: MutexGuard<Term> = term_mutex.lock().unwrap();
 
-    let key_raw: Key = Key::new();
+    let mut key_raw: Key = Key::new();
+    key_raw.bindings = KeyBindings::new(&CONFIG.key_preset, &CONFIG.key_bindings);
+    for conflict in key_raw.bindings.get_conflicts() {
+        errlog(format!("Key binding conflict: {}", conflict));
+    }
     let key_parent: Arc<Mutex<Key>> = Arc::new(Mutex::new(key_raw));
     let key_mutex: Arc<Mutex<Key>> = Arc::clone(&key_parent);
     let mut key: MutexGuard<Key> = key_mutex.lock().unwrap();
@@ -495,6 +699,8 @@ pub fn main() {
     let draw_parent: Arc<Mutex<Draw>> = Arc::new(Mutex::new(draw_raw));
     let draw_mutex: Arc<Mutex<Draw>> = Arc::clone(&draw_parent);
     let mut draw: MutexGuard<Draw> = draw_mutex.lock().unwrap();
+    draw.screen
+        .set_sync(term.get_sync_begin(), term.get_sync_end());
 
     let brshtop_box_raw: BrshtopBox = BrshtopBox::new(&CONFIG, ARG_MODE.to_owned());
     let brshtop_box_parent: Arc<Mutex<BrshtopBox>> = Arc::new(Mutex::new(brshtop_box_raw));
@@ -611,6 +817,9 @@ pub fn main() {
             term.get_clear(),
             term.get_hide_cursor(),
             term.get_mouse_on(),
+            term.get_paste_on(),
+            term.get_focus_on(),
+            term.get_scheme_on(),
             Term::title("BRShtop".to_owned()),
         ],
         &mut key,
@@ -671,7 +880,13 @@ pub fn main() {
 
     errlog("Load theme".to_owned());
 
-    THEME.replace_self(match Theme::from_str(CONFIG.color_theme.clone()) {
+    // * With a light theme set, the terminal is asked if its background is light
+    if CONFIG.color_theme_light.len() > 0 {
+        term.set_light(Term::query_light().unwrap_or(false));
+        errlog(format!("Light terminal background: {}", term.get_light()));
+    }
+
+    THEME.replace_self(match Theme::from_str(CONFIG.theme_name(term.get_light())) {
         Ok(t) => {
             init.success(&CONFIG, &mut draw, &term, &mut key);
             t
@@ -883,6 +1098,8 @@ pub fn main() {
                     let mem_box_signal_clone = mem_box_signal.clone();
                     let net_box_signal_clone = net_box_signal.clone();
                     let proc_box_signal_clone = proc_box_signal.clone();
+                    // * Graphs are placed again when the resized boxes are drawn
+                    graphs_signal.lock().unwrap().unplace();
                     let mut term_winch = term_signal_clone.lock().unwrap();
                     let mut collector_winch = collector_signal_clone.lock().unwrap();
                     let mut init_winch = init_signal_clone.lock().unwrap();
@@ -982,6 +1199,18 @@ pub fn main() {
     draw = draw_mutex.lock().unwrap();
     menu = menu_mutex.lock().unwrap();
 
+    // * Changes to the config and theme files are applied while running
+    match Watcher::new(
+        &CONFIG.config_file,
+        vec![THEME_DIR.to_owned(), USER_THEME_DIR.to_owned()],
+    ) {
+        Ok(watcher) => {
+            watcher.start(Arc::clone(&key_parent));
+            errlog("Started watching the config and theme files".to_owned());
+        }
+        Err(e) => errlog(format!("Config and theme files won't be reloaded, {}", e)),
+    }
+
     init.success(&CONFIG, &mut draw, &term, &mut key);
     errlog("Started a separate thread for reading keyboard input successfully".to_owned());
 
@@ -1296,7 +1525,7 @@ pub fn run(
         while timer.not_zero(&CONFIG) {
             if key.input_wait(
                 timer.left(&CONFIG).as_secs_f64(),
-                false,
+                true,
                 &mut draw,
                 &mut term,
             ) {
@@ -1424,7 +1653,7 @@ pub fn create_box(
             format!(
                 "{}{}",
                 mv::to(hpos, wx),
-                symbol::h_line.repeat((ww - 1) as usize)
+                symbol::h_line().repeat((ww - 1) as usize)
             )
             .as_str(),
         );
@@ -1436,13 +1665,13 @@ pub fn create_box(
             format!(
                 "{}{}{}{}",
                 mv::to(hpos, wx),
-                symbol::v_line,
+                symbol::v_line(),
                 if fill {
                     " ".repeat((ww - 2) as usize)
                 } else {
                     mv::right(ww - 2)
                 },
-                symbol::v_line
+                symbol::v_line()
             )
             .as_str(),
         );
@@ -1453,13 +1682,13 @@ pub fn create_box(
         format!(
             "{}{}{}{}{}{}{}{}",
             mv::to(wy, wx),
-            symbol::left_up,
+            symbol::left_up(),
             mv::to(wy, wx + ww - 1),
-            symbol::right_up,
+            symbol::right_up(),
             mv::to(wy + wh - 1, wx),
-            symbol::left_down,
+            symbol::left_down(),
             mv::to(wy + wh - 1, wx + ww - 1),
-            symbol::right_down,
+            symbol::right_down(),
         )
         .as_str(),
     );
@@ -1469,13 +1698,13 @@ pub fn create_box(
         format!(
             "{}{}{}{}{}{}{}{}",
             mv::to(wy, wx + 2),
-            symbol::title_left,
+            symbol::title_left(),
             tc,
             fx::b,
             wt.clone(),
             fx::ub,
             lc,
-            symbol::title_right
+            symbol::title_right()
         )
         .as_str(),
     );
@@ -1486,13 +1715,13 @@ pub fn create_box(
                 format!(
                     "{}{}{}{}{}{}{}{}",
                     mv::to(hlines[1], wx + 2),
-                    symbol::title_left,
+                    symbol::title_left(),
                     tc,
                     fx::b,
                     s,
                     fx::ub,
                     lc,
-                    symbol::title_right,
+                    symbol::title_right(),
                 )
                 .as_str(),
             );
@@ -1571,6 +1800,9 @@ pub fn clean_quit_mutex(
             term.get_normal_screen(),
             term.get_show_cursor(),
             term.get_mouse_off(),
+            term.get_paste_off(),
+            term.get_focus_off(),
+            term.get_scheme_off(),
             term.get_mouse_direct_off(),
             Term::title(String::default()),
         ],
@@ -1630,6 +1862,9 @@ pub fn clean_quit_mutex_guard(
             term.get_normal_screen(),
             term.get_show_cursor(),
             term.get_mouse_off(),
+            term.get_paste_off(),
+            term.get_focus_off(),
+            term.get_scheme_off(),
             term.get_mouse_direct_off(),
             Term::title(String::default()),
         ],
@@ -1688,6 +1923,9 @@ pub fn clean_quit(
             term.get_normal_screen(),
             term.get_show_cursor(),
             term.get_mouse_off(),
+            term.get_paste_off(),
+            term.get_focus_off(),
+            term.get_scheme_off(),
             term.get_mouse_direct_off(),
             Term::title(String::default()),
         ],
@@ -1735,6 +1973,9 @@ pub fn emergency_quit(errcode: Option<i32>, errmsg: Option<String>) {
         term.get_normal_screen(),
         term.get_show_cursor(),
         term.get_mouse_off(),
+        term.get_paste_off(),
+        term.get_focus_off(),
+        term.get_scheme_off(),
         term.get_mouse_direct_off(),
         Term::title(String::default()),
     ]);
@@ -1865,6 +2106,26 @@ pub fn floating_humanizer(
     out
 }
 
+/// Scales up in steps of 1000 and returns a short count with a K, M, G or T suffix, i.e. for inodes or packets
+pub fn count_humanizer(value: u64) -> String {
+    let suffixes: Vec<&str> = vec!["", "K", "M", "G", "T"];
+    let mut working_val: f64 = value as f64;
+    let mut selector: usize = 0;
+
+    while working_val >= 1000.0 && selector < suffixes.len() - 1 {
+        working_val /= 1000.0;
+        selector += 1;
+    }
+
+    if selector == 0 {
+        value.to_string()
+    } else if working_val < 10.0 {
+        format!("{:.1}{}", working_val, suffixes[selector])
+    } else {
+        format!("{:.0}{}", working_val, suffixes[selector])
+    }
+}
+
 pub fn units_to_bytes(value: String) -> u64 {
     if value.len() == 0 {
         return 0;
@@ -1933,58 +2194,134 @@ pub fn units_to_bytes(value: String) -> u64 {
     value_i << (10 * mult)
 }
 
-pub fn process_keys_mutex(
+pub fn process_keys_mutex_guard(
     boxes: Vec<Boxes>,
     collectors: Vec<Collectors>,
-    ARG_MODE_mutex: Arc<Mutex<ViewMode>>,
-    key_class_mutex: Arc<Mutex<Key>>,
-    procbox_mutex: Arc<Mutex<ProcBox>>,
-    collector_mutex: Arc<Mutex<Collector>>,
-    proccollector_mutex: Arc<Mutex<ProcCollector>>,
-    CONFIG_mutex: Arc<Mutex<Config>>,
-    draw_mutex: Arc<Mutex<Draw>>,
-    term_mutex: Arc<Mutex<Term>>,
-    brshtop_box_mutex: Arc<Mutex<BrshtopBox>>,
-    cpu_box_mutex: Arc<Mutex<CpuBox>>,
-    menu_mutex: Arc<Mutex<Menu>>,
-    THEME_mutex: Arc<Mutex<Theme>>,
-    netcollector_mutex: Arc<Mutex<NetCollector>>,
-    init_mutex: Arc<Mutex<Init>>,
-    cpucollector_mutex: Arc<Mutex<CpuCollector>>,
-    netbox_mutex: Arc<Mutex<NetBox>>,
-    update_checker_mutex: Arc<Mutex<UpdateChecker>>,
-    timer_mutex: Arc<Mutex<Timer>>,
-    graphs_mutex: Arc<Mutex<Graphs>>,
-    mem_box_mutex: Arc<Mutex<MemBox>>,
+    ARG_MODE: &mut MutexGuard<ViewMode>,
+    key_class: &mut MutexGuard<Key>,
+    procbox: &mut MutexGuard<ProcBox>,
+    collector: &mut MutexGuard<Collector>,
+    proccollector: &mut MutexGuard<ProcCollector>,
+    CONFIG: &mut MutexGuard<Config>,
+    draw: &mut MutexGuard<Draw>,
+    term: &mut MutexGuard<Term>,
+    brshtop_box: &mut MutexGuard<BrshtopBox>,
+    cpu_box: &mut MutexGuard<CpuBox>,
+    menu: &mut MutexGuard<Menu>,
+    THEME: &mut MutexGuard<Theme>,
+    netcollector: &mut MutexGuard<NetCollector>,
+    init: &mut MutexGuard<Init>,
+    cpucollector: &mut MutexGuard<CpuCollector>,
+    netbox: &mut MutexGuard<NetBox>,
+    update_checker: &mut MutexGuard<UpdateChecker>,
+    timer: &mut MutexGuard<Timer>,
+    graphs: &mut MutexGuard<Graphs>,
+    mem_box: &mut MutexGuard<MemBox>,
 ) {
-    let mut ARG_MODE = ARG_MODE_mutex.lock().unwrap();
-    let mut key_class = key_class_mutex.lock().unwrap();
-    let mut procbox = procbox_mutex.lock().unwrap();
-    let mut collector = collector_mutex.lock().unwrap();
-    let mut proccollector = proccollector_mutex.lock().unwrap();
-    let mut CONFIG = CONFIG_mutex.lock().unwrap();
-    let mut draw = draw_mutex.lock().unwrap();
-    let mut term = term_mutex.lock().unwrap();
-    let mut brshtop_box = brshtop_box_mutex.lock().unwrap();
-    let mut cpu_box = cpu_box_mutex.lock().unwrap();
-    let mut menu = menu_mutex.lock().unwrap();
-    let mut THEME = THEME_mutex.lock().unwrap();
-    let mut netcollector = netcollector_mutex.lock().unwrap();
-    let mut init = init_mutex.lock().unwrap();
-    let mut cpucollector = cpucollector_mutex.lock().unwrap();
-    let mut netbox = netbox_mutex.lock().unwrap();
-    let update_checker = update_checker_mutex.lock().unwrap();
-    let mut timer = timer_mutex.lock().unwrap();
-    let mut graphs = graphs_mutex.lock().unwrap();
-    let mut mem_box = mem_box_mutex.lock().unwrap();
-
     let mut mouse_pos: (i32, i32) = (0, 0);
+    let mut mouse_box: String = String::default();
     let mut filtered: bool = false;
+
+    // * Hovering a graph shows the value and time of the column under the mouse
+    if key_class.mouse_moved() {
+        let (mx, my) = key_class.get_mouse();
+        let mut out: String = String::default();
+        if graphs.hovering {
+            out.push_str(draw.saved_buffer().as_str());
+            graphs.hovering = false;
+        }
+        if let Some((name, value, time)) = graphs.hover(mx, my) {
+            let shown: String = if !name.starts_with("net ") {
+                format!("{}%", value)
+            } else if CONFIG.net_packets {
+                format!("{} p/s", value)
+            } else {
+                floating_humanizer(value as f64, false, true, 0, false)
+            };
+            let tip: String = format!(
+                " {}: {}{} ",
+                name,
+                shown,
+                match time {
+                    Some(t) => format!(" at {}", t.format("%H:%M:%S")),
+                    None => String::default(),
+                }
+            );
+            let tx: i32 = min_max(mx + 1, 1, term.get_width() as i32 - tip.len() as i32);
+            let ty: i32 = if my + 1 > term.get_height() as i32 {
+                my - 1
+            } else {
+                my + 1
+            };
+            out.push_str(
+                format!(
+                    "{}{}{}{}{}",
+                    mv::to(ty as u32, tx as u32),
+                    THEME.colors.selected_bg,
+                    THEME.colors.selected_fg,
+                    tip,
+                    fx::reset
+                )
+                .as_str(),
+            );
+            graphs.hovering = true;
+        }
+        if out.len() > 0 {
+            draw.now(vec![out], key_class);
+        }
+    }
+
     while key_class.has_key() {
         let mut key = match key_class.get() {
             Some(k) => k.clone(),
             None => return,
         };
+        // * Pressing the left button on a border between boxes starts resizing them
+        if key == "mouse_down".to_owned() {
+            let dragging: bool = brshtop_box.start_drag(
+                key_class.get_mouse(),
+                term,
+                CONFIG,
+                vec![
+                    cpu_box.get_parent(),
+                    mem_box.get_parent(),
+                    netbox.get_parent(),
+                    procbox.get_parent(),
+                ],
+            );
+            // * The button can be up again by the time a press is handled
+            key_class.dragging = dragging && key_class.pressed;
+            continue;
+        } else if key == "mouse_drag".to_owned() || key == "mouse_release".to_owned() {
+            if brshtop_box.get_dragging().len() > 0 {
+                brshtop_box.drag(key_class.get_mouse(), term, CONFIG);
+                if key == "mouse_release".to_owned() {
+                    brshtop_box.end_drag(CONFIG);
+                }
+                graphs.unplace();
+                draw.clear(vec![], true);
+                term.refresh(
+                    vec![],
+                    boxes.clone(),
+                    collector,
+                    init,
+                    cpu_box,
+                    draw,
+                    true,
+                    key_class,
+                    menu,
+                    brshtop_box,
+                    timer,
+                    CONFIG,
+                    THEME,
+                    cpucollector,
+                    mem_box,
+                    netbox,
+                    procbox,
+                );
+            }
+            continue;
+        }
         if vec!["mouse_scroll_up", "mouse_scroll_down", "mouse_click"]
             .iter()
             .map(|s| s.to_owned().to_owned())
@@ -1997,10 +2334,26 @@ pub fn process_keys_mutex(
                 && mouse_pos.1 < procbox.get_current_y() as i32 + procbox.get_current_h() as i32 - 1
             {
                 ()
-            } else if key == "mouse_click".to_owned() {
-                key = "mouse_unselect".to_owned()
             } else {
-                key = "_null".to_owned()
+                // * Outside the process list the wheel scrolls the box under the mouse
+                // * and a click on the net box switches to the next interface
+                mouse_box = BrshtopBox::box_at(
+                    mouse_pos,
+                    vec![
+                        cpu_box.get_parent(),
+                        mem_box.get_parent(),
+                        netbox.get_parent(),
+                    ],
+                );
+                if key == "mouse_click".to_owned() && mouse_box == "net".to_owned() {
+                    key = "nic_next".to_owned()
+                } else if key == "mouse_click".to_owned() {
+                    key = "mouse_unselect".to_owned()
+                } else if mouse_box.len() > 0 {
+                    key = key.replace("mouse_scroll_", "box_scroll_")
+                } else {
+                    key = "_null".to_owned()
+                }
             }
         }
         if procbox.get_filtering() {
@@ -2030,9 +2383,12 @@ pub fn process_keys_mutex(
                 procbox.set_filtering(false);
             } else if key.len() == 1 {
                 proccollector.search_filter.push_str(key.as_str());
-            } else if key == "backspace".to_owned() && proccollector.search_filter.len() > 0 {
-                proccollector.search_filter =
-                    proccollector.search_filter[..proccollector.search_filter.len() - 2].to_owned();
+            } else if key == "paste".to_owned() {
+                proccollector
+                    .search_filter
+                    .push_str(key_class.take_paste().as_str());
+            } else if key == "backspace".to_owned() {
+                proccollector.search_filter.pop();
             } else {
                 continue;
             }
@@ -2049,518 +2405,208 @@ pub fn process_keys_mutex(
             continue;
         }
 
-        if key == "_null".to_owned() {
+        // * Pasted text is only used by text inputs, and regaining focus rewrites the whole screen
+        if key == "paste".to_owned() {
+            key_class.take_paste();
             continue;
-        } else if key == "q".to_owned() {
-            clean_quit_mutex_guard(
-                None,
-                None,
-                &mut key_class,
-                &mut collector,
-                &mut draw,
-                &mut term,
-                &mut CONFIG,
+        } else if key == "focus_in".to_owned() {
+            draw.screen.invalidate();
+            continue;
+        } else if key == "focus_out".to_owned() {
+            // * The mouse can leave without a last move being reported, take the tooltip down
+            if graphs.hovering {
+                let saved: String = draw.saved_buffer();
+                draw.now(vec![saved], key_class);
+            }
+            graphs.unplace();
+            continue;
+        } else if key == "config_changed".to_owned()
+            || key == "theme_changed".to_owned()
+            || key == "scheme_dark".to_owned()
+            || key == "scheme_light".to_owned()
+        {
+            reload_changed(
+                &key,
+                boxes.clone(),
+                THEME,
+                draw,
+                term,
+                CONFIG,
+                key_class,
+                timer,
+                netcollector,
+                brshtop_box,
+                collector,
+                init,
+                cpu_box,
+                cpucollector,
+                netbox,
+                proccollector,
+                procbox,
+                mem_box,
+                menu,
             );
-        } else if key == "+" && CONFIG.update_ms + 100 <= 86399900 {
-            CONFIG.update_ms += 100;
-            brshtop_box.draw_update_ms(
-                false,
-                &mut CONFIG,
-                &mut cpu_box,
-                &mut key_class,
-                &mut draw,
-                &menu,
-                &THEME,
-                &term,
+            continue;
+        }
+
+        // * Keys are looked up in the action table, mouse and internal keys resolve to no action
+        let action: String = match key_class.bindings.resolve(&key) {
+            Some(a) => a,
+            None => continue,
+        };
+
+        if key == "_null".to_owned() {
+            continue;
+        } else if action == "quit".to_owned() {
+            clean_quit_mutex_guard(None, None, key_class, collector, draw, term, CONFIG);
+        } else if action == "focus_next".to_owned() || action == "focus_prev".to_owned() {
+            brshtop_box.cycle_focus(
+                action == "focus_next".to_owned(),
+                vec![
+                    cpu_box.get_parent(),
+                    mem_box.get_parent(),
+                    netbox.get_parent(),
+                    procbox.get_parent(),
+                ],
             );
-        } else if key == "-".to_owned() && CONFIG.update_ms - 100 >= 100 {
-            CONFIG.update_ms -= 100;
-            brshtop_box.draw_update_ms(
-                false,
-                &mut CONFIG,
-                &mut cpu_box,
-                &mut key_class,
-                &mut draw,
-                &menu,
-                &THEME,
-                &term,
+            draw.clear(vec![], true);
+            term.refresh(
+                vec![],
+                boxes.clone(),
+                collector,
+                init,
+                cpu_box,
+                draw,
+                true,
+                key_class,
+                menu,
+                brshtop_box,
+                timer,
+                CONFIG,
+                THEME,
+                cpucollector,
+                mem_box,
+                netbox,
+                procbox,
             );
-        } else if vec!["b", "n"]
-            .iter()
-            .map(|s| s.to_owned().to_owned())
-            .collect::<Vec<String>>()
-            .contains(&key)
-        {
-            netcollector.switch(key, &mut collector);
-        } else if vec!["M", "escape"]
+        } else if brshtop_box.get_focused() != "proc".to_owned()
+            && vec![
+                "select_up",
+                "select_down",
+                "sort_prev",
+                "sort_next",
+                "page_up",
+                "page_down",
+                "home",
+                "end",
+                "details",
+            ]
             .iter()
             .map(|s| s.to_owned().to_owned())
             .collect::<Vec<String>>()
-            .contains(&key)
+            .contains(&action)
+            || key.starts_with("box_scroll_")
         {
-            menu.main(
-                &mut draw,
-                &mut term,
-                &update_checker,
-                &mut THEME,
-                &mut key_class,
-                &mut timer,
-                &mut collector,
-                collectors.clone(),
-                &mut CONFIG,
-                &mut ARG_MODE,
-                &mut netcollector,
-                &mut brshtop_box,
-                &mut init,
-                &mut cpu_box,
-                &mut cpucollector,
-                boxes.clone(),
-                &mut netbox,
-                &mut proccollector,
-                &mut mem_box,
-                &mut procbox,
-            );
-        } else if vec!["o", "f2"]
-            .iter()
-            .map(|s| s.to_owned().to_owned())
-            .collect::<Vec<String>>()
-            .contains(&key)
-        {
-            menu.options(
-                &mut ARG_MODE,
-                &mut THEME,
-                &mut draw,
-                &mut term,
-                &mut CONFIG,
-                &mut key_class,
-                &mut timer,
-                &mut netcollector,
-                &mut brshtop_box,
-                boxes.clone(),
-                &mut collector,
-                &mut init,
-                &mut cpu_box,
-                &mut cpucollector,
-                &mut netbox,
-                &mut proccollector,
-                collectors.clone(),
-                &mut procbox,
-                &mut mem_box,
-            );
-        } else if vec!["h", "f1"]
-            .iter()
-            .map(|s| s.to_owned().to_owned())
-            .collect::<Vec<String>>()
-            .contains(&key)
-        {
-            menu.help(
-                &THEME,
-                &mut draw,
-                &term,
-                &mut key_class,
-                &mut collector,
-                collectors.clone(),
-                &CONFIG,
-                &mut timer,
-            );
-        } else if key == "z".to_owned() {
-            let inserter = netcollector.get_reset();
-            netcollector.set_reset(!inserter);
-            collector.collect(
-                vec![Collectors::NetCollector],
-                true,
-                false,
-                false,
-                true,
-                false,
-            );
-        } else if key == "y".to_owned() {
-            let switch = CONFIG.net_sync.clone();
-            CONFIG.net_sync = !switch;
-            collector.collect(
-                vec![Collectors::NetCollector],
-                true,
-                false,
-                false,
-                true,
-                false,
-            );
-        } else if key == "a".to_owned() {
-            let inserter = netcollector.get_auto_min();
-            netcollector.set_auto_min(!inserter);
-            netcollector.set_net_min(
-                vec![("download", -1), ("upload", -1)]
-                    .iter()
-                    .map(|(s, i)| (s.to_owned().to_owned(), i.to_owned()))
-                    .collect::<HashMap<String, i32>>(),
-            );
-            collector.collect(
-                vec![Collectors::NetCollector],
-                true,
-                false,
-                false,
-                true,
-                false,
-            );
-        } else if vec!["left", "right"]
-            .iter()
-            .map(|s| s.to_owned().to_owned())
-            .collect::<Vec<String>>()
-            .contains(&key)
-        {
-            // TODO : Fix this...
-            //proccollector.sorting(key);
-        } else if key == " ".to_owned() && CONFIG.proc_tree && procbox.get_selected() > 0 {
-            if proccollector
-                .collapsed
-                .contains_key(&procbox.get_selected_pid())
-            {
-                let inserter = proccollector
-                    .collapsed
-                    .get(&procbox.get_selected_pid().clone())
-                    .unwrap()
-                    .to_owned();
-                proccollector
-                    .collapsed
-                    .insert(procbox.get_selected_pid().clone(), !inserter);
-            }
-            collector.collect(
-                vec![Collectors::ProcCollector],
-                true,
-                true,
-                false,
-                true,
-                false,
-            );
-        } else if key == "e".to_owned() {
-            let switch = CONFIG.proc_tree;
-            CONFIG.proc_tree = !switch;
-            collector.collect(
-                vec![Collectors::ProcCollector],
-                true,
-                true,
-                false,
-                true,
-                false,
-            );
-        } else if key == "r".to_owned() {
-            let switch = CONFIG.proc_reversed;
-            CONFIG.proc_reversed = !switch;
-            collector.collect(
-                vec![Collectors::ProcCollector],
-                true,
-                true,
-                false,
-                true,
-                false,
-            );
-        } else if key == "c".to_owned() {
-            let switch = CONFIG.proc_per_core;
-            CONFIG.proc_per_core = !switch;
-            collector.collect(
-                vec![Collectors::ProcCollector],
-                true,
-                true,
-                false,
-                true,
-                false,
-            );
-        } else if key == "g".to_owned() {
-            let switch = CONFIG.mem_graphs;
-            CONFIG.mem_graphs = !switch;
-            collector.collect(
-                vec![Collectors::MemCollector],
-                true,
-                true,
-                false,
-                true,
-                false,
-            );
-        } else if key == "s".to_owned() {
-            let switch = CONFIG.swap_disk;
-            CONFIG.swap_disk = !switch;
-            collector.collect(
-                vec![Collectors::MemCollector],
-                true,
-                true,
-                false,
-                true,
-                false,
-            );
-        } else if key == "f".to_owned() {
-            procbox.set_filtering(true);
-            if proccollector.search_filter.len() == 0 {
-                procbox.set_start(0);
+            // * Navigation keys go to the focused box and the wheel to the box under the mouse,
+            // * the proc box keeps its own handling below
+            let (target, key): (String, String) = if key.starts_with("box_scroll_") {
+                (mouse_box.clone(), key.replace("box_scroll_", ""))
+            } else {
+                (brshtop_box.get_focused(), KeyBindings::nav_key(&action))
+            };
+            match target.as_str() {
+                "cpu" => {
+                    cpu_box.select_core(key.clone());
+                    collector.collect(
+                        vec![Collectors::CpuCollector],
+                        true,
+                        false,
+                        false,
+                        true,
+                        true,
+                    );
+                }
+                "mem" => {
+                    mem_box.select_disk(key.clone());
+                    collector.collect(
+                        vec![Collectors::MemCollector],
+                        true,
+                        false,
+                        false,
+                        true,
+                        true,
+                    );
+                }
+                "net" => {
+                    if key == "up".to_owned() || key == "left".to_owned() {
+                        netcollector.switch("b".to_owned(), collector);
+                    } else if key == "down".to_owned() || key == "right".to_owned() {
+                        netcollector.switch("n".to_owned(), collector);
+                    }
+                }
+                _ => (),
             }
-            collector.collect(
-                vec![Collectors::ProcCollector],
-                true,
-                false,
-                false,
-                true,
-                true,
-            );
-        } else if key == "m".to_owned() {
-            if ARG_MODE.t != ViewModeEnum::None {
-                ARG_MODE.replace_self(ViewModeEnum::None);
-            } else if CONFIG
-                .view_modes
-                .iter()
-                .position(|v| *v == CONFIG.view_mode)
-                .unwrap()
-                + 1
-                > CONFIG.view_modes.len() - 1
-            {
-                CONFIG.view_mode = CONFIG.view_modes[0];
+        } else if key == "nic_next".to_owned() {
+            netcollector.switch("n".to_owned(), collector);
+        } else if key.starts_with("core_") || key.starts_with("disk_") {
+            if key.starts_with("core_") {
+                brshtop_box.set_focused("cpu".to_owned());
+                cpu_box.set_selected_core(key["core_".len()..].parse::<usize>().unwrap_or(0));
             } else {
-                CONFIG.view_mode = CONFIG.view_modes[CONFIG
-                    .view_modes
-                    .iter()
-                    .position(|v| *v == CONFIG.view_mode)
-                    .unwrap()
-                    + 1];
+                brshtop_box.set_focused("mem".to_owned());
+                mem_box.set_selected_disk(key["disk_".len()..].to_owned());
             }
-            brshtop_box.set_proc_mode(CONFIG.view_mode.t == ViewModeEnum::Proc);
-            brshtop_box.set_stat_mode(CONFIG.view_mode.t == ViewModeEnum::Stat);
             draw.clear(vec![], true);
             term.refresh(
                 vec![],
                 boxes.clone(),
-                &mut collector,
-                &mut init,
-                &mut cpu_box,
-                &mut draw,
+                collector,
+                init,
+                cpu_box,
+                draw,
                 true,
-                &mut key_class,
-                &mut menu,
-                &mut brshtop_box,
-                &mut timer,
-                &mut CONFIG,
-                &mut THEME,
-                &mut cpucollector,
-                &mut mem_box,
-                &mut netbox,
-                &mut procbox,
+                key_class,
+                menu,
+                brshtop_box,
+                timer,
+                CONFIG,
+                THEME,
+                cpucollector,
+                mem_box,
+                netbox,
+                procbox,
             );
-        } else if vec!["t", "k", "i"]
-            .iter()
-            .map(|s| s.to_owned().to_owned())
-            .collect::<Vec<String>>()
-            .contains(&key.to_ascii_lowercase())
-        {
-            let pid: u32 = if procbox.get_selected() > 0 {
-                procbox.get_selected_pid()
+        } else if key.starts_with("sortcol_") {
+            // * Clicking the sorted column again reverses the order
+            let sorting: SortingOption = SortingOption::from(key["sortcol_".len()..].to_owned());
+            if std::mem::discriminant(&sorting) == std::mem::discriminant(&CONFIG.proc_sorting) {
+                let switch = CONFIG.proc_reversed;
+                CONFIG.proc_reversed = !switch;
+                CONFIG.mark_changed("proc_reversed");
             } else {
-                proccollector.detailed_pid.unwrap()
-            };
-            let lower = key.to_ascii_lowercase();
-            if psutil::process::pid_exists(pid) {
-                let sig = if lower == "t".to_owned() {
-                    Signal::SIGTERM
-                } else if lower == "k".to_owned() {
-                    Signal::SIGKILL
-                } else {
-                    Signal::SIGINT
-                };
-                match psutil::process::Process::new(pid).unwrap().send_signal(sig) {
-                    Ok(_) => (),
-                    Err(_) => errlog(format!(
-                        "Execption when sending signal {} to pid {}",
-                        sig, pid
-                    )),
-                };
+                CONFIG.proc_sorting = sorting;
+                CONFIG.mark_changed("proc_sorting");
             }
-        } else if key == "delete".to_owned() && proccollector.search_filter.len() > 0 {
-            proccollector.search_filter = String::default();
+            procbox.set_redraw(true);
             collector.collect(
                 vec![Collectors::ProcCollector],
                 true,
-                false,
-                true,
                 true,
                 false,
-            );
-        } else if key == "enter".to_owned() {
-            if procbox.get_selected() > 0
-                && proccollector.detailed_pid.unwrap_or(0) != procbox.get_selected_pid()
-                && psutil::process::pid_exists(procbox.get_selected_pid())
-            {
-                proccollector.detailed = true;
-                let inserter = procbox.get_selected();
-                procbox.set_last_selection(inserter);
-                procbox.set_selected(0);
-                proccollector.detailed_pid = Some(procbox.get_selected_pid());
-                procbox.set_parent_resized(true);
-            } else if proccollector.detailed {
-                let inserter = procbox.get_last_selection();
-                procbox.set_selected(inserter);
-                procbox.set_last_selection(0);
-                proccollector.detailed = false;
-                proccollector.detailed_pid = None;
-                procbox.set_parent_resized(true);
-            } else {
-                continue;
-            }
-            proccollector.details = HashMap::<String, ProcCollectorDetails>::new();
-            proccollector.details_cpu = vec![];
-            proccollector.details_mem = vec![];
-            graphs.detailed_cpu.NotImplemented = true;
-            graphs.detailed_mem.NotImplemented = true;
-            collector.collect(
-                vec![Collectors::ProcCollector],
-                true,
-                false,
-                true,
-                true,
-                false,
-            );
-        } else if vec![
-            "up",
-            "down",
-            "mouse_scroll_up",
-            "mouse_scroll_down",
-            "page_up",
-            "page_down",
-            "home",
-            "end",
-            "mouse_click",
-            "mouse_unselect",
-        ]
-        .iter()
-        .map(|s| s.to_owned().to_owned())
-        .collect::<Vec<String>>()
-        .contains(&key)
-        {
-            procbox.selector(
-                key.clone(),
-                mouse_pos,
-                &mut proccollector,
-                &mut key_class,
-                &mut collector,
-                &mut CONFIG,
-            );
-        }
-    }
-}
-
-pub fn process_keys_mutex_guard(
-    boxes: Vec<Boxes>,
-    collectors: Vec<Collectors>,
-    ARG_MODE: &mut MutexGuard<ViewMode>,
-    key_class: &mut MutexGuard<Key>,
-    procbox: &mut MutexGuard<ProcBox>,
-    collector: &mut MutexGuard<Collector>,
-    proccollector: &mut MutexGuard<ProcCollector>,
-    CONFIG: &mut MutexGuard<Config>,
-    draw: &mut MutexGuard<Draw>,
-    term: &mut MutexGuard<Term>,
-    brshtop_box: &mut MutexGuard<BrshtopBox>,
-    cpu_box: &mut MutexGuard<CpuBox>,
-    menu: &mut MutexGuard<Menu>,
-    THEME: &mut MutexGuard<Theme>,
-    netcollector: &mut MutexGuard<NetCollector>,
-    init: &mut MutexGuard<Init>,
-    cpucollector: &mut MutexGuard<CpuCollector>,
-    netbox: &mut MutexGuard<NetBox>,
-    update_checker: &mut MutexGuard<UpdateChecker>,
-    timer: &mut MutexGuard<Timer>,
-    graphs: &mut MutexGuard<Graphs>,
-    mem_box: &mut MutexGuard<MemBox>,
-) {
-    let mut mouse_pos: (i32, i32) = (0, 0);
-    let mut filtered: bool = false;
-    while key_class.has_key() {
-        let mut key = match key_class.get() {
-            Some(k) => k.clone(),
-            None => return,
-        };
-        if vec!["mouse_scroll_up", "mouse_scroll_down", "mouse_click"]
-            .iter()
-            .map(|s| s.to_owned().to_owned())
-            .collect::<Vec<String>>()
-            .contains(&key)
-        {
-            mouse_pos = key_class.get_mouse();
-            if mouse_pos.0 >= procbox.get_parent().get_x() as i32
-                && procbox.get_current_y() as i32 + 1 <= mouse_pos.1
-                && mouse_pos.1 < procbox.get_current_y() as i32 + procbox.get_current_h() as i32 - 1
-            {
-                ()
-            } else if key == "mouse_click".to_owned() {
-                key = "mouse_unselect".to_owned()
-            } else {
-                key = "_null".to_owned()
-            }
-        }
-        if procbox.get_filtering() {
-            if vec!["enter", "mouse_click", "mouse_unselect"]
-                .iter()
-                .map(|s| s.to_owned().to_owned())
-                .collect::<Vec<String>>()
-                .contains(&key)
-            {
-                procbox.set_filtering(false);
-                collector.collect(
-                    vec![Collectors::ProcCollector],
-                    true,
-                    false,
-                    false,
-                    true,
-                    true,
-                );
-                continue;
-            } else if vec!["escape", "delete"]
-                .iter()
-                .map(|s| s.to_owned().to_owned())
-                .collect::<Vec<String>>()
-                .contains(&key)
-            {
-                proccollector.search_filter = String::default();
-                procbox.set_filtering(false);
-            } else if key.len() == 1 {
-                proccollector.search_filter.push_str(key.as_str());
-            } else if key == "backspace".to_owned() && proccollector.search_filter.len() > 0 {
-                proccollector.search_filter =
-                    proccollector.search_filter[..proccollector.search_filter.len() - 2].to_owned();
-            } else {
-                continue;
-            }
-            collector.collect(
-                vec![Collectors::ProcCollector],
-                true,
-                false,
-                true,
                 true,
                 false,
             );
-            if filtered {}
-            filtered = true;
-            continue;
-        }
-
-        if key == "_null".to_owned() {
-            continue;
-        } else if key == "q".to_owned() {
-            clean_quit_mutex_guard(None, None, key_class, collector, draw, term, CONFIG);
-        } else if key == "+" && CONFIG.update_ms + 100 <= 86399900 {
+        } else if action == "update_add".to_owned() && CONFIG.update_ms + 100 <= 86399900 {
             CONFIG.update_ms += 100;
+            CONFIG.mark_changed("update_ms");
             brshtop_box.draw_update_ms(false, CONFIG, cpu_box, key_class, draw, menu, THEME, term);
-        } else if key == "-".to_owned() && CONFIG.update_ms - 100 >= 100 {
+        } else if action == "update_sub".to_owned() && CONFIG.update_ms - 100 >= 100 {
             CONFIG.update_ms -= 100;
+            CONFIG.mark_changed("update_ms");
             brshtop_box.draw_update_ms(false, CONFIG, cpu_box, key_class, draw, menu, THEME, term);
-        } else if vec!["b", "n"]
-            .iter()
-            .map(|s| s.to_owned().to_owned())
-            .collect::<Vec<String>>()
-            .contains(&key)
-        {
-            netcollector.switch(key, collector);
-        } else if vec!["M", "escape"]
-            .iter()
-            .map(|s| s.to_owned().to_owned())
-            .collect::<Vec<String>>()
-            .contains(&key)
-        {
+        } else if action == "net_prev".to_owned() || action == "net_next".to_owned() {
+            netcollector.switch(KeyBindings::nav_key(&action), collector);
+        } else if action == "menu".to_owned() {
             menu.main(
                 draw,
                 term,
@@ -2583,12 +2629,7 @@ pub fn process_keys_mutex_guard(
                 mem_box,
                 procbox,
             );
-        } else if vec!["o", "f2"]
-            .iter()
-            .map(|s| s.to_owned().to_owned())
-            .collect::<Vec<String>>()
-            .contains(&key)
-        {
+        } else if action == "options".to_owned() {
             menu.options(
                 ARG_MODE,
                 THEME,
@@ -2610,12 +2651,7 @@ pub fn process_keys_mutex_guard(
                 procbox,
                 mem_box,
             );
-        } else if vec!["h", "f1"]
-            .iter()
-            .map(|s| s.to_owned().to_owned())
-            .collect::<Vec<String>>()
-            .contains(&key)
-        {
+        } else if action == "help".to_owned() {
             menu.help(
                 THEME,
                 draw,
@@ -2626,7 +2662,7 @@ pub fn process_keys_mutex_guard(
                 CONFIG,
                 timer,
             );
-        } else if key == "z".to_owned() {
+        } else if action == "net_zero".to_owned() {
             let inserter = netcollector.get_reset();
             netcollector.set_reset(!inserter);
             collector.collect(
@@ -2637,9 +2673,23 @@ pub fn process_keys_mutex_guard(
                 true,
                 false,
             );
-        } else if key == "y".to_owned() {
+        } else if action == "net_sync".to_owned() {
             let switch = CONFIG.net_sync.clone();
             CONFIG.net_sync = !switch;
+            CONFIG.mark_changed("net_sync");
+            collector.collect(
+                vec![Collectors::NetCollector],
+                true,
+                false,
+                false,
+                true,
+                false,
+            );
+        } else if action == "net_packets".to_owned() {
+            let switch = CONFIG.net_packets.clone();
+            CONFIG.net_packets = !switch;
+            CONFIG.mark_changed("net_packets");
+            netcollector.reset_stats();
             collector.collect(
                 vec![Collectors::NetCollector],
                 true,
@@ -2648,7 +2698,19 @@ pub fn process_keys_mutex_guard(
                 true,
                 false,
             );
-        } else if key == "a".to_owned() {
+        } else if action == "netstat".to_owned() {
+            menu.netstat(
+                THEME,
+                draw,
+                term,
+                key_class,
+                collector,
+                collectors.clone(),
+                CONFIG,
+                timer,
+                netcollector,
+            );
+        } else if action == "net_auto".to_owned() {
             let inserter = netcollector.get_auto_min();
             netcollector.set_auto_min(!inserter);
             netcollector.set_net_min(
@@ -2665,15 +2727,11 @@ pub fn process_keys_mutex_guard(
                 true,
                 false,
             );
-        } else if vec!["left", "right"]
-            .iter()
-            .map(|s| s.to_owned().to_owned())
-            .collect::<Vec<String>>()
-            .contains(&key)
-        {
+        } else if action == "sort_prev".to_owned() || action == "sort_next".to_owned() {
             // TODO : Fix this...
             //proccollector.sorting(key);
-        } else if key == " ".to_owned() && CONFIG.proc_tree && procbox.get_selected() > 0 {
+        } else if action == "collapse".to_owned() && CONFIG.proc_tree && procbox.get_selected() > 0
+        {
             if proccollector
                 .collapsed
                 .contains_key(&procbox.get_selected_pid())
@@ -2695,9 +2753,10 @@ pub fn process_keys_mutex_guard(
                 true,
                 false,
             );
-        } else if key == "e".to_owned() {
+        } else if action == "proc_tree".to_owned() {
             let switch = CONFIG.proc_tree;
             CONFIG.proc_tree = !switch;
+            CONFIG.mark_changed("proc_tree");
             collector.collect(
                 vec![Collectors::ProcCollector],
                 true,
@@ -2706,9 +2765,10 @@ pub fn process_keys_mutex_guard(
                 true,
                 false,
             );
-        } else if key == "r".to_owned() {
+        } else if action == "proc_reversed".to_owned() {
             let switch = CONFIG.proc_reversed;
             CONFIG.proc_reversed = !switch;
+            CONFIG.mark_changed("proc_reversed");
             collector.collect(
                 vec![Collectors::ProcCollector],
                 true,
@@ -2717,9 +2777,10 @@ pub fn process_keys_mutex_guard(
                 true,
                 false,
             );
-        } else if key == "c".to_owned() {
+        } else if action == "proc_per_core".to_owned() {
             let switch = CONFIG.proc_per_core;
             CONFIG.proc_per_core = !switch;
+            CONFIG.mark_changed("proc_per_core");
             collector.collect(
                 vec![Collectors::ProcCollector],
                 true,
@@ -2728,9 +2789,10 @@ pub fn process_keys_mutex_guard(
                 true,
                 false,
             );
-        } else if key == "g".to_owned() {
+        } else if action == "mem_graphs".to_owned() {
             let switch = CONFIG.mem_graphs;
             CONFIG.mem_graphs = !switch;
+            CONFIG.mark_changed("mem_graphs");
             collector.collect(
                 vec![Collectors::MemCollector],
                 true,
@@ -2739,9 +2801,10 @@ pub fn process_keys_mutex_guard(
                 true,
                 false,
             );
-        } else if key == "s".to_owned() {
+        } else if action == "swap_disk".to_owned() {
             let switch = CONFIG.swap_disk;
             CONFIG.swap_disk = !switch;
+            CONFIG.mark_changed("swap_disk");
             collector.collect(
                 vec![Collectors::MemCollector],
                 true,
@@ -2750,7 +2813,7 @@ pub fn process_keys_mutex_guard(
                 true,
                 false,
             );
-        } else if key == "f".to_owned() {
+        } else if action == "filter".to_owned() {
             procbox.set_filtering(true);
             if proccollector.search_filter.len() == 0 {
                 procbox.set_start(0);
@@ -2763,7 +2826,7 @@ pub fn process_keys_mutex_guard(
                 true,
                 true,
             );
-        } else if key == "m".to_owned() {
+        } else if action == "view_mode".to_owned() {
             if ARG_MODE.t != ViewModeEnum::None {
                 ARG_MODE.replace_self(ViewModeEnum::None);
             } else if CONFIG
@@ -2775,6 +2838,7 @@ pub fn process_keys_mutex_guard(
                 > CONFIG.view_modes.len() - 1
             {
                 CONFIG.view_mode = CONFIG.view_modes[0];
+                CONFIG.mark_changed("view_mode");
             } else {
                 CONFIG.view_mode = CONFIG.view_modes[CONFIG
                     .view_modes
@@ -2782,9 +2846,12 @@ pub fn process_keys_mutex_guard(
                     .position(|v| *v == CONFIG.view_mode)
                     .unwrap()
                     + 1];
+                CONFIG.mark_changed("view_mode");
             }
             brshtop_box.set_proc_mode(CONFIG.view_mode.t == ViewModeEnum::Proc);
             brshtop_box.set_stat_mode(CONFIG.view_mode.t == ViewModeEnum::Stat);
+            CONFIG.layout = String::default();
+            CONFIG.mark_changed("layout");
             draw.clear(vec![], true);
             term.refresh(
                 vec![],
@@ -2805,22 +2872,80 @@ pub fn process_keys_mutex_guard(
                 netbox,
                 procbox,
             );
-        } else if vec!["t", "k", "i"]
+        } else if action == "zoom".to_owned() || key.starts_with("zoom_") {
+            // * Z zooms the focused box or zooms out again, clicking a box title zooms that box
+            let name: String = if key.starts_with("zoom_") {
+                key.trim_start_matches("zoom_").to_owned()
+            } else if brshtop_box.get_zoomed().len() > 0 {
+                brshtop_box.get_zoomed()
+            } else {
+                brshtop_box.get_focused()
+            };
+            brshtop_box.toggle_zoom(name);
+            draw.clear(vec![], true);
+            term.refresh(
+                vec![],
+                boxes.clone(),
+                collector,
+                init,
+                cpu_box,
+                draw,
+                true,
+                key_class,
+                menu,
+                brshtop_box,
+                timer,
+                CONFIG,
+                THEME,
+                cpucollector,
+                mem_box,
+                netbox,
+                procbox,
+            );
+        } else if action == "layout".to_owned() {
+            // * Cycles through the named layouts and back to the view mode
+            let names: Vec<String> = Layout::names(&CONFIG.layouts);
+            CONFIG.layout = match names.iter().position(|n| *n == CONFIG.layout) {
+                Some(i) if i + 1 < names.len() => names[i + 1].clone(),
+                Some(_) => String::default(),
+                None => names.first().cloned().unwrap_or_default(),
+            };
+            CONFIG.mark_changed("layout");
+            draw.clear(vec![], true);
+            term.refresh(
+                vec![],
+                boxes.clone(),
+                collector,
+                init,
+                cpu_box,
+                draw,
+                true,
+                key_class,
+                menu,
+                brshtop_box,
+                timer,
+                CONFIG,
+                THEME,
+                cpucollector,
+                mem_box,
+                netbox,
+                procbox,
+            );
+        } else if vec!["terminate", "kill", "interrupt"]
             .iter()
             .map(|s| s.to_owned().to_owned())
             .collect::<Vec<String>>()
-            .contains(&key.to_ascii_lowercase())
+            .contains(&action)
         {
             let pid: u32 = if procbox.get_selected() > 0 {
                 procbox.get_selected_pid()
             } else {
                 proccollector.detailed_pid.unwrap()
             };
-            let lower = key.to_ascii_lowercase();
             if psutil::process::pid_exists(pid) {
-                let sig = if lower == "t".to_owned() {
+                let sig = if action == "terminate".to_owned() {
                     Signal::SIGTERM
-                } else if lower == "k".to_owned() {
+                } else if action == "kill".to_owned() {
                     Signal::SIGKILL
                 } else {
                     Signal::SIGINT
@@ -2833,7 +2958,7 @@ pub fn process_keys_mutex_guard(
                     )),
                 };
             }
-        } else if key == "delete".to_owned() && proccollector.search_filter.len() > 0 {
+        } else if action == "clear_filter".to_owned() && proccollector.search_filter.len() > 0 {
             proccollector.search_filter = String::default();
             collector.collect(
                 vec![Collectors::ProcCollector],
@@ -2843,7 +2968,7 @@ pub fn process_keys_mutex_guard(
                 true,
                 false,
             );
-        } else if key == "enter".to_owned() {
+        } else if action == "details".to_owned() {
             if procbox.get_selected() > 0
                 && proccollector.detailed_pid.unwrap_or(0) != procbox.get_selected_pid()
                 && psutil::process::pid_exists(procbox.get_selected_pid())
@@ -2878,24 +3003,34 @@ pub fn process_keys_mutex_guard(
                 false,
             );
         } else if vec![
-            "up",
-            "down",
-            "mouse_scroll_up",
-            "mouse_scroll_down",
+            "select_up",
+            "select_down",
             "page_up",
             "page_down",
             "home",
             "end",
-            "mouse_click",
-            "mouse_unselect",
         ]
         .iter()
         .map(|s| s.to_owned().to_owned())
         .collect::<Vec<String>>()
-        .contains(&key)
+        .contains(&action)
+            || vec![
+                "mouse_scroll_up",
+                "mouse_scroll_down",
+                "mouse_click",
+                "mouse_unselect",
+            ]
+            .iter()
+            .map(|s| s.to_owned().to_owned())
+            .collect::<Vec<String>>()
+            .contains(&key)
         {
             procbox.selector(
-                key.clone(),
+                if action.len() > 0 {
+                    KeyBindings::nav_key(&action)
+                } else {
+                    key.clone()
+                },
                 mouse_pos,
                 proccollector,
                 key_class,
@@ -2906,6 +3041,137 @@ pub fn process_keys_mutex_guard(
     }
 }
 
+/// Apply a change the watcher saw, "config_changed" for the config file or "theme_changed" for a theme file,
+/// or a "scheme_dark"/"scheme_light" report from the terminal
+pub fn reload_changed(
+    key: &str,
+    boxes: Vec<Boxes>,
+    THEME: &mut Theme,
+    draw: &mut Draw,
+    term: &mut Term,
+    CONFIG: &mut Config,
+    key_class: &mut Key,
+    timer: &mut Timer,
+    netcollector: &mut NetCollector,
+    brshtop_box: &mut BrshtopBox,
+    collector: &mut Collector,
+    init: &mut Init,
+    cpu_box: &mut CpuBox,
+    cpucollector: &mut CpuCollector,
+    netbox: &mut NetBox,
+    proccollector: &mut ProcCollector,
+    procbox: &mut ProcBox,
+    mem_box: &mut MemBox,
+    menu: &mut Menu,
+) {
+    if key == "config_changed" {
+        // * The config file was changed outside of brshtop, changed options are applied like in the options menu
+        match CONFIG.reload() {
+            Ok(changed) => {
+                let problems: Vec<String> = CONFIG
+                    .errors
+                    .iter()
+                    .chain(CONFIG.warnings.iter())
+                    .cloned()
+                    .collect();
+                for problem in problems.iter() {
+                    errlog(problem.clone());
+                }
+                if let Some(problem) = problems.first() {
+                    brshtop_box.set_message(format!("Config: {}", problem));
+                }
+                for name in changed {
+                    menu.apply_option(
+                        &name,
+                        THEME,
+                        draw,
+                        term,
+                        CONFIG,
+                        key_class,
+                        timer,
+                        netcollector,
+                        brshtop_box,
+                        boxes.clone(),
+                        collector,
+                        init,
+                        cpu_box,
+                        cpucollector,
+                        netbox,
+                        proccollector,
+                        procbox,
+                        mem_box,
+                    );
+                }
+            }
+            Err(e) => {
+                errlog(format!("Config file not reloaded, {}", e));
+                brshtop_box.set_message(format!("Config not reloaded: {}", e));
+            }
+        }
+    } else if key == "theme_changed" {
+        // * A theme file was changed, the current theme is read again and kept if it doesn't load
+        match THEME.reload() {
+            Ok(_) => {
+                draw.clear(vec![], true);
+                term.refresh(
+                    vec![],
+                    boxes.clone(),
+                    collector,
+                    init,
+                    cpu_box,
+                    draw,
+                    true,
+                    key_class,
+                    menu,
+                    brshtop_box,
+                    timer,
+                    CONFIG,
+                    THEME,
+                    cpucollector,
+                    mem_box,
+                    netbox,
+                    procbox,
+                );
+            }
+            Err(e) => {
+                errlog(format!("Theme \"{}\" not reloaded, {}", THEME.current, e));
+                brshtop_box.set_message(format!("Theme not reloaded: {}", e));
+            }
+        }
+    } else if key == "scheme_dark" || key == "scheme_light" {
+        // * The terminal switched between dark and light, the theme follows if a light theme is set
+        let light: bool = key == "scheme_light";
+        if light == term.get_light() {
+            return;
+        }
+        term.set_light(light);
+        let name: String = CONFIG.theme_name(light);
+        if name != THEME.current {
+            THEME.replace_self(Theme::from_str(name).unwrap_or(Theme::default()));
+            draw.clear(vec![], true);
+            term.refresh(
+                vec![],
+                boxes.clone(),
+                collector,
+                init,
+                cpu_box,
+                draw,
+                true,
+                key_class,
+                menu,
+                brshtop_box,
+                timer,
+                CONFIG,
+                THEME,
+                cpucollector,
+                mem_box,
+                netbox,
+                procbox,
+            );
+        }
+    }
+}
+
 pub fn get_cpu_core_mapping() -> Vec<i32> {
     let mut mapping: Vec<i32> = vec![];
     let map_file = Path::new("/proc/cpuinfo");
@@ -2976,6 +3242,9 @@ pub fn now_sleeping(
             term.get_normal_screen(),
             term.get_show_cursor(),
             term.get_mouse_off(),
+            term.get_paste_off(),
+            term.get_focus_off(),
+            term.get_scheme_off(),
             Term::title("".to_owned()),
         ],
         &mut key,
@@ -3040,6 +3309,9 @@ pub fn now_awake(
             term.get_clear(),
             term.get_hide_cursor(),
             term.get_mouse_on(),
+            term.get_paste_on(),
+            term.get_focus_on(),
+            term.get_scheme_on(),
             Term::title("BRShtop".to_owned()),
         ],
         &mut key,
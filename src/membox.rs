@@ -3,7 +3,7 @@ use {
         brshtop_box::{Boxes, BrshtopBox},
         collector::Collector,
         config::{Config, ViewMode},
-        create_box,
+        count_humanizer, create_box,
         draw::Draw,
         fx,
         fx::Fx,
@@ -35,6 +35,8 @@ pub struct MemBox {
     swap_on: bool,
     mem_names: Vec<String>,
     swap_names: Vec<String>,
+    disk_names: Vec<String>,
+    selected_disk: String,
 }
 impl MemBox {
     pub fn new(brshtop_box: &mut BrshtopBox, CONFIG: &Config, ARG_MODE: ViewMode) -> Self {
@@ -58,6 +60,8 @@ impl MemBox {
                 .iter()
                 .map(|s| s.to_owned().to_owned())
                 .collect(),
+            disk_names: Vec::<String>::new(),
+            selected_disk: String::default(),
         };
         brshtop_box.push_buffers(membox.buffer.clone());
 
@@ -81,11 +85,22 @@ impl MemBox {
             width_p = self.get_parent().get_width_p();
             height_p = self.get_parent().get_height_p();
         }
-        self.set_parent_width(term.get_width() as u32 * width_p / 100);
-        self.set_parent_height((term.get_height() as u32 * height_p / 100) + 1);
+        match self.get_parent().get_layout_rect() {
+            Some((x, y, w, h)) => {
+                self.set_parent_x(x);
+                self.set_parent_y(y);
+                self.set_parent_width(w);
+                self.set_parent_height(h);
+            }
+            None => {
+                self.set_parent_x(1);
+                self.set_parent_width(term.get_width() as u32 * width_p / 100);
+                self.set_parent_height((term.get_height() as u32 * height_p / 100) + 1);
+                self.set_parent_y(u32::try_from(b_cpu_h + 1).unwrap_or(0));
+            }
+        }
         let mut set_b_mem_h = b_mem_h.clone();
         set_b_mem_h = self.get_parent().get_height() as i32;
-        self.set_parent_y(u32::try_from(b_cpu_h + 1).unwrap_or(0));
         if CONFIG.show_disks {
             self.set_mem_width(
                 u32::try_from(
@@ -189,7 +204,7 @@ impl MemBox {
     }
 
     pub fn draw_bg(&self, THEME: &Theme, CONFIG: &Config, term: &Term) -> String {
-        if self.get_parent().get_proc_mode() {
+        if self.get_parent().get_proc_mode() || self.get_parent().get_hidden() {
             String::default()
         } else {
             let mut out: String = String::default();
@@ -201,7 +216,11 @@ impl MemBox {
                     0,
                     None,
                     None,
-                    Some(THEME.colors.mem_box),
+                    Some(if self.get_parent().is_focused() {
+                        THEME.colors.hi_fg
+                    } else {
+                        THEME.colors.mem_box
+                    }),
                     None,
                     true,
                     Some(Boxes::MemBox),
@@ -225,7 +244,7 @@ impl MemBox {
                                 self.get_parent().get_y() as u32 + i,
                                 self.get_divider() as u32
                             ),
-                            symbol::v_line
+                            symbol::v_line()
                         )
                         .as_str(),
                     );
@@ -238,21 +257,24 @@ impl MemBox {
                         THEME
                             .colors
                             .mem_box
-                            .call(symbol::title_left.to_owned(), term),
+                            .call(symbol::title_left().to_owned(), term),
                         fx::b,
                         THEME.colors.title.call("disks".to_owned(), term),
                         fx::ub,
                         THEME
                             .colors
                             .mem_box
-                            .call(symbol::title_right.to_owned(), term),
+                            .call(symbol::title_right().to_owned(), term),
                         mv::to(self.get_parent().get_y(), self.get_divider() as u32),
-                        THEME.colors.mem_box.call(symbol::div_up.to_owned(), term),
+                        THEME.colors.mem_box.call(symbol::div_up().to_owned(), term),
                         mv::to(
                             self.get_parent().get_y() as u32 + self.get_parent().get_height() - 1,
                             self.get_divider() as u32
                         ),
-                        THEME.colors.mem_box.call(symbol::div_down.to_owned(), term),
+                        THEME
+                            .colors
+                            .mem_box
+                            .call(symbol::div_down().to_owned(), term),
                         THEME.colors.div_line,
                         adder
                     )
@@ -276,7 +298,7 @@ impl MemBox {
         draw: &mut Draw,
         menu: &Menu,
     ) {
-        if self.get_parent().get_proc_mode() {
+        if self.get_parent().get_proc_mode() || self.get_parent().get_hidden() {
             return;
         }
 
@@ -308,6 +330,7 @@ impl MemBox {
             meters.set_swap(HashMap::<String, MeterUnion>::new());
             meters.set_disks_used(HashMap::<String, Meter>::new());
             meters.set_disks_free(HashMap::<String, Meter>::new());
+            meters.set_disks_inodes(HashMap::<String, Meter>::new());
             if self.get_mem_meter() > 0 {
                 for name in self.get_mem_names() {
                     if CONFIG.mem_graphs {
@@ -427,7 +450,7 @@ impl MemBox {
                                 DiskInfo::U64(u) => u as i32,
                                 DiskInfo::U32(u) => u as i32,
                                 DiskInfo::String(s) => s.parse::<i32>().unwrap_or(0),
-                                DiskInfo::None => 0,
+                                DiskInfo::Bool(_) | DiskInfo::None => 0,
                             },
                             self.get_disk_meter() as u32,
                             "used".to_owned(),
@@ -447,7 +470,7 @@ impl MemBox {
                                     DiskInfo::U64(u) => u as i32,
                                     DiskInfo::U32(u) => u as i32,
                                     DiskInfo::String(s) => s.parse::<i32>().unwrap_or(0),
-                                    DiskInfo::None => 0,
+                                    DiskInfo::Bool(_) | DiskInfo::None => 0,
                                 },
                                 self.get_disk_meter() as u32,
                                 "free".to_owned(),
@@ -457,6 +480,27 @@ impl MemBox {
                             ),
                         );
                     }
+                    if mem.get_disks().len() * 4 <= h as usize + 1 {
+                        meters.set_disks_inodes_index(
+                            name.clone(),
+                            Meter::new(
+                                match mem
+                                    .get_disks_inner_index(name.clone(), "inodes_percent".to_owned())
+                                    .unwrap_or(DiskInfo::U32(0))
+                                {
+                                    DiskInfo::U64(u) => u as i32,
+                                    DiskInfo::U32(u) => u as i32,
+                                    DiskInfo::String(s) => s.parse::<i32>().unwrap_or(0),
+                                    DiskInfo::Bool(_) | DiskInfo::None => 0,
+                                },
+                                self.get_disk_meter() as u32,
+                                "used".to_owned(),
+                                false,
+                                THEME,
+                                term,
+                            ),
+                        );
+                    }
                 }
             }
             if !key.mouse.contains_key(&"g".to_owned()) {
@@ -476,7 +520,7 @@ impl MemBox {
                     THEME
                         .colors
                         .mem_box
-                        .call(symbol::title_left.to_owned(), term),
+                        .call(symbol::title_left().to_owned(), term),
                     if CONFIG.mem_graphs { fx::b } else { "" },
                     THEME.colors.hi_fg.call("g".to_owned(), term),
                     THEME.colors.title.call("wap".to_owned(), term),
@@ -484,7 +528,7 @@ impl MemBox {
                     THEME
                         .colors
                         .mem_box
-                        .call(symbol::title_right.to_owned(), term),
+                        .call(symbol::title_right().to_owned(), term),
                 )
                 .as_str(),
             );
@@ -506,7 +550,7 @@ impl MemBox {
                         THEME
                             .colors
                             .mem_box
-                            .call(symbol::title_left.to_owned(), term),
+                            .call(symbol::title_left().to_owned(), term),
                         if CONFIG.swap_disk { fx::b } else { "" },
                         THEME.colors.hi_fg.call("s".to_owned(), term),
                         THEME.colors.title.call("raph".to_owned(), term),
@@ -514,7 +558,7 @@ impl MemBox {
                         THEME
                             .colors
                             .mem_box
-                            .call(symbol::title_right.to_owned(), term),
+                            .call(symbol::title_right().to_owned(), term),
                     )
                     .as_str(),
                 );
@@ -558,15 +602,15 @@ impl MemBox {
                 THEME
                     .colors
                     .mem_box
-                    .call(symbol::title_right.to_owned(), term),
+                    .call(symbol::title_right().to_owned(), term),
                 THEME.colors.div_line,
-                symbol::h_line.repeat(self.get_mem_width() as usize - 1),
+                symbol::h_line().repeat(self.get_mem_width() as usize - 1),
                 if CONFIG.show_disks {
                     "".to_owned()
                 } else {
                     THEME.colors.mem_box.to_string()
                 },
-                symbol::title_left,
+                symbol::title_left(),
                 mv::left(self.get_mem_width() - 1),
                 THEME.colors.title,
             );
@@ -923,13 +967,14 @@ impl MemBox {
                 "{}{}{}{}{}{}{}",
                 mv::left(2),
                 THEME.colors.div_line,
-                symbol::title_right,
-                symbol::h_line.repeat(self.get_disks_width() as usize),
+                symbol::title_right(),
+                symbol::h_line().repeat(self.get_disks_width() as usize),
                 THEME.colors.mem_box,
-                symbol::title_left,
+                symbol::title_left(),
                 mv::left(u32::try_from(self.get_disks_width() as i32 - 1).unwrap_or(0)),
             );
 
+            let mut disk_names: Vec<String> = Vec::<String>::new();
             for (name, item) in mem.get_disks() {
                 if collector.get_collect_interrupt() {
                     return;
@@ -940,6 +985,14 @@ impl MemBox {
                 if cy > h - 2 {
                     break;
                 }
+                disk_names.push(name.clone());
+                // * Clicking a disk name focuses the mem box with that disk selected
+                key.mouse.insert(
+                    format!("disk_{}", name),
+                    (0..u32::try_from(self.get_disks_width() as i32 - 11).unwrap_or(0))
+                        .map(|i| vec![(x + cx + i) as i32, (y + cy) as i32])
+                        .collect(),
+                );
                 let item_s: String = item[&"total".to_owned()].to_string();
                 let item_len: usize = item_s.len();
                 let insert: String =
@@ -950,7 +1003,11 @@ impl MemBox {
                         "{}{}{}{}{:width$.12}{}{:>9}",
                         mv::to(y + cy, x + cx),
                         gli,
-                        THEME.colors.title,
+                        if name == self.selected_disk {
+                            THEME.colors.hi_fg
+                        } else {
+                            THEME.colors.title
+                        },
                         fx::b,
                         item_s,
                         mv::to(
@@ -987,6 +1044,47 @@ impl MemBox {
                     )
                     .as_str(),
                 );
+
+                // * Mark read-only disks and disks above the space or inode warning thresholds
+                let read_only: bool = match item.get(&"read_only".to_owned()) {
+                    Some(DiskInfo::Bool(b)) => *b,
+                    _ => false,
+                };
+                let warning: bool = match item.get(&"warning".to_owned()) {
+                    Some(DiskInfo::Bool(b)) => *b,
+                    _ => false,
+                };
+                // * The marker goes in the first gap after the name or the io text that fits
+                // * before the total, a row without room for it is left unmarked
+                let io_len: i32 = item[&"io".to_owned()].to_string().chars().count() as i32;
+                let io_start: i32 = (self.get_disks_width() / 2) as i32 - io_len / 2 - 2;
+                let total_start: i32 =
+                    self.get_disks_width() as i32 - 11 + 9 - (insert.chars().count() as i32).min(9);
+                let name_end: i32 = 1 + (item_s.chars().count() as i32).min(12);
+                let marker_x: Option<i32> = if io_len == 0 || name_end + 3 < io_start {
+                    Some(name_end + 1)
+                } else {
+                    Some(io_start + io_len + 1)
+                }
+                .filter(|m| m + 2 < total_start);
+                if let (true, Some(marker_x)) = (warning, marker_x) {
+                    out.push_str(
+                        format!(
+                            "{}{}{}{}{}{}",
+                            mv::save,
+                            mv::to(
+                                y + cy,
+                                u32::try_from(x as i32 + cx as i32 + marker_x).unwrap_or(0)
+                            ),
+                            fx::b,
+                            THEME.gradient[&"used".to_owned()][100],
+                            if read_only { "RO" } else { " !" },
+                            mv::restore,
+                        )
+                        .as_str(),
+                    );
+                    out.push_str(format!("{}{}", fx::ub, THEME.colors.main_fg).as_str());
+                }
                 let inserter_used = item.get(&"used_percent".to_owned()).unwrap().to_string() + "%";
                 out.push_str(
                     if big_disk {
@@ -1045,11 +1143,48 @@ impl MemBox {
                         .as_str(),
                     );
                     cy += 1;
-                    if mem.get_disks().len() as u32 * 4 <= h + 1 {
+                    if mem.get_disks().len() as u32 * 4 <= h + 1
+                        && meters.get_disks_inodes().contains_key(&name)
+                    {
+                        if cy > h - 1 {
+                            break;
+                        }
+                        out.push_str(mv::to(y + cy, x + cx).as_str());
+                        let inserter_inodes: String = match item.get(&"inodes_total".to_owned()) {
+                            Some(DiskInfo::U64(0)) | None => "-".to_owned(),
+                            _ => item[&"inodes_percent".to_owned()].to_string() + "%",
+                        };
+                        out.push_str(
+                            if big_disk {
+                                format!("Inod:{:>4} ", inserter_inodes)
+                            } else {
+                                "I ".to_owned()
+                            }
+                            .as_str(),
+                        );
+                        let inodes_used: String = match item.get(&"inodes_used".to_owned()) {
+                            Some(DiskInfo::U64(u)) => count_humanizer(*u),
+                            _ => String::default(),
+                        };
+                        out.push_str(
+                            format!(
+                                "{}{:>width$}",
+                                meters
+                                    .get_disks_inodes_index(name.clone())
+                                    .unwrap_or(Meter::default()),
+                                inodes_used,
+                                width = if big_disk { 9 } else { 7 }
+                            )
+                            .as_str(),
+                        );
+                        cy += 1;
+                    }
+                    if mem.get_disks().len() as u32 * 5 <= h + 1 {
                         cy += 1;
                     }
                 }
             }
+            self.disk_names = disk_names;
         }
         draw.buffer(
             self.get_buffer(),
@@ -1170,6 +1305,42 @@ impl MemBox {
         self.redraw = redraw.clone()
     }
 
+    pub fn get_selected_disk(&self) -> String {
+        self.selected_disk.clone()
+    }
+
+    pub fn set_selected_disk(&mut self, selected_disk: String) {
+        self.selected_disk = selected_disk.clone()
+    }
+
+    /// Move the disk selection through the disks last drawn when the mem box has focus
+    pub fn select_disk(&mut self, key: String) {
+        if self.disk_names.len() == 0 {
+            return;
+        }
+        let last: usize = self.disk_names.len() - 1;
+        let current: Option<usize> = self
+            .disk_names
+            .iter()
+            .position(|n| *n == self.selected_disk);
+        self.selected_disk = match (key.as_str(), current) {
+            ("up", Some(i)) | ("left", Some(i)) => {
+                self.disk_names[if i == 0 { last } else { i - 1 }].clone()
+            }
+            ("up", None) | ("left", None) | ("end", _) | ("page_down", _) => {
+                self.disk_names[last].clone()
+            }
+            ("down", Some(i)) | ("right", Some(i)) => {
+                self.disk_names[if i == last { 0 } else { i + 1 }].clone()
+            }
+            ("down", None) | ("right", None) | ("home", _) | ("page_up", _) => {
+                self.disk_names[0].clone()
+            }
+            _ => String::default(),
+        };
+        self.redraw = true;
+    }
+
     pub fn get_buffer(&self) -> String {
         self.buffer.clone()
     }
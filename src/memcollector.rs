@@ -18,6 +18,7 @@ use {
     },
     futures::{future, stream::StreamExt},
     heim::disk::{io_counters, IoCounters},
+    nix::sys::statvfs::{statvfs, FsFlags},
     psutil::{
         disk::{DiskUsage, FileSystem},
         memory::{
@@ -40,6 +41,7 @@ pub enum DiskInfo {
     String(String),
     U32(u32),
     U64(u64),
+    Bool(bool),
     None,
 }
 impl fmt::Display for DiskInfo {
@@ -48,6 +50,7 @@ impl fmt::Display for DiskInfo {
             DiskInfo::String(s) => write!(f, "{}", s.to_owned()),
             DiskInfo::U32(u) => write!(f, "{}", u.to_owned()),
             DiskInfo::U64(u) => write!(f, "{}", u.to_owned()),
+            DiskInfo::Bool(b) => write!(f, "{}", b.to_owned()),
             DiskInfo::None => write!(f, ""),
         }
     }
@@ -289,6 +292,8 @@ impl MemCollector {
             Err(e) => future::ready(()),
         });
 
+        let mount_options: HashMap<String, String> = MemCollector::get_mount_options();
+
         match psutil::disk::partitions() {
             Ok(disks) => {
                 for disk in disks {
@@ -338,12 +343,55 @@ impl MemCollector {
                             }
                         };
                     let u_percent: u32 = disk_u.clone().percent().round() as u32;
+
+                    // * Collect inode usage and read-only state, filesystems like btrfs report 0 inodes
+                    let mut inodes_total: u64 = 0;
+                    let mut inodes_used: u64 = 0;
+                    let mut read_only: bool = false;
+                    match statvfs(disk.clone().mountpoint()) {
+                        Ok(v) => {
+                            inodes_total = v.files() as u64;
+                            inodes_used = inodes_total.saturating_sub(v.files_free() as u64);
+                            read_only = v.flags().contains(FsFlags::ST_RDONLY);
+                        }
+                        Err(e) => errlog(format!(
+                            "Unable to get inode usage of disk {:?} (error {:?})",
+                            disk.clone().mountpoint(),
+                            e
+                        )),
+                    };
+                    let i_percent: u32 = if inodes_total > 0 {
+                        (inodes_used * 100 / inodes_total) as u32
+                    } else {
+                        0
+                    };
+                    let mount_options: String = match mount_options
+                        .get(&disk.clone().mountpoint().to_str().unwrap_or("").to_owned())
+                    {
+                        Some(s) => s.clone(),
+                        None => String::default(),
+                    };
+                    if mount_options.split(',').any(|o| o == "ro") {
+                        read_only = true;
+                    }
+                    let warning: bool = read_only
+                        || (CONFIG.disks_warn_used > 0 && u_percent as i64 >= CONFIG.disks_warn_used)
+                        || (CONFIG.disks_warn_inodes > 0
+                            && inodes_total > 0
+                            && i_percent as i64 >= CONFIG.disks_warn_inodes);
+
                     self.set_disks_index(
                         disk.clone().device().to_owned(),
                         vec![
                             ("name", DiskInfo::String(disk_name)),
                             ("used_percent", DiskInfo::U32(u_percent)),
                             ("free_percent", DiskInfo::U32(100 - u_percent)),
+                            ("inodes_total", DiskInfo::U64(inodes_total)),
+                            ("inodes_used", DiskInfo::U64(inodes_used)),
+                            ("inodes_percent", DiskInfo::U32(i_percent)),
+                            ("read_only", DiskInfo::Bool(read_only)),
+                            ("mount_options", DiskInfo::String(mount_options)),
+                            ("warning", DiskInfo::Bool(warning)),
                         ]
                         .iter()
                         .map(|(s, d)| (s.to_owned().to_owned(), d.clone()))
@@ -573,6 +621,56 @@ impl MemCollector {
         }
     }
 
+    /// Returns the mount options of every mountpoint listed in /proc/self/mountinfo
+    pub fn get_mount_options() -> HashMap<String, String> {
+        let mut out: HashMap<String, String> = HashMap::<String, String>::new();
+        let mountinfo: String = match File::open("/proc/self/mountinfo") {
+            Ok(f) => match crate::readfile(f) {
+                Some(s) => s,
+                None => return out,
+            },
+            Err(_) => return out,
+        };
+
+        // * Fields are: id parent major:minor root mountpoint options [optional...] - fstype source super_options
+        for line in mountinfo.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 6 {
+                continue;
+            }
+            out.insert(Self::unescape_octal(fields[4]), fields[5].to_owned());
+        }
+
+        out
+    }
+
+    /// Decodes the `\ooo` octal escapes the kernel uses for whitespace and backslashes in mountinfo paths
+    pub fn unescape_octal(s: &str) -> String {
+        let bytes: &[u8] = s.as_bytes();
+        let mut out: Vec<u8> = Vec::<u8>::with_capacity(bytes.len());
+        let mut i: usize = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'\\'
+                && i + 3 < bytes.len()
+                && bytes[i + 1..i + 4]
+                    .iter()
+                    .all(|b| (b'0'..=b'7').contains(b))
+            {
+                let value: u32 = bytes[i + 1..i + 4]
+                    .iter()
+                    .fold(0, |acc, b| acc * 8 + (b - b'0') as u32);
+                if value <= 0xFF {
+                    out.push(value as u8);
+                    i += 4;
+                    continue;
+                }
+            }
+            out.push(bytes[i]);
+            i += 1;
+        }
+        String::from_utf8_lossy(&out).into_owned()
+    }
+
     /// JUST CALL MemBox.draw_fg()
     pub fn draw(
         &mut self,
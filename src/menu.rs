@@ -16,22 +16,29 @@ use {
         first_letter_to_upper_case, fx,
         init::Init,
         key::Key,
+        keybindings::{KeyBindings, ACTIONS},
+        layout::{parse_sizes, Layout, DEFAULT_SIZES},
         membox::MemBox,
         mv,
         netbox::NetBox,
         netcollector::NetCollector,
+        netstat::{Socket, TCP_STATES},
         procbox::ProcBox,
         proccollector::ProcCollector,
-        symbol,
+        symbol::{self, SymbolSet},
         term::Term,
-        theme::{Color, Colors, Theme},
+        theme::{Color, ColorDepth, Colors, Theme, THEME_KEYS},
+        themeimport,
         timer::Timer,
         updatechecker::UpdateChecker,
-        THEME_DIR, THREADS, VERSION,
+        DEFAULT_THEME, THEME_DIR, THREADS, VERSION,
     },
+    config_schema::{ConfigSchema, Kind, OptionInfo},
+    gradient::Space,
     math::round::ceil,
     std::{
         collections::HashMap,
+        convert::TryFrom,
         iter::FromIterator,
         mem,
         path::Path,
@@ -457,7 +464,7 @@ impl Menu {
                 term.get_fg()
             );
         }
-        let mut help_items: HashMap<String, String> = [
+        let mut help_items: Vec<(String, String)> = [
             ("(Mouse 1)", "Clicks buttons and selects in process list."),
             (
                 "Selected (Mouse 1)",
@@ -467,50 +474,43 @@ impl Menu {
                 "(Mouse scroll)",
                 "Scrolls any scrollable list/text under cursor.",
             ),
-            ("(Esc, shift+m)", "Toggles main menu."),
-            ("(m)", "Change current view mode, order full->proc->stat."),
-            ("(F2, o)", "Shows options."),
-            ("(F1, h)", "Shows this window."),
+            ("Label (Mouse 1)", "Sort by column, click again to reverse."),
+            ("Core, disk (Mouse 1)", "Focus the box and select it."),
+            ("Net box (Mouse 1)", "Select next network device."),
+            ("Graph (Mouse over)", "Show the value and time under it."),
+            ("Border (Mouse drag)", "Resize the boxes next to it."),
+        ]
+        .iter()
+        .map(|(s1, s2)| (s1.to_string(), s2.to_string()))
+        .collect();
+        // * Bindable actions are listed with the keys they are bound to, followed by any conflicts
+        for (action, _, description) in ACTIONS.iter() {
+            let label: String = key_class.bindings.label(action);
+            if label.len() == 0 {
+                continue;
+            }
+            // * Neighbouring actions with the same help text share a line, like Up and Down
+            match help_items.last_mut() {
+                Some((keys, desc)) if desc.as_str() == *description => {
+                    keys.insert_str(keys.len() - 1, format!(", {}", label).as_str())
+                }
+                _ => help_items.push((format!("({})", label), description.to_string())),
+            }
+        }
+        for (i, conflict) in key_class.bindings.get_conflicts().iter().enumerate() {
+            help_items.push((format!("Conflict {}", i + 1), conflict.clone()));
+        }
+        for (keys, desc) in [
             ("(ctrl+z)", "Sleep program and put in background."),
-            ("(ctrl+c, q)", "Quits program."),
-            ("(+) / (-)", "Add/Subtract 100ms to/from update timer."),
-            ("(Up) (Down)", "Select in process list."),
-            ("(Enter)", "Show detailed information for selected process."),
-            (
-                "(Spacebar)",
-                "Expand/collapse the selected process in tree view.",
-            ),
-            ("(Pg Up) (Pg Down)", "Jump 1 page in process list."),
-            (
-                "(Home) (End)",
-                "Jump to first or last page in process list.",
-            ),
-            ("(Left) (Right)", "Select previous/next sorting column."),
-            ("(b) (n)", "Select previous/next network device."),
-            ("(z)", "Toggle totals reset for current network device"),
-            ("(a)", "Toggle auto scaling for the network graphs."),
-            ("(y)", "Toggle synced scaling mode for network graphs."),
-            ("(f)", "Input a string to filter processes with."),
-            ("(c)", "Toggle per-core cpu usage of processes."),
-            ("(r)", "Reverse sorting order in processes box."),
-            ("(e)", "Toggle processes tree view."),
-            ("(delete)", "Clear any entered filter."),
-            (
-                "Selected (T, t)",
-                "Terminate selected process with SIGTERM - 15.",
-            ),
-            ("Selected (K, k)", "Kill selected process with SIGKILL - 9."),
-            (
-                "Selected (I, i)",
-                "Interrupt selected process with SIGINT - 2.",
-            ),
+            ("(ctrl+c)", "Quits program."),
             ("_1", " "),
             ("_2", "For bug reporting and project updates, visit,"),
             ("_3", "https,//github.com/aristocratos/bpytop"),
         ]
         .iter()
-        .map(|(s1, s2)| (s1.clone().to_owned(), s2.clone().to_owned()))
-        .collect();
+        {
+            help_items.push((keys.to_string(), desc.to_string()));
+        }
 
         while !self.close {
             key = String::default();
@@ -587,21 +587,21 @@ impl Menu {
                                 THEME
                                     .colors
                                     .div_line
-                                    .call(symbol::title_left.to_owned(), term),
+                                    .call(symbol::title_left().to_owned(), term),
                                 fx::b,
                                 THEME.colors.title.call("pg".to_owned(), term),
                                 fx::ub,
-                                THEME.colors.main_fg.call(symbol::up.to_owned(), term),
+                                THEME.colors.main_fg.call(symbol::up().to_owned(), term),
                                 fx::b,
                                 THEME.colors.title,
                                 page,
                                 pages,
                                 fx::ub,
-                                THEME.colors.main_fg.call(symbol::down.to_owned(), term),
+                                THEME.colors.main_fg.call(symbol::down().to_owned(), term),
                                 THEME
                                     .colors
                                     .div_line
-                                    .call(symbol::title_right.to_owned(), term),
+                                    .call(symbol::title_right().to_owned(), term),
                             )
                             .as_str(),
                         );
@@ -770,6 +770,397 @@ impl Menu {
         }
     }
 
+    pub fn netstat(
+        &mut self,
+        THEME: &Theme,
+        draw: &mut Draw,
+        term: &Term,
+        key_class: &mut Key,
+        collector: &mut Collector,
+        collectors: Vec<Collectors>,
+        CONFIG: &Config,
+        timer: &mut Timer,
+        netcollector: &mut NetCollector,
+    ) {
+        let mut out: String = String::default();
+        let mut out_misc: String = String::default();
+        let mut redraw: bool = true;
+        let mut key: String = String::default();
+        let mut skip: bool = false;
+        let mut main_active: bool = self.active;
+        let mut offset: usize = 0;
+        let mut lines: Vec<String> = Vec::<String>::new();
+        let mut x: u32 = 0;
+        let mut y: u32 = 0;
+        let mut w: u32 = 0;
+        let mut h: u32 = 0;
+
+        self.active = true;
+        self.resized = true;
+        if self.background == String::default() {
+            self.background = format!(
+                "{}{}{}",
+                THEME.colors.inactive_fg,
+                fx::Fx::uncolor(draw.saved_buffer()),
+                term.get_fg()
+            );
+        }
+        netcollector.collect_sockets(true);
+
+        while !self.close {
+            key = String::default();
+            if self.resized {
+                w = if term.get_width() > 104 {
+                    100
+                } else {
+                    term.get_width() as u32 - 4
+                };
+                h = term.get_height() as u32 - 8;
+                x = (term.get_width() as u32 - w) / 2 + 1;
+                y = 4;
+                out_misc = create_box(
+                    x,
+                    y,
+                    w,
+                    h + 2,
+                    Some("connections".to_owned()),
+                    None,
+                    Some(THEME.colors.div_line),
+                    None,
+                    true,
+                    None,
+                    term,
+                    THEME,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                );
+                self.resized = false;
+                redraw = true;
+            }
+
+            if redraw {
+                let netstat = netcollector.get_netstat();
+                let states: HashMap<String, u64> = netstat.get_tcp_states();
+                let mut sockets: Vec<Socket> = netstat.get_sockets();
+                sockets.sort_by(|a, b| {
+                    (a.proto.starts_with("udp"), a.local.clone())
+                        .cmp(&(b.proto.starts_with("udp"), b.local.clone()))
+                });
+                let name_w: usize =
+                    u32::try_from(w as i32 - 2 - 6 - 13 - 28 - 28 - 8).unwrap_or(0) as usize;
+
+                lines = Vec::<String>::new();
+                let mut state_line: String = format!(
+                    "{}{}Tcp:{}{}",
+                    fx::b,
+                    THEME.colors.title,
+                    fx::ub,
+                    THEME.colors.main_fg
+                );
+                let mut state_len: usize = 4;
+                for (_, state) in TCP_STATES.iter() {
+                    let count: u64 = states.get(&state.to_string()).unwrap_or(&0).to_owned();
+                    if count == 0 {
+                        continue;
+                    }
+                    let item: String = format!(" {} {}", state, count);
+                    if state_len + item.len() > (w - 2) as usize {
+                        lines.push(state_line);
+                        state_line = " ".repeat(4);
+                        state_len = 4;
+                    }
+                    state_len += item.len();
+                    state_line.push_str(item.as_str());
+                }
+                lines.push(state_line);
+                lines.push(String::default());
+                lines.push(format!(
+                    "{}{}{:<6}{:<13}{:<28}{:<28}{:>7} {}{}{}",
+                    fx::b,
+                    THEME.colors.title,
+                    "Proto",
+                    "State",
+                    "Local address",
+                    "Remote address",
+                    "Pid",
+                    "Program",
+                    fx::ub,
+                    THEME.colors.main_fg
+                ));
+                // * The tcp state and header lines stay on top while scrolling the socket list
+                let fixed: usize = lines.len();
+                for socket in sockets {
+                    lines.push(format!(
+                        "{:<6}{:<13}{:<28.27}{:<28.27}{:>7} {:.name_w$}",
+                        socket.proto,
+                        socket.state,
+                        socket.local,
+                        socket.remote,
+                        if socket.pid > 0 {
+                            socket.pid.to_string()
+                        } else {
+                            "-".to_owned()
+                        },
+                        socket.name,
+                        name_w = name_w,
+                    ));
+                }
+
+                let page: usize = if (h as usize) > fixed {
+                    h as usize - fixed
+                } else {
+                    1
+                };
+                let rows: usize = lines.len() - fixed;
+                if offset + page > rows {
+                    offset = if rows > page { rows - page } else { 0 };
+                }
+
+                out = String::default();
+                let mut cy: u32 = 0;
+                for line in lines[..fixed]
+                    .iter()
+                    .chain(lines[fixed + offset..].iter().take(page))
+                {
+                    if cy == h {
+                        break;
+                    }
+                    out.push_str(
+                        format!(
+                            "{}{}{}",
+                            mv::to(y + 1 + cy, x + 1),
+                            " ".repeat((w - 2) as usize),
+                            mv::to(y + 1 + cy, x + 1),
+                        )
+                        .as_str(),
+                    );
+                    out.push_str(format!("{}{}", THEME.colors.main_fg, line).as_str());
+                    cy += 1;
+                }
+                for i in cy..h {
+                    out.push_str(
+                        format!(
+                            "{}{}",
+                            mv::to(y + 1 + i, x + 1),
+                            " ".repeat((w - 2) as usize),
+                        )
+                        .as_str(),
+                    );
+                }
+            }
+
+            if skip && redraw {
+                draw.now(vec![out.clone()], key_class);
+            } else if !skip {
+                draw.now(
+                    vec![format!("{}{}{}", self.background, out_misc, out)],
+                    key_class,
+                );
+            }
+            skip = false;
+            redraw = false;
+
+            if key_class.input_wait(timer.left(CONFIG).as_secs_f64(), false, draw, term) {
+                key = match key_class.get() {
+                    Some(k) => k,
+                    None => break,
+                };
+
+                if key == "mouse_click".to_owned() {
+                    let (mx, my) = key_class.get_mouse();
+                    if !(x <= mx as u32
+                        && mx <= (x + w) as i32
+                        && y <= my as u32
+                        && my <= (y + h + 2) as i32)
+                    {
+                        key = "escape".to_owned();
+                    }
+                }
+
+                if key == "q".to_owned() {
+                    clean_quit(None, None, key_class, collector, draw, term, CONFIG);
+                } else if vec!["escape", "M", "backspace", "x"].contains(&key.as_str()) {
+                    self.close = true;
+                    break;
+                } else if vec!["up", "mouse_scroll_up"].contains(&key.as_str()) {
+                    if offset > 0 {
+                        offset -= 1;
+                        redraw = true;
+                    }
+                } else if vec!["down", "mouse_scroll_down"].contains(&key.as_str()) {
+                    offset += 1;
+                    redraw = true;
+                } else if key == "page_up".to_owned() {
+                    offset = if offset > h as usize {
+                        offset - h as usize
+                    } else {
+                        0
+                    };
+                    redraw = true;
+                } else if key == "page_down".to_owned() {
+                    offset += h as usize;
+                    redraw = true;
+                } else if key == "home".to_owned() {
+                    offset = 0;
+                    redraw = true;
+                } else if key == "end".to_owned() {
+                    offset = lines.len();
+                    redraw = true;
+                }
+            }
+
+            if timer.not_zero(CONFIG) && !self.resized {
+                skip = true;
+            } else {
+                collector.collect(collectors.clone(), true, false, false, false, false);
+                netcollector.collect_sockets(true);
+                if CONFIG.background_update {
+                    self.background = format!(
+                        "{}{}{}",
+                        THEME.colors.inactive_fg,
+                        fx::Fx::uncolor(draw.saved_buffer()),
+                        term.get_fg(),
+                    );
+                }
+                timer.stamp();
+                redraw = true;
+            }
+        }
+
+        if main_active {
+            self.close = false;
+            return;
+        }
+        let saved_buffer = draw.saved_buffer();
+        draw.now(vec![saved_buffer], key_class);
+        self.active = false;
+        self.close = false;
+    }
+
+    /// Update what depends on an option after it changed in the options menu or in the config
+    /// file, the name is "keys" for changed key bindings
+    pub fn apply_option(
+        &mut self,
+        name: &String,
+        THEME: &mut Theme,
+        draw: &mut Draw,
+        term: &mut Term,
+        CONFIG: &mut Config,
+        key_class: &mut Key,
+        timer: &mut Timer,
+        netcollector: &mut NetCollector,
+        brshtop_box: &mut BrshtopBox,
+        boxes: Vec<Boxes>,
+        collector: &mut Collector,
+        init: &mut Init,
+        cpubox: &mut CpuBox,
+        cpucollector: &mut CpuCollector,
+        netbox: &mut NetBox,
+        proc_collector: &mut ProcCollector,
+        procbox: &mut ProcBox,
+        membox: &mut MemBox,
+    ) {
+        if name == "box_sizes" {
+            brshtop_box.set_sizes(parse_sizes(&CONFIG.box_sizes).unwrap_or(DEFAULT_SIZES));
+            draw.clear(vec![], true);
+        } else if name == "layouts" || name == "layout" {
+            brshtop_box.set_layouts(CONFIG.layouts.clone());
+            draw.clear(vec![], true);
+        } else if name == "view_mode" {
+            brshtop_box.set_proc_mode(CONFIG.view_mode.t == ViewModeEnum::Proc);
+            brshtop_box.set_stat_mode(CONFIG.view_mode.t == ViewModeEnum::Stat);
+            draw.clear(vec![], true);
+        } else if name == "color_theme"
+            || name == "color_theme_light"
+            || name == "color_depth"
+            || name == "gradient_space"
+        {
+            // * The background is only asked at start when a light theme was already set
+            if name == "color_theme_light" && CONFIG.color_theme_light.len() > 0 {
+                term.set_light(Term::query_light().unwrap_or(false));
+            }
+            ColorDepth::set(ColorDepth::from_config(&CONFIG.color_depth));
+            Theme::set_gradient_space(Space::from_config(&CONFIG.gradient_space));
+            THEME.replace_self(
+                Theme::from_str(CONFIG.theme_name(term.get_light())).unwrap_or(Theme::default()),
+            );
+            draw.clear(vec![], true);
+        } else if name == "graph_symbol" {
+            SymbolSet::set(SymbolSet::from_config(&CONFIG.graph_symbol));
+            draw.clear(vec![], true);
+        } else if name == "key_preset" || name == "keys" {
+            key_class.bindings = KeyBindings::new(&CONFIG.key_preset, &CONFIG.key_bindings);
+        } else if name == "check_temp" {
+            if CONFIG.check_temp {
+                cpucollector.get_sensors(CONFIG);
+            } else {
+                cpucollector.set_sensor_method(String::default());
+                cpucollector.set_got_sensors(false);
+            }
+        } else if name == "cpu_sensor" {
+            cpucollector.set_sensor_swap(true);
+            if CONFIG.check_temp {
+                cpucollector.get_sensors(CONFIG);
+            }
+        } else if name == "tree_depth" {
+            proc_collector.collapsed = HashMap::new();
+        } else if name == "update_ms" {
+            brshtop_box.draw_update_ms(true, CONFIG, cpubox, key_class, draw, self, THEME, term);
+        } else if name.starts_with("net_") {
+            if name == "net_auto" {
+                netcollector.set_auto_min(CONFIG.net_auto);
+            } else if name == "net_packets" {
+                netcollector.reset_stats();
+            } else if name == "net_download" || name == "net_upload" || name == "net_interfaces" {
+                netcollector.set_net_min(
+                    vec![("download".to_owned(), -1), ("upload".to_owned(), -1)]
+                        .iter()
+                        .map(|(s, i)| (s.clone(), *i as i32))
+                        .collect::<HashMap<String, i32>>(),
+                );
+            }
+            netbox.set_redraw(true);
+        } else if name.starts_with("disks_warn_") {
+            membox.set_redraw(true);
+        } else if name == "draw_clock" {
+            brshtop_box.set_clock_on(CONFIG.draw_clock.len() > 0);
+            if !brshtop_box.get_clock_on() {
+                draw.clear(vec!["clock".to_owned()], true);
+            }
+        } else if name == "theme_background" {
+            term.set_bg(if CONFIG.theme_background {
+                THEME.colors.main_bg
+            } else {
+                Color::from("\x1b[49m".to_owned())
+            });
+            draw.now(vec![term.get_bg().to_string()], key_class);
+        } else if name == "show_battery" {
+            draw.clear(vec!["battery".to_owned()], true);
+        }
+        term.refresh(
+            vec![],
+            boxes,
+            collector,
+            init,
+            cpubox,
+            draw,
+            true,
+            key_class,
+            self,
+            brshtop_box,
+            timer,
+            CONFIG,
+            THEME,
+            cpucollector,
+            membox,
+            netbox,
+            procbox,
+        );
+    }
+
     pub fn options(
         &mut self,
         ARG_MODE: &mut ViewMode,
@@ -814,342 +1205,10 @@ impl Menu {
             );
         }
 
-        let mut option_items: HashMap<String, Vec<String>> = vec![
-            (
-                "color_theme",
-                vec![
-                    "Set color theme.",
-                    "",
-                    "Choose from all theme files in",
-                    "\"/usr/[local/]share/bpytop/themes\" and",
-                    "\"~/.config/bpytop/themes\".",
-                    "",
-                    "\"Default\" for builtin default theme.",
-                    "User themes are prefixed by a plus sign \"+\".",
-                    "",
-                    "For theme updates see:",
-                    "https://github.com/aristocratos/bpytop",
-                ],
-            ),
-            (
-                "theme_background",
-                vec![
-                    "If the theme set background should be shown.",
-                    "",
-                    "Set to False if you want terminal background",
-                    "transparency.",
-                ],
-            ),
-            (
-                "view_mode",
-                vec![
-                    "Set bpytop view mode.",
-                    "",
-                    "\"full\" for everything shown.",
-                    "\"proc\" for cpu stats and processes.",
-                    "\"stat\" for cpu, mem, disks and net stats shown.",
-                ],
-            ),
-            (
-                "update_ms",
-                vec![
-                    "Update time in milliseconds.",
-                    "",
-                    "Recommended 2000 ms or above for better sample",
-                    "times for graphs.",
-                    "",
-                    "Min value: 100 ms",
-                    "Max value: 86400000 ms = 24 hours.",
-                ],
-            ),
-            (
-                "proc_sorting",
-                vec![
-                    "Processes sorting option.",
-                    "",
-                    "Possible values: \"pid\", \"program\", \"arguments\",",
-                    "\"threads\", \"user\", \"memory\", \"cpu lazy\" and",
-                    "\"cpu responsive\".",
-                    "",
-                    "\"cpu lazy\" updates top process over time,",
-                    "\"cpu responsive\" updates top process directly.",
-                ],
-            ),
-            (
-                "proc_reversed",
-                vec!["Reverse processes sorting order.", "", "True or False."],
-            ),
-            (
-                "proc_tree",
-                vec![
-                    "Processes tree view.",
-                    "",
-                    "Set true to show processes grouped by parents,",
-                    "with lines drawn between parent and child",
-                    "process.",
-                ],
-            ),
-            (
-                "tree_depth",
-                vec![
-                    "Process tree auto collapse depth.",
-                    "",
-                    "Sets the depth were the tree view will auto",
-                    "collapse processes at.",
-                ],
-            ),
-            (
-                "proc_colors",
-                vec![
-                    "Enable colors in process view.",
-                    "",
-                    "Uses the cpu graph gradient colors.",
-                ],
-            ),
-            (
-                "proc_gradient",
-                vec![
-                    "Enable process view gradient fade.",
-                    "",
-                    "Fades from top or current selection.",
-                    "Max fade value is equal to current themes",
-                    "\"inactive_fg\" color value.",
-                ],
-            ),
-            (
-                "proc_per_core",
-                vec![
-                    "Process usage per core.",
-                    "",
-                    "If process cpu usage should be of the core",
-                    "it\'s running on or usage of the total",
-                    "available cpu power.",
-                    "",
-                    "If true and process is multithreaded",
-                    "cpu usage can reach over 100%.",
-                ],
-            ),
-            (
-                "proc_mem_bytes",
-                vec![
-                    "Show memory as bytes in process list.",
-                    " ",
-                    "True or False.",
-                ],
-            ),
-            (
-                "check_temp",
-                vec!["Enable cpu temperature reporting.", "", "True or False."],
-            ),
-            (
-                "cpu_sensor",
-                vec![
-                    "Cpu temperature sensor",
-                    "",
-                    "Select the sensor that corresponds to",
-                    "your cpu temperature.",
-                    "Set to \"Auto\" for auto detection.",
-                ],
-            ),
-            (
-                "show_coretemp",
-                vec![
-                    "Show temperatures for cpu cores.",
-                    "",
-                    "Only works if check_temp is True and",
-                    "the system is reporting core temps.",
-                ],
-            ),
-            (
-                "draw_clock",
-                vec![
-                    "Draw a clock at top of screen.",
-                    "",
-                    "Formatting according to strftime, empty",
-                    "string to disable.",
-                    "",
-                    "Custom formatting options:",
-                    "\"/host\" = hostname",
-                    "\"/user\" = username",
-                    "",
-                    "Examples of strftime formats:",
-                    "\"%X\" = locale HH:MM:SS",
-                    "\"%H\" = 24h hour, \"%I\" = 12h hour",
-                    "\"%M\" = minute, \"%S\" = second",
-                    "\"%d\" = day, \"%m\" = month, \"%y\" = year",
-                ],
-            ),
-            (
-                "background_update",
-                vec![
-                    "Update main ui when menus are showing.",
-                    "",
-                    "True or False.",
-                    "",
-                    "Set this to false if the menus is flickering",
-                    "too much for a comfortable experience.",
-                ],
-            ),
-            (
-                "custom_cpu_name",
-                vec![
-                    "Custom cpu model name in cpu percentage box.",
-                    "",
-                    "Empty string to disable.",
-                ],
-            ),
-            (
-                "disks_filter",
-                vec![
-                    "Optional filter for shown disks.",
-                    "",
-                    "Should be last folder in path of a mountpoint,",
-                    "\"root\" replaces \"/\", separate multiple values",
-                    "with a comma.",
-                    "Begin line with \"exclude=\" to change to exclude",
-                    "filter.",
-                    "Oterwise defaults to \"most include\" filter.",
-                    "",
-                    "Example: disks_filter=\"exclude=boot, home\"",
-                ],
-            ),
-            (
-                "mem_graphs",
-                vec!["Show graphs for memory values.", "", "True or False."],
-            ),
-            (
-                "show_swap",
-                vec![
-                    "If swap memory should be shown in memory box.",
-                    "",
-                    "True or False.",
-                ],
-            ),
-            (
-                "swap_disk",
-                vec![
-                    "Show swap as a disk.",
-                    "",
-                    "Ignores show_swap value above.",
-                    "Inserts itself after first disk.",
-                ],
-            ),
-            (
-                "show_disks",
-                vec!["Split memory box to also show disks.", "", "True or False."],
-            ),
-            (
-                "net_download",
-                vec![
-                    "Fixed network graph download value.",
-                    "",
-                    "Default \"10M\" = 10 MibiBytes.",
-                    "Possible units:",
-                    "\"K\" (KiB), \"M\" (MiB), \"G\" (GiB).",
-                    "",
-                    "Append \"bit\" for bits instead of bytes,",
-                    "i.e \"100Mbit\"",
-                    "",
-                    "Can be toggled with auto button.",
-                ],
-            ),
-            (
-                "net_upload",
-                vec![
-                    "Fixed network graph upload value.",
-                    "",
-                    "Default \"10M\" = 10 MibiBytes.",
-                    "Possible units:",
-                    "\"K\" (KiB), \"M\" (MiB), \"G\" (GiB).",
-                    "",
-                    "Append \"bit\" for bits instead of bytes,",
-                    "i.e \"100Mbit\"",
-                    "",
-                    "Can be toggled with auto button.",
-                ],
-            ),
-            (
-                "net_auto",
-                vec![
-                    "Start in network graphs auto rescaling mode.",
-                    "",
-                    "Ignores any values set above at start and",
-                    "rescales down to 10KibiBytes at the lowest.",
-                    "",
-                    "True or False.",
-                ],
-            ),
-            (
-                "net_sync",
-                vec![
-                    "Network scale sync.",
-                    "",
-                    "Syncs the scaling for download and upload to",
-                    "whichever currently has the highest scale.",
-                    "",
-                    "True or False.",
-                ],
-            ),
-            (
-                "net_color_fixed",
-                vec![
-                    "Set network graphs color gradient to fixed.",
-                    "",
-                    "If True the network graphs color is based",
-                    "on the total bandwidth usage instead of",
-                    "the current autoscaling.",
-                    "",
-                    "The bandwidth usage is based on the",
-                    "\"net_download\" and \"net_upload\" values set",
-                    "above.",
-                ],
-            ),
-            (
-                "show_battery",
-                vec![
-                    "Show battery stats.",
-                    "",
-                    "Show battery stats in the top right corner",
-                    "if a battery is present.",
-                ],
-            ),
-            (
-                "show_init",
-                vec![
-                    "Show init screen at startup.",
-                    "",
-                    "The init screen is purely cosmetical and",
-                    "slows down start to show status messages.",
-                ],
-            ),
-            (
-                "update_check",
-                vec![
-                    "Check for updates at start.",
-                    "",
-                    "Checks for latest version from:",
-                    "https://github.com/aristocratos/bpytop",
-                ],
-            ),
-            (
-                "log_level",
-                vec![
-                    "Set loglevel for error.log",
-                    "",
-                    "Levels are: \"ERROR\" \"WARNING\" \"INFO\" \"DEBUG\".",
-                    "The level set includes all lower levels,",
-                    "i.e. \"DEBUG\" will show all logging info.",
-                ],
-            ),
-        ]
-        .iter()
-        .map(|(key, val)| {
-            (
-                key.clone().to_owned(),
-                val.iter().map(|s| s.clone().to_owned()).collect(),
-            )
-        })
-        .collect();
+        let mut option_items: HashMap<String, Vec<String>> = Config::options()
+            .into_iter()
+            .map(|info| (info.name.to_owned(), info.menu))
+            .collect();
 
         let option_len: usize = option_items.clone().len() * 2;
         let sorting_i: usize = CONFIG
@@ -1269,21 +1328,21 @@ impl Menu {
                             THEME
                                 .colors
                                 .main_fg
-                                .call(symbol::title_left.to_owned(), term),
+                                .call(symbol::title_left().to_owned(), term),
                             fx::b,
                             THEME.colors.title.call("pg".to_owned(), term),
                             fx::ub,
-                            THEME.colors.main_fg.call(symbol::up.to_owned(), term),
+                            THEME.colors.main_fg.call(symbol::up().to_owned(), term),
                             fx::b,
                             THEME.colors.title,
                             page,
                             pages,
                             fx::ub,
-                            THEME.colors.main_fg.call(symbol::down.to_owned(), term),
+                            THEME.colors.main_fg.call(symbol::down().to_owned(), term),
                             THEME
                                 .colors
                                 .div_line
-                                .call(symbol::title_right.to_owned(), term),
+                                .call(symbol::title_right().to_owned(), term),
                         )
                         .as_str(),
                     );
@@ -1295,23 +1354,11 @@ impl Menu {
                         continue;
                     }
 
-                    let mut value: ConfigAttr = CONFIG.getattr(opt.clone());
+                    let value: ConfigAttr = CONFIG.getattr(opt.clone());
                     let attr: String = match value {
                         ConfigAttr::Bool(_) => "bool".to_owned(),
                         ConfigAttr::Int64(_) => "i64".to_owned(),
-                        ConfigAttr::LogLevel(l) => {
-                            value = ConfigAttr::String(l.to_string());
-                            "String".to_owned()
-                        }
-                        ConfigAttr::SortingOption(s) => {
-                            value = ConfigAttr::String(s.to_string());
-                            "String".to_owned()
-                        }
                         ConfigAttr::String(_) => "String".to_owned(),
-                        ConfigAttr::ViewMode(v) => {
-                            value = ConfigAttr::String(v.to_string());
-                            "String".to_owned()
-                        }
                     };
 
                     let t_color: String = format!(
@@ -1364,23 +1411,13 @@ impl Menu {
 
                     if opt == selected {
                         if attr == "bool".to_owned()
-                            || vec![
-                                "color_theme",
-                                "proc_sorting",
-                                "log_level",
-                                "view_mode",
-                                "cpu_sensor",
-                            ]
-                            .iter()
-                            .map(|s| s.clone().to_owned())
-                            .collect::<Vec<String>>()
-                            .contains(&opt.clone())
+                            || Config::option(opt.as_str()).map_or(false, |info| info.cycle)
                         {
                             out.push_str(
                                 format!(
                                     "{} {}{}{:^20.20}{}{}",
                                     t_color,
-                                    symbol::left,
+                                    symbol::left(),
                                     v_color,
                                     d_quote.clone()
                                         + match value {
@@ -1394,7 +1431,7 @@ impl Menu {
                                         .as_str()
                                         + d_quote.clone().as_str(),
                                     t_color,
-                                    symbol::right
+                                    symbol::right()
                                 )
                                 .as_str(),
                             );
@@ -1402,12 +1439,15 @@ impl Menu {
                             out.push_str(
                                 format!(
                                     "{:^33.33}",
-                                    input_val.clone()[input_val.len() - 18..].to_owned()
+                                    input_val
+                                        .chars()
+                                        .skip(input_val.chars().count().saturating_sub(18))
+                                        .collect::<String>()
                                         + fx::bl
                                         + "█"
                                         + fx::ubl
                                         + ""
-                                        + symbol::enter,
+                                        + symbol::enter(),
                                 )
                                 .as_str(),
                             );
@@ -1416,7 +1456,7 @@ impl Menu {
                                 format!(
                                     "{}{:^20.20}{}",
                                     if attr == "i64".to_owned() {
-                                        format!("{} {}{}", t_color, symbol::left, v_color,)
+                                        format!("{} {}{}", t_color, symbol::left(), v_color,)
                                     } else {
                                         "  ".to_owned()
                                     },
@@ -1424,15 +1464,12 @@ impl Menu {
                                         ConfigAttr::Bool(b) => b.to_string(),
                                         ConfigAttr::Int64(i) => i.to_string(),
                                         ConfigAttr::String(s) => s.clone(),
-                                        ConfigAttr::LogLevel(l) => l.clone().to_string(),
-                                        ConfigAttr::SortingOption(s) => s.clone().to_string(),
-                                        ConfigAttr::ViewMode(v) => v.clone().to_string(),
                                     }
                                     .clone()
                                         + " "
-                                        + symbol::enter,
+                                        + symbol::enter(),
                                     if attr == "i64".to_owned() {
-                                        format!("{}{} ", t_color, symbol::right)
+                                        format!("{}{} ", t_color, symbol::right())
                                     } else {
                                         "  ".to_owned()
                                     }
@@ -1449,9 +1486,6 @@ impl Menu {
                                         ConfigAttr::Bool(b) => b.to_string(),
                                         ConfigAttr::Int64(i) => i.to_string(),
                                         ConfigAttr::String(s) => s.clone(),
-                                        ConfigAttr::LogLevel(l) => l.clone().to_string(),
-                                        ConfigAttr::SortingOption(s) => s.clone().to_string(),
-                                        ConfigAttr::ViewMode(v) => v.clone().to_string(),
                                     }
                                     .clone()
                                     .as_str()
@@ -1462,7 +1496,20 @@ impl Menu {
                     }
                     out.push_str(term.get_bg().to_string().as_str());
                     if opt == selected {
-                        let h2: u32 = (option_items[&opt].len() + 2) as u32;
+                        let mut descriptions: Vec<String> = option_items[&opt].clone();
+                        if opt == "color_theme".to_owned() && THEME.warnings.len() > 0 {
+                            // * Show what was wrong with the current theme, the full list is in the error log
+                            descriptions.push(String::default());
+                            descriptions.push(format!("Warnings in \"{}\":", THEME.current));
+                            descriptions.extend(THEME.warnings.iter().take(5).cloned());
+                            if THEME.warnings.len() > 5 {
+                                descriptions.push(format!(
+                                    "and {} more in the error log",
+                                    THEME.warnings.len() - 5
+                                ));
+                            }
+                        }
+                        let h2: u32 = (descriptions.len() + 2) as u32;
                         let mut y2: u32 = (y + (selected_int as u32 * 2) - ((page - 1) * h)) as u32;
                         if y2 + h2 > term.get_height() as u32 {
                             y2 = term.get_height() as u32 - h2;
@@ -1490,7 +1537,7 @@ impl Menu {
                             .as_str(),
                         );
                         let mut n2: usize = 0;
-                        for desc in option_items[&opt.clone()].clone() {
+                        for desc in descriptions {
                             out.push_str(
                                 format!("{}{:.48}", mv::to(y2 + 1 + n2 as u32, x2 + 2), desc)
                                     .as_str(),
@@ -1584,80 +1631,78 @@ impl Menu {
                         inputting = false;
                     } else if key == "enter".to_owned() {
                         inputting = false;
-                        match CONFIG.getattr(selected.clone()) {
-                            ConfigAttr::String(s) => {
-                                if s != input_val {
-                                    let parsed: i64 = input_val.parse::<i64>().unwrap();
-                                    if selected == "update_ms".to_owned() {
-                                        if input_val == String::default() || parsed < 100 {
-                                            CONFIG.update_ms = 100;
-                                        } else if parsed > 86399900 {
-                                            CONFIG.update_ms = 86399900;
-                                        } else {
-                                            CONFIG.update_ms = parsed;
-                                        }
-                                    } else if selected == "tree_depth".to_owned() {
-                                        if input_val == String::default() || parsed < 0 {
-                                            CONFIG.tree_depth = 0;
-                                        } else {
-                                            CONFIG.tree_depth = parsed as i32;
-                                        }
-                                    } else {
-                                        match CONFIG.getattr(selected.clone()) {
-                                            ConfigAttr::String(s) => {
-                                                CONFIG.setattr_configattr(
-                                                    selected.clone(),
-                                                    ConfigAttr::String(input_val.clone()),
-                                                );
-                                                if selected.starts_with("net_") {
-                                                    netcollector.set_net_min(
-                                                        vec![
-                                                            ("download".to_owned(), -1),
-                                                            ("upload".to_owned(), -1),
-                                                        ]
-                                                        .iter()
-                                                        .map(|(s, i)| (s.clone(), *i as i32))
-                                                        .collect::<HashMap<String, i32>>(),
-                                                    );
-                                                } else if selected == "draw_clock".to_owned() {
-                                                    brshtop_box
-                                                        .set_clock_on(CONFIG.draw_clock.len() > 0);
-                                                    if !brshtop_box.get_clock_on() {
-                                                        draw.clear(vec!["clock".to_owned()], true);
-                                                    }
-                                                }
-                                            }
-                                            _ => (),
-                                        }
-                                        term.refresh(
-                                            vec![],
-                                            boxes.clone(),
-                                            collector,
-                                            init,
-                                            cpubox,
-                                            draw,
-                                            true,
-                                            key_class,
-                                            self,
-                                            brshtop_box,
-                                            timer,
-                                            CONFIG,
-                                            THEME,
-                                            cpucollector,
-                                            membox,
-                                            netbox,
-                                            procbox,
-                                        );
-                                        self.resized = false;
+                        if CONFIG.get_value(selected.as_str()) != Some(input_val.clone()) {
+                            let info: OptionInfo = Config::option(selected.as_str()).unwrap();
+                            if info.kind == Kind::Int {
+                                // * Numbers out of range are set to the nearest allowed value
+                                input_val = info
+                                    .clamp(input_val.parse::<i64>().unwrap_or(0))
+                                    .to_string();
+                            }
+                            if selected == "layouts".to_owned() || selected == "layout".to_owned() {
+                                let layouts: String = if selected == "layouts".to_owned() {
+                                    input_val.clone()
+                                } else {
+                                    CONFIG.layouts.clone()
+                                };
+                                let mut layout: String = if selected == "layout".to_owned() {
+                                    input_val.clone()
+                                } else {
+                                    CONFIG.layout.clone()
+                                };
+                                // * Only accept definitions that parse, and drop an active layout that no longer exists
+                                if let Ok(parsed) = Layout::parse_all(&layouts) {
+                                    if !parsed.iter().any(|l| l.name == layout) {
+                                        layout = String::default();
                                     }
+                                    CONFIG.layouts = layouts;
+                                    CONFIG.layout = layout;
+                                    CONFIG.mark_changed("layouts");
+                                    CONFIG.mark_changed("layout");
+                                    brshtop_box.set_layouts(CONFIG.layouts.clone());
+                                    draw.clear(vec![], true);
                                 }
+                            } else if selected == "color_theme_light".to_owned()
+                                && input_val.len() > 0
+                                && !THEME.themes.contains_key(&input_val)
+                            {
+                                error::errlog(format!("Theme {:?} was not found", input_val));
+                            } else if let Err(e) =
+                                CONFIG.set_value(selected.as_str(), input_val.as_str())
+                            {
+                                // * Invalid values are dropped and the old value is kept
+                                error::errlog(e);
+                            } else {
+                                CONFIG.mark_changed(selected.as_str());
+                                self.apply_option(
+                                    &selected,
+                                    THEME,
+                                    draw,
+                                    term,
+                                    CONFIG,
+                                    key_class,
+                                    timer,
+                                    netcollector,
+                                    brshtop_box,
+                                    boxes.clone(),
+                                    collector,
+                                    init,
+                                    cpubox,
+                                    cpucollector,
+                                    netbox,
+                                    proc_collector,
+                                    procbox,
+                                    membox,
+                                );
+                                self.resized = false;
                             }
-                            _ => (),
-                        };
-                    } else if key == "backspace".to_owned() && input_val.len() > 0 {
-                        input_val = input_val[..input_val.len() - 2].to_owned();
+                        }
+                    } else if key == "backspace".to_owned() {
+                        input_val.pop();
                     } else if key == "delete".to_owned() {
                         input_val = String::default();
+                    } else if key == "paste".to_owned() {
+                        input_val.push_str(key_class.take_paste().as_str());
                     } else if match CONFIG.getattr(selected.clone()) {
                         ConfigAttr::String(_) => true,
                         _ => false,
@@ -1691,20 +1736,28 @@ impl Menu {
                 {
                     self.close = true;
                     break;
+                } else if key == "enter".to_owned() && selected == "color_theme".to_owned() {
+                    self.theme_editor(
+                        THEME,
+                        draw,
+                        term,
+                        CONFIG,
+                        key_class,
+                        timer,
+                        brshtop_box,
+                        boxes.clone(),
+                        collector,
+                        init,
+                        cpubox,
+                        cpucollector,
+                        netbox,
+                        collectors.clone(),
+                        procbox,
+                        membox,
+                    );
                 } else if key == "enter".to_owned()
-                    && [
-                        "update_ms",
-                        "disks_filter",
-                        "custom_cpu_name",
-                        "net_download",
-                        "net_upload",
-                        "draw_clock",
-                        "tree_depth",
-                    ]
-                    .iter()
-                    .map(|s| s.to_owned().to_owned())
-                    .collect::<Vec<String>>()
-                    .contains(&selected.clone())
+                    && Config::option(selected.as_str())
+                        .map_or(false, |info| info.kind != Kind::Bool && !info.cycle)
                 {
                     inputting = true;
                     input_val = CONFIG.getattr(selected.clone()).to_string();
@@ -1713,6 +1766,7 @@ impl Menu {
                     && CONFIG.update_ms - 100 >= 100
                 {
                     CONFIG.update_ms -= 100;
+                    CONFIG.mark_changed("update_ms");
                     brshtop_box.draw_update_ms(
                         true,
                         CONFIG,
@@ -1728,6 +1782,7 @@ impl Menu {
                     && CONFIG.update_ms + 100 <= 86399900
                 {
                     CONFIG.update_ms += 100;
+                    CONFIG.mark_changed("update_ms");
                     brshtop_box.draw_update_ms(
                         true,
                         CONFIG,
@@ -1743,6 +1798,7 @@ impl Menu {
                     && CONFIG.tree_depth > 0
                 {
                     CONFIG.tree_depth -= 1;
+                    CONFIG.mark_changed("tree_depth");
                     proc_collector.collapsed = HashMap::<u32, bool>::new();
                 } else if ["left", "right"]
                     .iter()
@@ -1759,52 +1815,25 @@ impl Menu {
                         _ => false,
                     };
                     CONFIG.setattr_configattr(selected.clone(), ConfigAttr::Bool(inserter));
-                    if selected == "check_temp".to_owned() {
-                        if CONFIG.check_temp {
-                            cpucollector.get_sensors(CONFIG);
-                        } else {
-                            cpucollector.set_sensor_method(String::default());
-                            cpucollector.set_got_sensors(false);
-                        }
-                    }
-                    if ["net_auto", "net_color_fixed", "net_sync"]
-                        .iter()
-                        .map(|s| s.to_owned().to_owned())
-                        .collect::<Vec<String>>()
-                        .contains(&selected)
-                    {
-                        if selected == "net_auto".to_owned() {
-                            netcollector.set_auto_min(CONFIG.net_auto);
-                        }
-                        netbox.set_redraw(true);
-                    } else if selected == "theme_background".to_owned() {
-                        term.set_bg(if CONFIG.theme_background {
-                            THEME.colors.main_bg
-                        } else {
-                            Color::from("\x1b[49m".to_owned())
-                        });
-                        draw.now(vec![term.get_bg().to_string()], key_class);
-                    } else if selected == "show_battery".to_owned() {
-                        draw.clear(vec!["battery".to_owned()], true);
-                    }
-                    term.refresh(
-                        vec![],
+                    self.apply_option(
+                        &selected,
+                        THEME,
+                        draw,
+                        term,
+                        CONFIG,
+                        key_class,
+                        timer,
+                        netcollector,
+                        brshtop_box,
                         boxes.clone(),
                         collector,
                         init,
                         cpubox,
-                        draw,
-                        true,
-                        key_class,
-                        self,
-                        brshtop_box,
-                        timer,
-                        CONFIG,
-                        THEME,
                         cpucollector,
-                        membox,
                         netbox,
+                        proc_collector,
                         procbox,
+                        membox,
                     );
                     self.resized = true;
                 } else if ["left", "right"]
@@ -1829,7 +1858,8 @@ impl Menu {
                     CONFIG.color_theme =
                         THEME.themes.keys().cloned().collect::<Vec<String>>()[color_i].clone();
                     THEME.replace_self(
-                        Theme::from_str(CONFIG.color_theme.clone()).unwrap_or(Theme::default()),
+                        Theme::from_str(CONFIG.theme_name(term.get_light()))
+                            .unwrap_or(Theme::default()),
                     );
                     term.refresh(
                         vec![],
@@ -1880,6 +1910,7 @@ impl Menu {
                         }
                     }
                     CONFIG.log_level = CONFIG.log_levels[loglevel_i];
+                    CONFIG.mark_changed("log_level");
                     // TODO : Implement error logging level
                     error::errlog(
                         ("LogLevel set to ".to_owned() + CONFIG.log_level.to_string().as_str())
@@ -1906,6 +1937,7 @@ impl Menu {
                     }
                     cpucollector.set_sensor_swap(true);
                     CONFIG.cpu_sensor = CONFIG.cpu_sensors[cpu_sensor_i].clone();
+                    CONFIG.mark_changed("cpu_sensor");
                     if CONFIG.check_temp
                         && (cpucollector.get_sensor_method() != "psutil".to_owned()
                             || CONFIG.cpu_sensor == "Auto".to_owned())
@@ -1951,6 +1983,9 @@ impl Menu {
                         }
                     }
                     CONFIG.view_mode = CONFIG.view_modes[view_mode_i];
+                    CONFIG.layout = String::default();
+                    CONFIG.mark_changed("view_mode");
+                    CONFIG.mark_changed("layout");
                     brshtop_box.set_proc_mode(CONFIG.view_mode.t == ViewModeEnum::Proc);
                     brshtop_box.set_stat_mode(CONFIG.view_mode.t == ViewModeEnum::Stat);
                     if ARG_MODE.t != ViewModeEnum::None {
@@ -2047,4 +2082,506 @@ impl Menu {
         self.active = false;
         self.close = false;
     }
+
+    /// Edit the colors of the current theme with a live preview on the boxes, and save them as a user theme
+    pub fn theme_editor(
+        &mut self,
+        THEME: &mut Theme,
+        draw: &mut Draw,
+        term: &mut Term,
+        CONFIG: &mut Config,
+        key_class: &mut Key,
+        timer: &mut Timer,
+        brshtop_box: &mut BrshtopBox,
+        boxes: Vec<Boxes>,
+        collector: &mut Collector,
+        init: &mut Init,
+        cpubox: &mut CpuBox,
+        cpucollector: &mut CpuCollector,
+        netbox: &mut NetBox,
+        collectors: Vec<Collectors>,
+        procbox: &mut ProcBox,
+        membox: &mut MemBox,
+    ) {
+        let mut out: String = String::default();
+        let mut out_misc: String = String::default();
+        let mut redraw: bool = true;
+        let mut key: String = String::default();
+        let mut skip: bool = false;
+        let mut selected: usize = 0;
+        let mut offset: usize = 0;
+        let mut palette: Option<u8> = None;
+        let mut inputting: String = String::default();
+        let mut input_val: String = String::default();
+        let mut message: String = String::default();
+        let mut changed: bool = false;
+        let mut left: bool = false;
+        let mut x: u32 = 0;
+        let y: u32 = 1;
+        let w: u32 = 46;
+        let mut h: u32 = 0;
+        let mut list_h: u32 = 0;
+        let footer: u32 = 5;
+
+        let mut original: Theme = THEME.clone();
+        let mut tdict: HashMap<String, String> = Theme::complete(
+            &THEME
+                .cached
+                .get(&THEME.current)
+                .cloned()
+                .unwrap_or_default(),
+        );
+        let mut save_name: String = THEME.current.trim_start_matches('+').to_owned();
+
+        // * The boxes are shown in full color behind the editor to preview the changes
+        self.background = draw.saved_buffer();
+        self.resized = true;
+
+        loop {
+            key = String::default();
+            if self.resized {
+                h = term.get_height() as u32;
+                x = if left {
+                    1
+                } else {
+                    term.get_width() as u32 - w + 1
+                };
+                list_h = h - 2 - footer;
+                out_misc = create_box(
+                    x,
+                    y,
+                    w,
+                    h,
+                    Some("theme editor".to_owned()),
+                    None,
+                    None,
+                    None,
+                    true,
+                    None,
+                    term,
+                    THEME,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                );
+                self.resized = false;
+                redraw = true;
+            }
+
+            if redraw {
+                out = String::default();
+                for row in 0..h - 2 {
+                    out.push_str(
+                        format!(
+                            "{}{}{}",
+                            term.get_bg(),
+                            mv::to(y + 1 + row, x + 1),
+                            " ".repeat((w - 2) as usize)
+                        )
+                        .as_str(),
+                    );
+                }
+                let item: &str = THEME_KEYS[selected];
+
+                if let Some(index) = palette {
+                    for row in 0..16 {
+                        out.push_str(mv::to(y + 1 + row, x + 2).as_str());
+                        for col in 0..16 {
+                            let n: u8 = (row * 16 + col) as u8;
+                            let cell: Color = Color::bg(format!("{:X}", Color::from_256(n)))
+                                .unwrap_or(Color::BlackBg());
+                            out.push_str(
+                                format!(
+                                    "{}{}{}",
+                                    cell,
+                                    THEME.colors.main_fg,
+                                    if n == index { "[]" } else { "  " }
+                                )
+                                .as_str(),
+                            );
+                        }
+                        out.push_str(term.get_bg().to_string().as_str());
+                    }
+                    out.push_str(
+                        format!(
+                            "{}{}{}Color {:>3}{}{}{:X}",
+                            mv::to(y + 1, x + 35),
+                            THEME.colors.title,
+                            fx::b,
+                            index,
+                            fx::ub,
+                            mv::to(y + 2, x + 35),
+                            Color::from_256(index)
+                        )
+                        .as_str(),
+                    );
+                } else {
+                    if selected < offset {
+                        offset = selected;
+                    } else if selected >= offset + list_h as usize {
+                        offset = selected + 1 - list_h as usize;
+                    }
+                    for (i, name) in THEME_KEYS
+                        .iter()
+                        .enumerate()
+                        .skip(offset)
+                        .take(list_h as usize)
+                    {
+                        let name_str: String = if i == selected {
+                            format!(
+                                "{}{}{}{:<20}{}{}",
+                                THEME.colors.selected_bg,
+                                THEME.colors.selected_fg,
+                                fx::b,
+                                name,
+                                fx::ub,
+                                term.get_bg()
+                            )
+                        } else {
+                            format!("{}{:<20}", THEME.colors.main_fg, name)
+                        };
+                        let item_value: String = tdict.get(*name).cloned().unwrap_or_default();
+                        let swatch: String = match Color::bg(&item_value) {
+                            Ok(c) if !c.is_null() => format!("{}    {}", c, term.get_bg()),
+                            _ => "    ".to_owned(),
+                        };
+                        out.push_str(
+                            format!(
+                                "{}{}{} {}{:<15.15}",
+                                mv::to(y + 1 + (i - offset) as u32, x + 2),
+                                name_str,
+                                swatch,
+                                THEME.colors.main_fg,
+                                if item_value.len() == 0 {
+                                    "none".to_owned()
+                                } else {
+                                    item_value
+                                },
+                            )
+                            .as_str(),
+                        );
+                    }
+                }
+
+                // * Gradient of the selected color as the boxes will draw it
+                let fy: u32 = y + 1 + list_h;
+                let prefix: String = item.rsplitn(2, '_').last().unwrap_or("").to_owned();
+                if palette.is_none()
+                    && (item.ends_with("_start")
+                        || item.ends_with("_mid")
+                        || item.ends_with("_end"))
+                {
+                    if let Some(gradient) = THEME.gradient.get(&prefix) {
+                        out.push_str(
+                            format!("{}{}{:<12}", mv::to(fy, x + 2), THEME.colors.title, prefix)
+                                .as_str(),
+                        );
+                        for i in 0..30 {
+                            out.push_str(
+                                format!("{}{}", gradient[i * 100 / 29], symbol::meter()).as_str(),
+                            );
+                        }
+                    }
+                }
+                if inputting.len() > 0 {
+                    out.push_str(
+                        format!(
+                            "{}{}{}{}{}{:.30}{}_{}",
+                            mv::to(fy + 1, x + 2),
+                            THEME.colors.title,
+                            fx::b,
+                            match inputting.as_str() {
+                                "value" => format!("{}: ", item),
+                                "import" => "Import from: ".to_owned(),
+                                _ => "Save as: +".to_owned(),
+                            },
+                            fx::ub,
+                            input_val,
+                            fx::bl,
+                            fx::ubl,
+                        )
+                        .as_str(),
+                    );
+                }
+                out.push_str(
+                    format!(
+                        "{}{}{:.42}{}{}{}{}",
+                        mv::to(fy + 2, x + 2),
+                        THEME.colors.hi_fg,
+                        message,
+                        mv::to(fy + 3, x + 2),
+                        THEME.colors.main_fg,
+                        if palette.is_some() {
+                            "Arrows: Move   Enter: Pick"
+                        } else {
+                            "Enter: Edit   p: Palette   d: Default"
+                        },
+                        mv::to(fy + 4, x + 2),
+                    )
+                    .as_str(),
+                );
+                out.push_str(if palette.is_some() {
+                    "Esc: Cancel"
+                } else {
+                    "s: Save   i: Import   m: Move   Esc: Back"
+                });
+            }
+
+            if skip && redraw {
+                draw.now(vec![out.clone()], key_class);
+            } else if !skip {
+                draw.now(
+                    vec![format!("{}{}{}", self.background, out_misc, out)],
+                    key_class,
+                );
+            }
+            skip = false;
+            redraw = false;
+
+            if key_class.input_wait(timer.left(CONFIG).as_secs_f64(), false, draw, term) {
+                key = match key_class.get() {
+                    Some(k) => k,
+                    None => "".to_owned(),
+                };
+                redraw = true;
+                let mut apply: Option<String> = None;
+                let mut reload: bool = false;
+
+                if key == "mouse_click".to_owned() && inputting.len() == 0 {
+                    let (mx, my) = key_class.get_mouse();
+                    let inside_x =
+                        |start: u32, end: u32| -> bool { mx >= start as i32 && mx < end as i32 };
+                    if palette.is_some() && my > y as i32 && my <= (y + 16) as i32 {
+                        if inside_x(x + 2, x + 34) {
+                            palette =
+                                Some(((my as u32 - y - 1) * 16 + (mx as u32 - x - 2) / 2) as u8);
+                            key = "enter".to_owned();
+                        }
+                    } else if palette.is_none()
+                        && my > y as i32
+                        && my <= (y + list_h) as i32
+                        && inside_x(x + 1, x + w - 1)
+                    {
+                        let clicked: usize = offset + (my as u32 - y - 1) as usize;
+                        if clicked == selected {
+                            key = "enter".to_owned();
+                        } else if clicked < THEME_KEYS.len() {
+                            selected = clicked;
+                        }
+                    }
+                }
+
+                if inputting.len() > 0 {
+                    if key == "escape".to_owned() {
+                        inputting = String::default();
+                    } else if key == "enter".to_owned() {
+                        if inputting == "value".to_owned() {
+                            if Color::is_valid(&input_val) {
+                                apply = Some(input_val.clone());
+                            } else {
+                                message = format!("\"{}\" is not a color", input_val);
+                            }
+                        } else if inputting == "import".to_owned() {
+                            match themeimport::import(&input_val, "auto") {
+                                Ok((imported, warnings)) => {
+                                    for warning in warnings.iter() {
+                                        error::errlog(format!(
+                                            "Import \"{}\": {}",
+                                            input_val, warning
+                                        ));
+                                    }
+                                    tdict = imported;
+                                    save_name = themeimport::theme_name(&input_val);
+                                    message = format!(
+                                        "Imported with {} warnings, s to save",
+                                        warnings.len()
+                                    );
+                                    reload = true;
+                                }
+                                Err(e) => message = e,
+                            }
+                        } else if input_val.len() == 0
+                            || input_val.contains('/')
+                            || input_val.starts_with('.')
+                        {
+                            message = "Not a valid theme name".to_owned();
+                        } else {
+                            match Theme::save(&input_val, &tdict) {
+                                Ok(path) => {
+                                    save_name = input_val.clone();
+                                    // * A theme edited on a light background replaces the light theme
+                                    if term.get_light() && CONFIG.color_theme_light.len() > 0 {
+                                        CONFIG.color_theme_light = format!("+{}", save_name);
+                                        CONFIG.mark_changed("color_theme_light");
+                                    } else {
+                                        CONFIG.color_theme = format!("+{}", save_name);
+                                        CONFIG.mark_changed("color_theme");
+                                    }
+                                    THEME.refresh();
+                                    THEME.current = CONFIG.theme_name(term.get_light());
+                                    THEME.cached.insert(THEME.current.clone(), tdict.clone());
+                                    THEME.warnings = Vec::<String>::new();
+                                    // * Esc only drops the edits made after the save
+                                    original = THEME.clone();
+                                    changed = false;
+                                    message = format!("Saved to {}", path.display());
+                                }
+                                Err(e) => {
+                                    error::errlog(e.clone());
+                                    message = e;
+                                }
+                            }
+                        }
+                        inputting = String::default();
+                    } else if key == "backspace".to_owned() {
+                        input_val.pop();
+                    } else if key == "delete".to_owned() {
+                        input_val = String::default();
+                    } else if key == "paste".to_owned() {
+                        input_val.push_str(key_class.take_paste().as_str());
+                    } else if key.len() == 1 {
+                        input_val.push_str(key.as_str());
+                    }
+                } else if let Some(index) = palette {
+                    if key == "escape".to_owned() {
+                        palette = None;
+                    } else if key == "enter".to_owned() {
+                        apply = Some(format!("{:X}", Color::from_256(index)));
+                        palette = None;
+                    } else if key == "left".to_owned() {
+                        palette = Some(index.wrapping_sub(1));
+                    } else if key == "right".to_owned() {
+                        palette = Some(index.wrapping_add(1));
+                    } else if key == "up".to_owned() {
+                        palette = Some(index.wrapping_sub(16));
+                    } else if key == "down".to_owned() {
+                        palette = Some(index.wrapping_add(16));
+                    } else {
+                        redraw = false;
+                    }
+                } else if key == "q".to_owned() {
+                    clean_quit(None, None, key_class, collector, draw, term, CONFIG);
+                } else if ["escape", "backspace"].contains(&key.as_str()) {
+                    break;
+                } else if key == "enter".to_owned() {
+                    inputting = "value".to_owned();
+                    input_val = tdict.get(THEME_KEYS[selected]).cloned().unwrap_or_default();
+                    message = String::default();
+                } else if key == "s".to_owned() {
+                    inputting = "save".to_owned();
+                    input_val = save_name.clone();
+                    message = String::default();
+                } else if key == "p".to_owned() {
+                    palette = Some(
+                        Color::new(tdict.get(THEME_KEYS[selected]).cloned().unwrap_or_default())
+                            .map(|c| c.to_256())
+                            .unwrap_or(0),
+                    );
+                } else if key == "d".to_owned() {
+                    apply = DEFAULT_THEME.get(THEME_KEYS[selected]).cloned();
+                } else if key == "i".to_owned() {
+                    inputting = "import".to_owned();
+                    input_val = "terminal".to_owned();
+                    message = String::default();
+                } else if key == "m".to_owned() {
+                    left = !left;
+                    self.resized = true;
+                } else if vec!["up", "mouse_scroll_up"].contains(&key.as_str()) {
+                    selected = if selected > 0 {
+                        selected - 1
+                    } else {
+                        THEME_KEYS.len() - 1
+                    };
+                } else if vec!["down", "mouse_scroll_down"].contains(&key.as_str()) {
+                    selected = if selected < THEME_KEYS.len() - 1 {
+                        selected + 1
+                    } else {
+                        0
+                    };
+                } else if key == "page_up".to_owned() {
+                    selected = selected.saturating_sub(list_h as usize);
+                } else if key == "page_down".to_owned() {
+                    selected = (selected + list_h as usize).min(THEME_KEYS.len() - 1);
+                } else if key == "home".to_owned() {
+                    selected = 0;
+                } else if key == "end".to_owned() {
+                    selected = THEME_KEYS.len() - 1;
+                } else {
+                    redraw = false;
+                }
+
+                if let Some(value) = apply {
+                    tdict.insert(THEME_KEYS[selected].to_owned(), value);
+                    message = String::default();
+                    reload = true;
+                }
+                if reload {
+                    THEME.set_colors(Colors::from_dict(&tdict));
+                    changed = true;
+                    term.refresh(
+                        vec![],
+                        boxes.clone(),
+                        collector,
+                        init,
+                        cpubox,
+                        draw,
+                        true,
+                        key_class,
+                        self,
+                        brshtop_box,
+                        timer,
+                        CONFIG,
+                        THEME,
+                        cpucollector,
+                        membox,
+                        netbox,
+                        procbox,
+                    );
+                    self.resized = true;
+                }
+            }
+
+            if timer.not_zero(CONFIG) && !self.resized {
+                skip = true;
+            } else {
+                collector.collect(collectors.clone(), true, false, false, false, false);
+                self.background = draw.saved_buffer();
+                timer.stamp();
+                redraw = true;
+            }
+        }
+
+        // * Changes that weren't saved are dropped
+        if changed {
+            THEME.replace_self(original);
+            term.refresh(
+                vec![],
+                boxes.clone(),
+                collector,
+                init,
+                cpubox,
+                draw,
+                true,
+                key_class,
+                self,
+                brshtop_box,
+                timer,
+                CONFIG,
+                THEME,
+                cpucollector,
+                membox,
+                netbox,
+                procbox,
+            );
+        }
+        self.background = format!(
+            "{}{}{}",
+            THEME.colors.inactive_fg,
+            fx::Fx::uncolor(draw.saved_buffer()),
+            term.get_fg(),
+        );
+        self.resized = true;
+    }
 }
@@ -22,6 +22,7 @@ pub struct Meters {
     swap: HashMap<String, MeterUnion>,
     disks_used: HashMap<String, Meter>,
     disks_free: HashMap<String, Meter>,
+    disks_inodes: HashMap<String, Meter>,
 }
 impl Meters {
     pub fn get_cpu(&self) -> Meter {
@@ -115,6 +116,25 @@ impl Meters {
     pub fn set_disks_free_index(&mut self, index: String, element: Meter) {
         self.disks_free.insert(index.clone(), element.clone());
     }
+
+    pub fn get_disks_inodes(&self) -> HashMap<String, Meter> {
+        self.disks_inodes.clone()
+    }
+
+    pub fn set_disks_inodes(&mut self, disks_inodes: HashMap<String, Meter>) {
+        self.disks_inodes = disks_inodes.clone()
+    }
+
+    pub fn get_disks_inodes_index(&self, index: String) -> Option<Meter> {
+        match self.get_disks_inodes().get(&index.clone()) {
+            Some(m) => Some(m.clone()),
+            None => None,
+        }
+    }
+
+    pub fn set_disks_inodes_index(&mut self, index: String, element: Meter) {
+        self.disks_inodes.insert(index.clone(), element.clone());
+    }
 }
 
 #[derive(Default, Clone)]
@@ -190,14 +210,14 @@ impl Meter {
                         } else {
                             100 - (i * 100 / self.width) as usize
                         }],
-                        symbol::meter,
+                        symbol::meter(),
                     )
                     .as_str(),
                 );
             } else {
                 out.push_str(
                     self.color_inactive
-                        .call(symbol::meter.repeat((self.width + 1 - i) as usize), term)
+                        .call(symbol::meter().repeat((self.width + 1 - i) as usize), term)
                         .to_string()
                         .as_str(),
                 );
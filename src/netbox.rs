@@ -11,6 +11,7 @@ use {
         menu::Menu,
         mv,
         netcollector::{NetCollector, NetCollectorStat},
+        netstat::{NetStat, NicInfo},
         subbox::SubBox,
         symbol,
         term::Term,
@@ -60,14 +61,27 @@ impl NetBox {
         } else {
             width_p = self.get_parent().get_width_p();
         }
-        self.set_parent_width(((term.get_width() as u32) * width_p / 100) as u32);
-        self.set_parent_height(
-            u32::try_from(term.get_height() as i32 - b_cpu_h - b_mem_h).unwrap_or(0),
-        );
-        self.set_parent_y(
-            u32::try_from((term.get_height() as i32) - self.parent.get_height() as i32 + 1)
-                .unwrap_or(0),
-        );
+        match self.get_parent().get_layout_rect() {
+            Some((x, y, w, h)) => {
+                self.set_parent_x(x);
+                self.set_parent_y(y);
+                self.set_parent_width(w);
+                self.set_parent_height(h);
+            }
+            None => {
+                self.set_parent_x(1);
+                self.set_parent_width(((term.get_width() as u32) * width_p / 100) as u32);
+                self.set_parent_height(
+                    u32::try_from(term.get_height() as i32 - b_cpu_h - b_mem_h).unwrap_or(0),
+                );
+                self.set_parent_y(
+                    u32::try_from(
+                        (term.get_height() as i32) - self.parent.get_height() as i32 + 1,
+                    )
+                    .unwrap_or(0),
+                );
+            }
+        }
         self.set_sub_box_width(if self.parent.get_width() > 45 { 27 } else { 19 });
         self.set_sub_box_height(if self.parent.get_height() > 10 {
             9
@@ -75,8 +89,12 @@ impl NetBox {
             u32::try_from(self.parent.get_height() as i32 - 2).unwrap_or(0)
         });
         self.set_sub_box_x(
-            u32::try_from(self.parent.get_width() as i32 - self.sub.get_box_width() as i32 - 1)
-                .unwrap_or(0),
+            u32::try_from(
+                self.parent.get_x() as i32 + self.parent.get_width() as i32
+                    - self.sub.get_box_width() as i32
+                    - 2,
+            )
+            .unwrap_or(0),
         );
         self.set_sub_box_y(
             self.get_parent().get_y()
@@ -106,7 +124,7 @@ impl NetBox {
     }
 
     pub fn draw_bg(&self, theme: &Theme, term: &Term) -> String {
-        if self.parent.get_proc_mode() {
+        if self.parent.get_proc_mode() || self.parent.get_hidden() {
             return String::default();
         }
 
@@ -119,7 +137,11 @@ impl NetBox {
                 0,
                 None,
                 None,
-                Some(theme.colors.net_box),
+                Some(if self.parent.is_focused() {
+                    theme.colors.hi_fg
+                } else {
+                    theme.colors.net_box
+                }),
                 None,
                 true,
                 Some(Boxes::NetBox),
@@ -164,7 +186,7 @@ impl NetBox {
         menu: &Menu,
         net: &mut NetCollector,
     ) {
-        if self.get_parent().get_proc_mode() {
+        if self.get_parent().get_proc_mode() || self.get_parent().get_hidden() {
             return;
         }
 
@@ -185,6 +207,13 @@ impl NetBox {
         let by: u32 = self.get_sub().get_box_y() + 1;
         let bw: u32 = u32::try_from(self.get_sub().get_box_width() as i32 - 2).unwrap_or(0);
         let bh: u32 = u32::try_from(self.get_sub().get_box_height() as i32 - 2).unwrap_or(0);
+        // * Protocol graphs take a quarter of the graph area when there is room for them
+        let pw: u32 = if CONFIG.net_proto && w as i32 - bw as i32 - 3 >= 40 {
+            (w - bw - 3) / 4
+        } else {
+            0
+        };
+        let gw: u32 = u32::try_from(w as i32 - bw as i32 - 3 - pw as i32).unwrap_or(0);
         let nic_name: String = net.nic.clone().unwrap().name().to_owned();
         let reset: bool = match net.get_stats_inner_inner_index(
             nic_name.clone(),
@@ -245,8 +274,8 @@ impl NetBox {
                     "{}{}{}{}{}{}{}{}{}{}{}{}{}{} {} {}{}{}{}",
                     mv::to(y as u32 - 1, x as u32 + w - 25),
                     theme.colors.net_box,
-                    symbol::h_line.repeat(10 - nic_name[..10].len()),
-                    symbol::title_left,
+                    symbol::h_line().repeat(10 - nic_name[..10].len()),
+                    symbol::title_left(),
                     if reset { fx::bold } else { "" },
                     theme.colors.hi_fg.call("z".to_owned(), term),
                     theme.colors.title.call("ero".to_owned(), term),
@@ -254,10 +283,10 @@ impl NetBox {
                     theme
                         .colors
                         .net_box
-                        .call(symbol::title_right.to_owned(), term),
+                        .call(symbol::title_right().to_owned(), term),
                     term.get_fg(),
                     theme.colors.net_box,
-                    symbol::title_left,
+                    symbol::title_left(),
                     fx::b,
                     theme.colors.hi_fg.call("<b".to_owned(), term),
                     theme.colors.title.call(nic_name[..10].to_owned(), term),
@@ -266,7 +295,7 @@ impl NetBox {
                     theme
                         .colors
                         .net_box
-                        .call(symbol::title_right.to_owned(), term),
+                        .call(symbol::title_right().to_owned(), term),
                     term.get_fg(),
                 )
                 .as_str(),
@@ -295,7 +324,7 @@ impl NetBox {
                         theme
                             .colors
                             .net_box
-                            .call(symbol::title_left.to_owned(), term),
+                            .call(symbol::title_left().to_owned(), term),
                         if net.get_auto_min() { fx::b } else { "" },
                         theme.colors.hi_fg.call("a".to_owned(), term),
                         theme.colors.title.call("uto".to_owned(), term),
@@ -303,7 +332,7 @@ impl NetBox {
                         theme
                             .colors
                             .net_box
-                            .call(symbol::title_right.to_owned(), term),
+                            .call(symbol::title_right().to_owned(), term),
                         term.get_fg(),
                     )
                     .as_str(),
@@ -332,7 +361,7 @@ impl NetBox {
                         theme
                             .colors
                             .net_box
-                            .call(symbol::title_left.to_owned(), term),
+                            .call(symbol::title_left().to_owned(), term),
                         if net.get_auto_min() { fx::b } else { "" },
                         theme.colors.hi_fg.call("a".to_owned(), term),
                         theme.colors.title.call("auto".to_owned(), term),
@@ -340,7 +369,7 @@ impl NetBox {
                         theme
                             .colors
                             .net_box
-                            .call(symbol::title_right.to_owned(), term),
+                            .call(symbol::title_right().to_owned(), term),
                         term.get_fg(),
                     )
                     .as_str(),
@@ -370,7 +399,7 @@ impl NetBox {
                         theme
                             .colors
                             .net_box
-                            .call(symbol::title_left.to_owned(), term),
+                            .call(symbol::title_left().to_owned(), term),
                         if CONFIG.net_sync { fx::b } else { "" },
                         theme.colors.title.call("s".to_owned(), term),
                         theme.colors.hi_fg.call("y".to_owned(), term),
@@ -379,7 +408,7 @@ impl NetBox {
                         theme
                             .colors
                             .net_box
-                            .call(symbol::title_right.to_owned(), term),
+                            .call(symbol::title_right().to_owned(), term),
                         term.get_fg(),
                     )
                     .as_str(),
@@ -399,6 +428,13 @@ impl NetBox {
             );
         }
 
+        if CONFIG.net_multi {
+            out.push_str(
+                self.draw_multi(theme, term, CONFIG, graphs, net, x, y, gw, h)
+                    .as_str(),
+            );
+        }
+
         let mut cy = 0;
 
         for direction in ["download", "upload"]
@@ -416,30 +452,27 @@ impl NetBox {
             if self.get_redraw() {
                 stats.insert("redraw".to_owned(), NetCollectorStat::Bool(true));
             }
-            if match stats.get(&"redraw".to_owned()).unwrap() {
-                NetCollectorStat::Bool(b) => b.to_owned(),
-                NetCollectorStat::I32(i) => i.to_owned() > 0,
-                NetCollectorStat::Vec(v) => v.to_owned().len() > 0,
-                NetCollectorStat::U64(u) => u.to_owned() > 0,
-                NetCollectorStat::String(s) => {
-                    errlog("Malformed type in stats['redraw']".to_owned());
-                    s.to_owned().parse::<i32>().unwrap_or(0) > 0
-                }
-            } || self.get_parent().get_resized()
+            if !CONFIG.net_multi
+                && (match stats.get(&"redraw".to_owned()).unwrap() {
+                    NetCollectorStat::Bool(b) => b.to_owned(),
+                    NetCollectorStat::I32(i) => i.to_owned() > 0,
+                    NetCollectorStat::Vec(v) => v.to_owned().len() > 0,
+                    NetCollectorStat::U64(u) => u.to_owned() > 0,
+                    NetCollectorStat::String(s) => {
+                        errlog("Malformed type in stats['redraw']".to_owned());
+                        s.to_owned().parse::<i32>().unwrap_or(0) > 0
+                    }
+                } || self.get_parent().get_resized())
             {
                 graphs.net.insert(
                     direction.clone(),
                     Graph::new_with_vec::<Color>(
-                        w - bw - 3,
+                        gw,
                         self.graph_height
                             .get(&direction.clone())
                             .unwrap()
                             .to_owned(),
-                        theme
-                            .gradient
-                            .get(&direction.clone())
-                            .unwrap()
-                            .to_owned(),
+                        theme.gradient.get(&direction.clone()).unwrap().to_owned(),
                         match stats.get(&"speed".to_owned()).unwrap() {
                             NetCollectorStat::Vec(v) => {
                                 v.iter().map(|u| u.to_owned() as i32).collect()
@@ -478,24 +511,31 @@ impl NetBox {
                 );
             }
 
-            out.push_str(
-                format!(
-                    "{}{}",
-                    mv::to(
-                        if direction == "download".to_owned() {
-                            y
-                        } else {
-                            y + self
-                                .get_graph_height_index("download".to_owned())
-                                .unwrap_or(0)
-                        },
-                        x as u32
-                    ),
-                    graphs
-                        .net
-                        .get_mut(&direction.to_owned())
-                        .unwrap()
-                        .call(
+            if !CONFIG.net_multi {
+                graphs.net.get_mut(&direction.to_owned()).unwrap().place(
+                    x,
+                    if direction == "download".to_owned() {
+                        y
+                    } else {
+                        y + self
+                            .get_graph_height_index("download".to_owned())
+                            .unwrap_or(0)
+                    },
+                );
+                out.push_str(
+                    format!(
+                        "{}{}",
+                        mv::to(
+                            if direction == "download".to_owned() {
+                                y
+                            } else {
+                                y + self
+                                    .get_graph_height_index("download".to_owned())
+                                    .unwrap_or(0)
+                            },
+                            x as u32
+                        ),
+                        graphs.net.get_mut(&direction.to_owned()).unwrap().call(
                             if match stats.get(&"redraw".to_owned()).unwrap() {
                                 NetCollectorStat::Bool(b) => b.to_owned(),
                                 NetCollectorStat::I32(i) => i.to_owned() > 0,
@@ -515,9 +555,10 @@ impl NetBox {
                             },
                             term
                         ),
-                )
-                .as_str(),
-            );
+                    )
+                    .as_str(),
+                );
+            }
 
             out.push_str(
                 format!(
@@ -526,7 +567,7 @@ impl NetBox {
                     theme.colors.main_fg,
                     self.symbols[&direction],
                     strings[&"byte_ps".to_owned()],
-                    if bw < 20 {
+                    if bw < 20 || CONFIG.net_packets {
                         "".to_owned()
                     } else {
                         format!(
@@ -581,38 +622,52 @@ impl NetBox {
         }
 
         out.push_str(
-            format!(
-                "{}{}{}{}",
-                mv::to(y, x),
-                theme.colors.graph_text.call(
-                    if CONFIG.net_sync {
-                        net.get_sync_string()
-                    } else {
-                        net.get_strings_inner_inner_index(
-                            nic_name.clone(),
-                            "download".to_owned(),
-                            "graph_top".to_owned(),
-                        )
-                    },
-                    term
-                ),
-                mv::to(u32::try_from(y as i32 + h as i32 - 1).unwrap_or(0), x),
-                theme.colors.graph_text.call(
-                    if CONFIG.net_sync {
-                        net.get_sync_string()
-                    } else {
-                        net.get_strings_inner_inner_index(
-                            nic_name.clone(),
-                            "upload".to_owned(),
-                            "graph_top".to_owned(),
-                        )
-                    },
-                    term
-                ),
-            )
-            .as_str(),
+            self.draw_details(theme, term, net, nic_name.clone(), x, y, w, h)
+                .as_str(),
         );
 
+        if pw > 0 {
+            out.push_str(
+                self.draw_proto(theme, term, graphs, net, x + gw + 1, y, pw - 1, h)
+                    .as_str(),
+            );
+        }
+
+        if !CONFIG.net_multi {
+            out.push_str(
+                format!(
+                    "{}{}{}{}",
+                    mv::to(y, x),
+                    theme.colors.graph_text.call(
+                        if CONFIG.net_sync {
+                            net.get_sync_string()
+                        } else {
+                            net.get_strings_inner_inner_index(
+                                nic_name.clone(),
+                                "download".to_owned(),
+                                "graph_top".to_owned(),
+                            )
+                        },
+                        term
+                    ),
+                    mv::to(u32::try_from(y as i32 + h as i32 - 1).unwrap_or(0), x),
+                    theme.colors.graph_text.call(
+                        if CONFIG.net_sync {
+                            net.get_sync_string()
+                        } else {
+                            net.get_strings_inner_inner_index(
+                                nic_name.clone(),
+                                "upload".to_owned(),
+                                "graph_top".to_owned(),
+                            )
+                        },
+                        term
+                    ),
+                )
+                .as_str(),
+            );
+        }
+
         draw.buffer(
             self.get_buffer(),
             vec![format!("{}{}{}", out_misc.clone(), out, term.get_fg())],
@@ -629,6 +684,347 @@ impl NetBox {
         self.set_parent_resized(false);
     }
 
+    /// Draw a compact download/upload graph pair for every interface in net.multi_nics
+    pub fn draw_multi(
+        &mut self,
+        theme: &Theme,
+        term: &Term,
+        CONFIG: &Config,
+        graphs: &mut Graphs,
+        net: &mut NetCollector,
+        x: u32,
+        y: u32,
+        w: u32,
+        h: u32,
+    ) -> String {
+        let mut out: String = String::default();
+        let main_nic: String = net.nic.clone().unwrap().name().to_owned();
+        let nics: Vec<String> = net
+            .get_multi_nics()
+            .iter()
+            .filter(|n| net.get_stats_index(n.to_owned().to_owned()).is_some())
+            .map(|n| n.to_owned())
+            .collect::<Vec<String>>();
+
+        // * Every interface needs at least one row per direction
+        let rows: usize = if nics.len() > (h / 2) as usize {
+            (h / 2) as usize
+        } else {
+            nics.len()
+        };
+        if rows == 0 || w == 0 {
+            return out;
+        }
+        let region_h: u32 = h / rows as u32;
+
+        for (i, name) in nics[..rows].iter().enumerate() {
+            let ry: u32 = y + i as u32 * region_h;
+            let rh: u32 = if i == rows - 1 {
+                h - i as u32 * region_h
+            } else {
+                region_h
+            };
+            let mut label: String = String::default();
+
+            for direction in ["download", "upload"]
+                .iter()
+                .map(|s| s.to_owned().to_owned())
+                .collect::<Vec<String>>()
+            {
+                let graph_key: String = format!("{}_{}", name, direction);
+                let gh: u32 = if direction == "download".to_owned() {
+                    (rh + 1) / 2
+                } else {
+                    rh / 2
+                };
+                let gy: u32 = if direction == "download".to_owned() {
+                    ry
+                } else {
+                    ry + (rh + 1) / 2
+                };
+                let strings = net
+                    .get_strings_inner_index(name.clone(), direction.clone())
+                    .unwrap_or(HashMap::<String, String>::new());
+                let mut stats = match net.get_stats_inner_index(name.clone(), direction.clone()) {
+                    Some(s) => s,
+                    None => continue,
+                };
+                let speed: Vec<i32> = match stats.get(&"speed".to_owned()) {
+                    Some(NetCollectorStat::Vec(v)) => {
+                        v.iter().map(|u| u.to_owned() as i32).collect()
+                    }
+                    _ => vec![],
+                };
+                let redraw: bool = self.get_redraw()
+                    || self.get_parent().get_resized()
+                    || !graphs.net.contains_key(&graph_key)
+                    || match stats.get(&"redraw".to_owned()) {
+                        Some(NetCollectorStat::Bool(b)) => b.to_owned(),
+                        _ => false,
+                    };
+
+                if redraw {
+                    graphs.net.insert(
+                        graph_key.clone(),
+                        Graph::new_with_vec::<Color>(
+                            w,
+                            gh,
+                            theme.gradient.get(&direction.clone()).unwrap().to_owned(),
+                            speed.clone(),
+                            term,
+                            direction != "download".to_owned(),
+                            if CONFIG.net_sync {
+                                net.get_sync_top()
+                            } else {
+                                match stats.get(&"graph_top".to_owned()) {
+                                    Some(NetCollectorStat::I32(i)) => i.to_owned(),
+                                    Some(NetCollectorStat::U64(u)) => u.to_owned() as i32,
+                                    _ => 0,
+                                }
+                            },
+                            0,
+                            if CONFIG.net_color_fixed {
+                                net.get_net_min_index(direction.clone())
+                            } else {
+                                None
+                            },
+                        ),
+                    );
+                }
+
+                graphs.net.get_mut(&graph_key).unwrap().place(x, gy);
+                out.push_str(
+                    format!(
+                        "{}{}",
+                        mv::to(gy, x),
+                        graphs.net.get_mut(&graph_key).unwrap().call(
+                            if redraw {
+                                None
+                            } else {
+                                Some(speed.last().unwrap_or(&0).to_owned())
+                            },
+                            term
+                        ),
+                    )
+                    .as_str(),
+                );
+
+                label.push_str(
+                    format!(
+                        " {}{}",
+                        self.symbols[&direction],
+                        strings
+                            .get(&"byte_ps".to_owned())
+                            .unwrap_or(&String::default()),
+                    )
+                    .as_str(),
+                );
+
+                // * The main interface is reset by the sub box loop in draw_fg
+                if name.clone() != main_nic {
+                    stats.insert("redraw".to_owned(), NetCollectorStat::Bool(false));
+                    net.set_stats_inner_index(name.clone(), direction.clone(), stats.clone());
+                }
+            }
+
+            out.push_str(
+                format!(
+                    "{}{}{}{}{}{}",
+                    mv::to(ry, x),
+                    fx::b,
+                    theme
+                        .colors
+                        .title
+                        .call(name.chars().take(10).collect::<String>(), term),
+                    fx::ub,
+                    theme.colors.graph_text.call(label, term),
+                    term.get_fg(),
+                )
+                .as_str(),
+            );
+        }
+
+        out
+    }
+
+    /// Draw addresses and link speed in the top border and mac, mtu and error/drop counts in the bottom border
+    pub fn draw_details(
+        &self,
+        theme: &Theme,
+        term: &Term,
+        net: &NetCollector,
+        nic_name: String,
+        x: u32,
+        y: u32,
+        w: u32,
+        h: u32,
+    ) -> String {
+        let mut out: String = String::default();
+        let info: NicInfo = match net.get_nic_info_index(nic_name.clone()) {
+            Some(i) => i,
+            None => return out,
+        };
+        let title = |text: String, width: usize| -> String {
+            if width < 3 {
+                return String::default();
+            }
+            let text: String = text.chars().take(width - 2).collect();
+            format!(
+                "{}{}{}{}{}{}{}",
+                theme.colors.net_box,
+                symbol::title_left(),
+                theme.colors.title,
+                text,
+                theme.colors.net_box,
+                symbol::title_right(),
+                symbol::h_line().repeat(width - 2 - text.chars().count()),
+            )
+        };
+
+        // * Space between the "net" title and the buttons drawn by draw_fg
+        let top_w: usize =
+            u32::try_from(w as i32 - 36 - nic_name.len().min(10) as i32).unwrap_or(0) as usize;
+        let mut top: Vec<String> = Vec::<String>::new();
+        if info.ipv4.len() > 0 {
+            top.push(if info.ipv4.len() > 1 {
+                format!("{} +{}", info.ipv4[0], info.ipv4.len() - 1)
+            } else {
+                info.ipv4[0].clone()
+            });
+        }
+        if info.ipv6.len() > 0 {
+            top.push(info.ipv6[0].clone());
+        }
+        if info.speed > 0 {
+            top.push(info.speed_string());
+        }
+        if top.len() > 0 {
+            out.push_str(
+                format!("{}{}", mv::to(y - 1, x + 6), title(top.join(" "), top_w)).as_str(),
+            );
+        }
+
+        let mut bottom: String = String::default();
+        let mut bottom_w: usize = u32::try_from(w as i32 - 2).unwrap_or(0) as usize;
+        for text in vec![
+            format!("{} mtu {}", info.mac, info.mtu),
+            match net.get_netstat().get_dev_index(nic_name.clone()) {
+                Some(dev) => format!(
+                    "rx err {} drop {} tx err {} drop {}",
+                    dev.get(&"rx_errs".to_owned()).unwrap_or(&0),
+                    dev.get(&"rx_drop".to_owned()).unwrap_or(&0),
+                    dev.get(&"tx_errs".to_owned()).unwrap_or(&0),
+                    dev.get(&"tx_drop".to_owned()).unwrap_or(&0),
+                ),
+                None => String::default(),
+            },
+        ] {
+            if text.len() == 0 || text.len() + 2 > bottom_w {
+                continue;
+            }
+            bottom.push_str(title(text.clone(), text.len() + 2).as_str());
+            bottom_w -= text.len() + 2;
+        }
+        out.push_str(
+            format!(
+                "{}{}{}{}",
+                mv::to(y + h, x + 1),
+                bottom,
+                theme.colors.net_box,
+                symbol::h_line().repeat(bottom_w),
+            )
+            .as_str(),
+        );
+
+        format!("{}{}", out, term.get_fg())
+    }
+
+    /// Draw the tcp retransmit and interface error/drop rate graphs stacked in a column
+    pub fn draw_proto(
+        &mut self,
+        theme: &Theme,
+        term: &Term,
+        graphs: &mut Graphs,
+        net: &NetCollector,
+        x: u32,
+        y: u32,
+        w: u32,
+        h: u32,
+    ) -> String {
+        let mut out: String = String::default();
+        let netstat: NetStat = net.get_netstat();
+        let series: [(&str, &str); 3] = [
+            ("retrans", "Retrans"),
+            ("errors", "Errors"),
+            ("drops", "Drops"),
+        ];
+        if w == 0 || h < series.len() as u32 {
+            return out;
+        }
+        let region_h: u32 = h / series.len() as u32;
+
+        for (i, (name, title)) in series.iter().enumerate() {
+            let ry: u32 = y + i as u32 * region_h;
+            let rh: u32 = if i == series.len() - 1 {
+                h - i as u32 * region_h
+            } else {
+                region_h
+            };
+            let rates: Vec<i32> = netstat
+                .get_rates_index(name.to_owned().to_owned())
+                .unwrap_or(Vec::<u64>::new())
+                .iter()
+                .map(|r| r.to_owned() as i32)
+                .collect();
+            let current: i32 = rates.last().unwrap_or(&0).to_owned();
+
+            // * Rebuilt from the full history every time since the scale follows the highest rate shown
+            if rh > 1 {
+                let graph_key: String = format!("proto_{}", name);
+                let max: i32 = rates.iter().max().unwrap_or(&0).to_owned();
+                graphs.net.insert(
+                    graph_key.clone(),
+                    Graph::new_with_vec::<Color>(
+                        w,
+                        rh - 1,
+                        theme.gradient.get(&"used".to_owned()).unwrap().to_owned(),
+                        rates.clone(),
+                        term,
+                        false,
+                        if max < 10 { 10 } else { max },
+                        0,
+                        None,
+                    ),
+                );
+                out.push_str(
+                    format!(
+                        "{}{}",
+                        mv::to(ry + 1, x),
+                        graphs.net.get_mut(&graph_key).unwrap().call(None, term),
+                    )
+                    .as_str(),
+                );
+            }
+
+            out.push_str(
+                format!(
+                    "{}{}{}{}{}{:>width$}{}",
+                    mv::to(ry, x),
+                    fx::b,
+                    theme.colors.title.call(title.to_owned().to_owned(), term),
+                    fx::ub,
+                    theme.colors.graph_text,
+                    format!("{}/s", current),
+                    term.get_fg(),
+                    width = u32::try_from(w as i32 - title.len() as i32).unwrap_or(0) as usize,
+                )
+                .as_str(),
+            );
+        }
+
+        out
+    }
+
     pub fn get_parent(&self) -> BrshtopBox {
         self.parent.clone()
     }
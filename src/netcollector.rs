@@ -2,6 +2,7 @@ use {
     crate::{
         collector::{Collector, Collectors},
         config::Config,
+        count_humanizer,
         draw::Draw,
         error::errlog,
         floating_humanizer,
@@ -9,12 +10,13 @@ use {
         key::Key,
         menu::Menu,
         netbox::NetBox,
+        netstat::{NetStat, NicInfo},
         term::Term,
         theme::Theme,
         units_to_bytes,
     },
     futures::{future, stream::StreamExt},
-    heim::net::{io_counters, nic, IoCounters, Nic},
+    heim::net::{io_counters, nic, Address, IoCounters, Nic},
     std::{
         collections::HashMap,
         fmt,
@@ -59,6 +61,7 @@ pub struct NetCollector {
     nic_i: i32,
     pub nic: Option<Arc<Nic>>,
     pub new_nic: Option<Arc<Nic>>,
+    multi_nics: Vec<String>,
     nic_error: bool,
     reset: bool,
     graph_raise: HashMap<String, i32>,
@@ -71,6 +74,8 @@ pub struct NetCollector {
     auto_min: bool,
     sync_top: i32,
     sync_string: String,
+    netstat: NetStat,
+    nic_info: HashMap<String, NicInfo>,
 }
 impl NetCollector {
     pub fn new(netbox: &NetBox, CONFIG: &Config) -> Self {
@@ -82,6 +87,7 @@ impl NetCollector {
             nic_i: 0,
             nic: None,
             new_nic: None,
+            multi_nics: Vec::<String>::new(),
             nic_error: false,
             reset: false,
             graph_raise: [("download", 5), ("upload", 5)]
@@ -103,6 +109,8 @@ impl NetCollector {
             auto_min: CONFIG.net_auto,
             sync_top: 0,
             sync_string: String::default(),
+            netstat: NetStat::new(),
+            nic_info: HashMap::<String, NicInfo>::new(),
         }
     }
 
@@ -165,6 +173,44 @@ impl NetCollector {
         self.nic = Some(self.nics[self.nic_i as usize].clone());
     }
 
+    /// Get the interfaces shown together when net_multi is set, in the order given by net_interfaces
+    pub fn find_multi_nics(&self, CONFIG: &Config) -> Vec<String> {
+        let is_up = |name: &String| -> bool {
+            match self.up_stat.get(name) {
+                Some(n) => n.is_up(),
+                None => false,
+            }
+        };
+
+        if CONFIG.net_interfaces.trim().len() == 0 {
+            let mut up: Vec<String> = self
+                .up_stat
+                .keys()
+                .filter(|n| n.as_str() != "lo" && is_up(n))
+                .map(|n| n.to_owned())
+                .collect::<Vec<String>>();
+            up.sort();
+            up.insert(0, "all".to_owned());
+            return up;
+        }
+
+        let mut pinned: Vec<String> = Vec::<String>::new();
+        for name in CONFIG
+            .net_interfaces
+            .split(',')
+            .map(|s| s.trim().to_owned())
+            .collect::<Vec<String>>()
+        {
+            if name.len() == 0 || pinned.contains(&name) {
+                continue;
+            }
+            if name == "all".to_owned() || is_up(&name) {
+                pinned.push(name);
+            }
+        }
+        pinned
+    }
+
     pub fn switch(&mut self, key: String, collector: &mut Collector) {
         if self.nics.len() < 2 {
             return;
@@ -189,12 +235,20 @@ impl NetCollector {
     }
 
     pub fn collect(&mut self, CONFIG: &Config, netbox: &mut NetBox) {
-        let mut speed: i32 = 0;
-        let mut stat: HashMap<String, NetCollectorStat> =
-            HashMap::<String, NetCollectorStat>::new();
+        let mut addresses: HashMap<String, (Vec<String>, Vec<String>)> =
+            HashMap::<String, (Vec<String>, Vec<String>)>::new();
         let up_stat_stream = nic();
         up_stat_stream.for_each(|o| match o {
             Ok(val) => {
+                // * heim gives one entry per address, so collect them before the entry is replaced
+                let nic_addresses = addresses
+                    .entry(val.name().to_owned())
+                    .or_insert((Vec::<String>::new(), Vec::<String>::new()));
+                match val.address() {
+                    Address::Inet(a) => nic_addresses.0.push(a.ip().to_string()),
+                    Address::Inet6(a) => nic_addresses.1.push(a.ip().to_string()),
+                    _ => (),
+                }
                 self.up_stat.insert(val.name().to_owned(), Arc::new(val));
                 future::ready(())
             }
@@ -244,21 +298,196 @@ impl NetCollector {
             }
         });
 
-        let mut io_all: &IoCounters = match io_all_hash.get(&self.nic.clone().unwrap().name().to_owned()) {
-            Some(i) => i,
-            None => return,
-        };
+        let io_all: &IoCounters =
+            match io_all_hash.get(&self.nic.clone().unwrap().name().to_owned()) {
+                Some(i) => i,
+                None => return,
+            };
+        let nic_name: String = self.nic.clone().unwrap().name().to_owned();
+
+        self.nic_info = HashMap::<String, NicInfo>::new();
+        for (name, (ipv4, ipv6)) in addresses {
+            let mut info: NicInfo = NicInfo::from_sysfs(name.as_str());
+            info.ipv4 = ipv4;
+            info.ipv6 = ipv6;
+            self.nic_info.insert(name, info);
+        }
 
-        if !self
-            .stats
-            .contains_key(&self.nic.clone().unwrap().name().to_owned())
-        {
+        self.collect_nic(
+            nic_name.clone(),
+            NetCollector::counter(io_all, true, CONFIG.net_packets),
+            NetCollector::counter(io_all, false, CONFIG.net_packets),
+            CONFIG,
+            netbox,
+        );
+
+        if CONFIG.net_multi {
+            self.multi_nics = self.find_multi_nics(CONFIG);
+            for name in self.multi_nics.clone() {
+                if name == nic_name {
+                    continue;
+                }
+                let (bytes_recv, bytes_sent): (u64, u64) = if name == "all".to_owned() {
+                    io_all_hash
+                        .iter()
+                        .filter(|(n, _)| {
+                            n.as_str() != "lo"
+                                && match self.up_stat.get(n.as_str()) {
+                                    Some(u) => u.is_up(),
+                                    None => false,
+                                }
+                        })
+                        .fold((0, 0), |(r, s), (_, io)| {
+                            (
+                                r + NetCollector::counter(io, true, CONFIG.net_packets),
+                                s + NetCollector::counter(io, false, CONFIG.net_packets),
+                            )
+                        })
+                } else {
+                    match io_all_hash.get(&name) {
+                        Some(io) => (
+                            NetCollector::counter(io, true, CONFIG.net_packets),
+                            NetCollector::counter(io, false, CONFIG.net_packets),
+                        ),
+                        None => continue,
+                    }
+                };
+                self.collect_nic(name, bytes_recv, bytes_sent, CONFIG, netbox);
+            }
+        }
+
+        self.timestamp = SystemTime::now();
+
+        if CONFIG.net_sync {
+            let download_top = self
+                .stats
+                .get(&nic_name)
+                .unwrap()
+                .get(&"download".to_owned())
+                .unwrap()
+                .get(&"graph_top".to_owned())
+                .unwrap();
+            let upload_top = self
+                .stats
+                .get(&nic_name)
+                .unwrap()
+                .get(&"upload".to_owned())
+                .unwrap()
+                .get(&"graph_top".to_owned())
+                .unwrap();
+
+            let dtu: i32 = match download_top {
+                NetCollectorStat::I32(i) => i.to_owned(),
+                NetCollectorStat::U64(u) => u.to_owned() as i32,
+                NetCollectorStat::String(s) => s.to_owned().parse::<i32>().unwrap_or(0),
+                _ => 0,
+            };
+            let dut: i32 = match upload_top {
+                NetCollectorStat::I32(i) => i.to_owned(),
+                NetCollectorStat::U64(u) => u.to_owned() as i32,
+                NetCollectorStat::String(s) => s.to_owned().parse::<i32>().unwrap_or(0),
+                _ => 0,
+            };
+
+            let c_max: i32 = if dtu > dut { dtu } else { dut };
+
+            if c_max != self.sync_top {
+                self.sync_top = c_max;
+                self.sync_string = NetCollector::rate_string(
+                    self.sync_top as f64,
+                    false,
+                    false,
+                    0,
+                    false,
+                    CONFIG.net_packets,
+                );
+                netbox.set_redraw(true);
+            }
+        }
+
+        if CONFIG.net_proto {
+            self.netstat.collect(
+                nic_name.clone(),
+                (netbox.get_parent().get_width() * 2) as usize,
+            );
+        } else {
+            self.netstat.collect_dev();
+        }
+    }
+
+    /// Byte counter of an interface, or its packet counter when net_packets is set
+    fn counter(io: &IoCounters, recv: bool, packets: bool) -> u64 {
+        match (recv, packets) {
+            (true, false) => io.bytes_recv().value,
+            (false, false) => io.bytes_sent().value,
+            (true, true) => io.packets_recv(),
+            (false, true) => io.packets_sent(),
+        }
+    }
+
+    /// Same as floating_humanizer for byte counters, packet counters are shown as plain counts
+    fn rate_string(
+        value: f64,
+        bit: bool,
+        per_second: bool,
+        start: usize,
+        short: bool,
+        packets: bool,
+    ) -> String {
+        if !packets {
+            return floating_humanizer(value, bit, per_second, start, short);
+        }
+        let count: String = count_humanizer(value as u64);
+        if short {
+            count
+        } else if per_second {
+            count + " pkt/s"
+        } else {
+            count + " pkts"
+        }
+    }
+
+    /// Throw away all counters, needed when switching between bytes and packets since the totals are not comparable
+    pub fn reset_stats(&mut self) {
+        self.stats = HashMap::<String, HashMap<String, HashMap<String, NetCollectorStat>>>::new();
+        self.strings = HashMap::<String, HashMap<String, HashMap<String, String>>>::new();
+        self.net_min = [("download", -1), ("upload", -1)]
+            .iter()
+            .map(|(s, i)| (s.to_owned().to_owned(), i.to_owned()))
+            .collect::<HashMap<String, i32>>();
+        self.sync_top = 0;
+    }
+
+    /// Refresh the tcp states and socket table, socket owners are only looked up on request since it walks every /proc/[pid]/fd
+    pub fn collect_sockets(&mut self, resolve: bool) {
+        self.netstat.collect_sockets(resolve);
+    }
+
+    /// Update the totals, speeds and strings of a single interface, "all" being the sum of every up interface
+    fn collect_nic(
+        &mut self,
+        nic_name: String,
+        bytes_recv: u64,
+        bytes_sent: u64,
+        CONFIG: &Config,
+        netbox: &mut NetBox,
+    ) {
+        let mut speed: i32 = 0;
+        let mut stat: HashMap<String, NetCollectorStat> =
+            HashMap::<String, NetCollectorStat>::new();
+        // * Packet rates have no configured or link speed ceiling so they are always auto scaled
+        let ceiling: Option<i32> = match self.nic_info.get(&nic_name) {
+            Some(i) if !CONFIG.net_packets => i.ceiling(),
+            _ => None,
+        };
+        let auto_min: bool = self.auto_min || CONFIG.net_packets;
+        if !self.stats.contains_key(&nic_name) {
             self.stats.insert(
-                self.nic.clone().unwrap().name().to_owned(),
+                nic_name.clone(),
                 HashMap::<String, HashMap<String, NetCollectorStat>>::new(),
             );
             self.strings.insert(
-                self.nic.clone().unwrap().name().to_owned(),
+                nic_name.clone(),
                 vec![
                     ("download", HashMap::<String, String>::new()),
                     ("upload", HashMap::<String, String>::new()),
@@ -267,40 +496,40 @@ impl NetCollector {
                 .map(|(s, h)| (s.to_owned().to_owned(), h.clone()))
                 .collect::<HashMap<String, HashMap<String, String>>>(),
             );
-            for (direction, value) in vec![
-                ("download", io_all.bytes_recv().value),
-                ("upload", io_all.bytes_sent().value),
-            ]
-            .iter()
-            .map(|(s, b)| (s.to_owned().to_owned(), b.clone()))
-            .collect::<HashMap<String, u64>>()
+            for (direction, value) in vec![("download", bytes_recv), ("upload", bytes_sent)]
+                .iter()
+                .map(|(s, b)| (s.to_owned().to_owned(), b.clone()))
+                .collect::<HashMap<String, u64>>()
             {
-                self.stats
-                    .get_mut(&self.nic.clone().unwrap().name().to_owned())
-                    .unwrap()
-                    .insert(
-                        direction,
-                        vec![
-                            ("total", NetCollectorStat::U64(value)),
-                            ("last", NetCollectorStat::U64(value)),
-                            ("top", NetCollectorStat::U64(0)),
-                            ("graph_top", NetCollectorStat::U64(0)),
-                            ("offset", NetCollectorStat::U64(0)),
-                            ("speed", NetCollectorStat::Vec(Vec::<u64>::new())),
-                            ("redraw", NetCollectorStat::Bool(true)),
-                            ("graph_raise", NetCollectorStat::U64(0)),
-                            ("graph_lower", NetCollectorStat::U64(7)),
-                        ]
-                        .iter()
-                        .map(|(s, n)| (s.to_owned().to_owned(), n.clone()))
-                        .collect::<HashMap<String, NetCollectorStat>>(),
-                    );
+                // * Interfaces added after the first start from the current minimum graph top
+                let min_top: i32 = match (ceiling, self.net_min.get(&direction)) {
+                    (Some(c), _) => c,
+                    (None, Some(m)) if m.to_owned() > 0 => m.to_owned(),
+                    _ => 0,
+                };
+                self.stats.get_mut(&nic_name).unwrap().insert(
+                    direction,
+                    vec![
+                        ("total", NetCollectorStat::U64(value)),
+                        ("last", NetCollectorStat::U64(value)),
+                        ("top", NetCollectorStat::U64(0)),
+                        ("graph_top", NetCollectorStat::I32(min_top)),
+                        ("offset", NetCollectorStat::U64(0)),
+                        ("speed", NetCollectorStat::Vec(Vec::<u64>::new())),
+                        ("redraw", NetCollectorStat::Bool(true)),
+                        ("graph_raise", NetCollectorStat::U64(0)),
+                        ("graph_lower", NetCollectorStat::U64(7)),
+                    ]
+                    .iter()
+                    .map(|(s, n)| (s.to_owned().to_owned(), n.clone()))
+                    .collect::<HashMap<String, NetCollectorStat>>(),
+                );
                 for v in vec!["total", "byte_ps", "bit_ps", "top", "graph_top"]
                     .iter()
                     .map(|s| s.to_owned().to_owned())
                     .collect::<Vec<String>>()
                 {
-                    match self.strings.get_mut(&self.nic.clone().unwrap().name().to_owned()) {
+                    match self.strings.get_mut(&nic_name) {
                         Some(h) => {
                             h.insert(v, HashMap::<String, String>::new());
                             ()
@@ -311,24 +540,18 @@ impl NetCollector {
             }
         }
 
-        match self.stats.get_mut(&self.nic.clone().unwrap().name().to_owned()) {
+        match self.stats.get_mut(&nic_name) {
             Some(h) => {
                 match h.get_mut(&"download".to_owned()) {
                     Some(hash) => {
-                        hash.insert(
-                            "total".to_owned(),
-                            NetCollectorStat::U64(io_all.bytes_recv().value),
-                        );
+                        hash.insert("total".to_owned(), NetCollectorStat::U64(bytes_recv));
                         ()
                     }
                     None => (),
                 }
                 match h.get_mut(&"upload".to_owned()) {
                     Some(hash) => {
-                        hash.insert(
-                            "total".to_owned(),
-                            NetCollectorStat::U64(io_all.bytes_sent().value),
-                        );
+                        hash.insert("total".to_owned(), NetCollectorStat::U64(bytes_sent));
                         ()
                     }
                     None => (),
@@ -341,7 +564,7 @@ impl NetCollector {
                     stat = h.get(&direction).unwrap().clone();
                     let mut strings: HashMap<String, NetCollectorStat> = self
                         .strings
-                        .get(&self.nic.clone().unwrap().name().to_owned())
+                        .get(&nic_name)
                         .unwrap()
                         .get(&direction)
                         .unwrap()
@@ -371,36 +594,45 @@ impl NetCollector {
                             0
                         }
                     };
-                    speed_vec.push(
-                        (total - last)
-                            / self
-                                .timestamp
-                                .elapsed()
-                                .unwrap_or(Duration::from_secs(1))
-                                .as_secs(),
-                    );
+                    // * Totals shrink when an interface goes down or a counter resets, and a fast
+                    // * tick can be shorter than a second
+                    let elapsed: f64 = self
+                        .timestamp
+                        .elapsed()
+                        .unwrap_or(Duration::from_secs(1))
+                        .as_secs_f64();
+                    speed_vec.push(if elapsed > 0.0 {
+                        (total.saturating_sub(last) as f64 / elapsed) as u64
+                    } else {
+                        0
+                    });
                     last = total;
-                    speed = speed_vec[speed_vec.len() - 2] as i32;
+                    speed = speed_vec[speed_vec.len() - 1] as i32;
 
                     if self.net_min.get(&direction).unwrap_or(&0).to_owned() == -1 {
+                        // * A known link speed is a better ceiling than the configured values
                         self.net_min.insert(
                             direction.clone(),
-                            units_to_bytes(match direction.as_str() {
-                                "download" => CONFIG.net_download.clone(),
-                                "upload" => CONFIG.net_upload.clone(),
-                                _ => "".to_owned(),
-                            }) as i32,
+                            match ceiling {
+                                Some(c) => c,
+                                None if CONFIG.net_packets => 10 << 10,
+                                None => units_to_bytes(match direction.as_str() {
+                                    "download" => CONFIG.net_download.clone(),
+                                    "upload" => CONFIG.net_upload.clone(),
+                                    _ => "".to_owned(),
+                                }) as i32,
+                            },
                         );
                         stat.insert(
                             "graph_top".to_owned(),
                             NetCollectorStat::I32(self.net_min.get(&direction).unwrap().to_owned()),
                         );
                         stat.insert("graph_lower".to_owned(), NetCollectorStat::I32(7));
-                        if !self.auto_min {
+                        if !auto_min {
                             stat.insert("redraw".to_owned(), NetCollectorStat::Bool(true));
                             strings.insert(
                                 "graph_top".to_owned(),
-                                NetCollectorStat::String(floating_humanizer(
+                                NetCollectorStat::String(NetCollector::rate_string(
                                     match stat.get(&"graph_top".to_owned()).unwrap() {
                                         NetCollectorStat::I32(i) => i.to_owned() as f64,
                                         NetCollectorStat::U64(u) => u.to_owned() as f64,
@@ -415,6 +647,7 @@ impl NetCollector {
                                     false,
                                     0,
                                     true,
+                                    CONFIG.net_packets,
                                 )),
                             );
                         }
@@ -455,32 +688,35 @@ impl NetCollector {
 
                     strings.insert(
                         "total".to_owned(),
-                        NetCollectorStat::String(floating_humanizer(
+                        NetCollectorStat::String(NetCollector::rate_string(
                             (total - stat_offset) as f64,
                             false,
                             false,
                             0,
                             false,
+                            CONFIG.net_packets,
                         )),
                     );
                     strings.insert(
                         "byte_ps".to_owned(),
-                        NetCollectorStat::String(floating_humanizer(
-                            speed_vec[speed_vec.len() - 2] as f64,
+                        NetCollectorStat::String(NetCollector::rate_string(
+                            speed_vec[speed_vec.len() - 1] as f64,
                             false,
                             true,
                             0,
                             false,
+                            CONFIG.net_packets,
                         )),
                     );
                     strings.insert(
                         "bit_ps".to_owned(),
-                        NetCollectorStat::String(floating_humanizer(
-                            speed_vec[speed_vec.len() - 2] as f64,
+                        NetCollectorStat::String(NetCollector::rate_string(
+                            speed_vec[speed_vec.len() - 1] as f64,
                             true,
                             true,
                             0,
                             false,
+                            CONFIG.net_packets,
                         )),
                     );
 
@@ -497,13 +733,18 @@ impl NetCollector {
                         top = speed;
                         strings.insert(
                             "top".to_owned(),
-                            NetCollectorStat::String(floating_humanizer(
-                                top as f64, true, true, 0, false,
+                            NetCollectorStat::String(NetCollector::rate_string(
+                                top as f64,
+                                true,
+                                true,
+                                0,
+                                false,
+                                CONFIG.net_packets,
                             )),
                         );
                     }
 
-                    if self.auto_min {
+                    if auto_min {
                         let mut graph_top: i32 = match stat.get(&"graph_top".to_owned()).unwrap() {
                             NetCollectorStat::I32(i) => i.to_owned(),
                             NetCollectorStat::U64(u) => u.to_owned() as i32,
@@ -563,12 +804,13 @@ impl NetCollector {
                             stat.insert("redraw".to_owned(), NetCollectorStat::Bool(true));
                             strings.insert(
                                 "graph_top".to_owned(),
-                                NetCollectorStat::String(floating_humanizer(
+                                NetCollectorStat::String(NetCollector::rate_string(
                                     graph_top as f64,
                                     false,
                                     false,
                                     0,
                                     true,
+                                    CONFIG.net_packets,
                                 )),
                             );
                         }
@@ -583,74 +825,28 @@ impl NetCollector {
                     stat.insert("total".to_owned(), NetCollectorStat::U64(total));
                     stat.insert("speed".to_owned(), NetCollectorStat::Vec(speed_vec.clone()));
 
-                    self.strings
-                        .get_mut(&self.nic.clone().unwrap().name().to_owned())
-                        .unwrap()
-                        .insert(
-                            direction.clone(),
-                            strings
-                                .clone()
-                                .iter()
-                                .map(|(s1, s2)| {
-                                    (
-                                        s1.clone(),
-                                        match s2 {
-                                            NetCollectorStat::String(s) => s.clone(),
-                                            _ => "".to_owned(),
-                                        },
-                                    )
-                                })
-                                .collect(),
-                        );
+                    self.strings.get_mut(&nic_name).unwrap().insert(
+                        direction.clone(),
+                        strings
+                            .clone()
+                            .iter()
+                            .map(|(s1, s2)| {
+                                (
+                                    s1.clone(),
+                                    match s2 {
+                                        NetCollectorStat::String(s) => s.clone(),
+                                        _ => "".to_owned(),
+                                    },
+                                )
+                            })
+                            .collect(),
+                    );
                     h.insert(direction.clone(), stat.clone());
                 }
-
-                self.timestamp = SystemTime::now();
-
-                if CONFIG.net_sync {
-                    let download_top = self
-                        .stats
-                        .get(&self.nic.clone().unwrap().name().to_owned())
-                        .unwrap()
-                        .get(&"download".to_owned())
-                        .unwrap()
-                        .get(&"graph_top".to_owned())
-                        .unwrap();
-                    let upload_top = self
-                        .stats
-                        .get(&self.nic.clone().unwrap().name().to_owned())
-                        .unwrap()
-                        .get(&"upload".to_owned())
-                        .unwrap()
-                        .get(&"graph_top".to_owned())
-                        .unwrap();
-
-                    let dtu: i32 = match download_top {
-                        NetCollectorStat::I32(i) => i.to_owned(),
-                        NetCollectorStat::U64(u) => u.to_owned() as i32,
-                        NetCollectorStat::String(s) => s.to_owned().parse::<i32>().unwrap_or(0),
-                        _ => 0,
-                    };
-                    let dut: i32 = match upload_top {
-                        NetCollectorStat::I32(i) => i.to_owned(),
-                        NetCollectorStat::U64(u) => u.to_owned() as i32,
-                        NetCollectorStat::String(s) => s.to_owned().parse::<i32>().unwrap_or(0),
-                        _ => 0,
-                    };
-
-                    let c_max: i32 = if dtu > dut { dtu } else { dut };
-
-                    if c_max != self.sync_top {
-                        self.sync_top = c_max;
-                        self.sync_string =
-                            floating_humanizer(self.sync_top as f64, false, false, 0, false);
-                        netbox.set_redraw(true);
-                    }
-                }
             }
             None => errlog(format!(
                 "Unable to access nic in self.stats (nic : {})",
-                self.nic.clone().unwrap().name()
+                nic_name
             )),
         }
     }
@@ -955,6 +1151,37 @@ impl NetCollector {
         self.sync_top = sync_top.clone();
     }
 
+    pub fn get_multi_nics(&self) -> Vec<String> {
+        self.multi_nics.clone()
+    }
+
+    pub fn set_multi_nics(&mut self, multi_nics: Vec<String>) {
+        self.multi_nics = multi_nics.clone();
+    }
+
+    pub fn get_netstat(&self) -> NetStat {
+        self.netstat.clone()
+    }
+
+    pub fn set_netstat(&mut self, netstat: NetStat) {
+        self.netstat = netstat.clone();
+    }
+
+    pub fn get_nic_info(&self) -> HashMap<String, NicInfo> {
+        self.nic_info.clone()
+    }
+
+    pub fn set_nic_info(&mut self, nic_info: HashMap<String, NicInfo>) {
+        self.nic_info = nic_info.clone();
+    }
+
+    pub fn get_nic_info_index(&self, index: String) -> Option<NicInfo> {
+        match self.nic_info.get(&index) {
+            Some(i) => Some(i.clone()),
+            None => None,
+        }
+    }
+
     pub fn get_sync_string(&self) -> String {
         self.sync_string.clone()
     }
@@ -0,0 +1,538 @@
+use {
+    crate::readfile,
+    std::{
+        collections::HashMap,
+        fs::{self, File},
+        net::{Ipv4Addr, Ipv6Addr},
+        time::{Duration, SystemTime},
+    },
+};
+
+/// Kernel tcp states as found in the "st" column of /proc/net/tcp{,6}
+pub const TCP_STATES: [(&str, &str); 12] = [
+    ("01", "ESTABLISHED"),
+    ("02", "SYN_SENT"),
+    ("03", "SYN_RECV"),
+    ("04", "FIN_WAIT1"),
+    ("05", "FIN_WAIT2"),
+    ("06", "TIME_WAIT"),
+    ("07", "CLOSE"),
+    ("08", "CLOSE_WAIT"),
+    ("09", "LAST_ACK"),
+    ("0A", "LISTEN"),
+    ("0B", "CLOSING"),
+    ("0C", "NEW_SYN_RECV"),
+];
+
+#[derive(Clone, Debug)]
+pub struct Socket {
+    pub proto: String,
+    pub local: String,
+    pub remote: String,
+    pub state: String,
+    pub inode: u64,
+    pub pid: u32,
+    pub name: String,
+}
+
+/// Per interface details that heim does not expose, addresses are filled in by NetCollector from heim
+#[derive(Clone, Debug)]
+pub struct NicInfo {
+    pub ipv4: Vec<String>,
+    pub ipv6: Vec<String>,
+    pub mac: String,
+    pub mtu: u32,
+    pub speed: u64,
+    pub duplex: String,
+}
+impl NicInfo {
+    /// Read mac, mtu, link speed in Mbit/s and duplex from /sys/class/net, speed is 0 when the driver does not report it
+    pub fn from_sysfs(nic: &str) -> Self {
+        let read = |attr: &str| -> String {
+            match File::open(format!("/sys/class/net/{}/{}", nic, attr)) {
+                Ok(f) => readfile(f).unwrap_or(String::default()).trim().to_owned(),
+                Err(_) => String::default(),
+            }
+        };
+        let speed: i64 = read("speed").parse::<i64>().unwrap_or(0);
+
+        NicInfo {
+            ipv4: Vec::<String>::new(),
+            ipv6: Vec::<String>::new(),
+            mac: read("address"),
+            mtu: read("mtu").parse::<u32>().unwrap_or(0),
+            speed: if speed > 0 { speed as u64 } else { 0 },
+            duplex: read("duplex"),
+        }
+    }
+
+    /// Link speed in bytes per second, usable as a graph ceiling
+    pub fn ceiling(&self) -> Option<i32> {
+        if self.speed == 0 {
+            return None;
+        }
+        let bytes: u64 = self.speed * 1_000_000 / 8;
+        Some(if bytes > i32::MAX as u64 {
+            i32::MAX
+        } else {
+            bytes as i32
+        })
+    }
+
+    /// Link speed as shown in the NetBox header, i.e "1G full"
+    pub fn speed_string(&self) -> String {
+        if self.speed == 0 {
+            return String::default();
+        }
+        let speed: String = if self.speed >= 1000 && self.speed % 1000 == 0 {
+            format!("{}G", self.speed / 1000)
+        } else {
+            format!("{}M", self.speed)
+        };
+        if self.duplex.len() > 0 && self.duplex != "unknown".to_owned() {
+            format!("{} {}", speed, self.duplex)
+        } else {
+            speed
+        }
+    }
+}
+
+/// Protocol level statistics read straight from /proc/net, tcp states and listening/udp sockets from tcp{,6} and udp{,6}, retransmits from snmp and interface errors and drops from dev
+#[derive(Clone)]
+pub struct NetStat {
+    tcp_states: HashMap<String, u64>,
+    sockets: Vec<Socket>,
+    snmp: HashMap<String, u64>,
+    dev: HashMap<String, HashMap<String, u64>>,
+    last: HashMap<String, u64>,
+    rates: HashMap<String, Vec<u64>>,
+    timestamp: SystemTime,
+}
+impl NetStat {
+    pub fn new() -> Self {
+        NetStat {
+            tcp_states: HashMap::<String, u64>::new(),
+            sockets: Vec::<Socket>::new(),
+            snmp: HashMap::<String, u64>::new(),
+            dev: HashMap::<String, HashMap<String, u64>>::new(),
+            last: HashMap::<String, u64>::new(),
+            rates: ["retrans", "errors", "drops"]
+                .iter()
+                .map(|s| (s.to_owned().to_owned(), Vec::<u64>::new()))
+                .collect::<HashMap<String, Vec<u64>>>(),
+            timestamp: SystemTime::now(),
+        }
+    }
+
+    /// Read only the dev counters, for the interface errors and drops when protocol stats are off
+    pub fn collect_dev(&mut self) {
+        self.dev = NetStat::parse_dev();
+    }
+
+    /// Read the snmp and dev counters and add a new per second sample for the graphs, errors and drops are taken from nic
+    pub fn collect(&mut self, nic: String, max_len: usize) {
+        self.snmp = NetStat::parse_snmp();
+        self.dev = NetStat::parse_dev();
+
+        let nic_counter = |dev: &HashMap<String, HashMap<String, u64>>, keys: [&str; 2]| -> u64 {
+            match dev.get(&nic) {
+                Some(d) => keys
+                    .iter()
+                    .map(|k| d.get(*k).unwrap_or(&0).to_owned())
+                    .sum(),
+                None => 0,
+            }
+        };
+        let current: Vec<(String, u64)> = vec![
+            (
+                "retrans".to_owned(),
+                self.snmp
+                    .get(&"Tcp.RetransSegs".to_owned())
+                    .unwrap_or(&0)
+                    .to_owned(),
+            ),
+            (
+                "errors".to_owned(),
+                nic_counter(&self.dev, ["rx_errs", "tx_errs"]),
+            ),
+            (
+                "drops".to_owned(),
+                nic_counter(&self.dev, ["rx_drop", "tx_drop"]),
+            ),
+        ];
+
+        let elapsed: f64 = match self
+            .timestamp
+            .elapsed()
+            .unwrap_or(Duration::from_secs(1))
+            .as_secs_f64()
+        {
+            s if s > 0.0 => s,
+            _ => 1.0,
+        };
+        for (name, value) in current {
+            let rate: u64 = match self.last.get(&name) {
+                Some(l) if value >= l.to_owned() => ((value - l) as f64 / elapsed).round() as u64,
+                _ => 0,
+            };
+            let rates = self.rates.entry(name.clone()).or_insert(Vec::<u64>::new());
+            rates.push(rate);
+            if rates.len() > max_len {
+                rates.remove(0);
+            }
+            self.last.insert(name, value);
+        }
+        self.timestamp = SystemTime::now();
+    }
+
+    /// Read tcp states and the listening and udp sockets, resolve also looks up the process owning each socket
+    pub fn collect_sockets(&mut self, resolve: bool) {
+        self.tcp_states = HashMap::<String, u64>::new();
+        self.sockets = Vec::<Socket>::new();
+
+        let owners: HashMap<u64, (u32, String)> = if resolve {
+            NetStat::socket_owners()
+        } else {
+            HashMap::<u64, (u32, String)>::new()
+        };
+        for (proto, path) in [
+            ("tcp", "/proc/net/tcp"),
+            ("tcp6", "/proc/net/tcp6"),
+            ("udp", "/proc/net/udp"),
+            ("udp6", "/proc/net/udp6"),
+        ]
+        .iter()
+        {
+            self.parse_sockets(proto.to_owned().to_owned(), path, &owners);
+        }
+    }
+
+    fn parse_sockets(&mut self, proto: String, path: &str, owners: &HashMap<u64, (u32, String)>) {
+        let content: String = match File::open(path) {
+            Ok(f) => match readfile(f) {
+                Some(s) => s,
+                None => return,
+            },
+            Err(_) => return,
+        };
+        let tcp: bool = proto.starts_with("tcp");
+
+        // * Fields are: sl local_address rem_address st tx_queue:rx_queue tr:tm->when retrnsmt uid timeout inode
+        for line in content.lines().skip(1) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 {
+                continue;
+            }
+            let state: String = if tcp {
+                match TCP_STATES.iter().find(|(hex, _)| *hex == fields[3]) {
+                    Some((_, s)) => s.to_owned().to_owned(),
+                    None => fields[3].to_owned(),
+                }
+            } else if fields[3] == "01" {
+                "CONNECTED".to_owned()
+            } else {
+                "UNCONN".to_owned()
+            };
+
+            if tcp {
+                let count = self.tcp_states.entry(state.clone()).or_insert(0);
+                *count += 1;
+                if state != "LISTEN".to_owned() {
+                    continue;
+                }
+            }
+
+            let inode: u64 = fields[9].parse::<u64>().unwrap_or(0);
+            let (pid, name) = match owners.get(&inode) {
+                Some((p, n)) => (p.to_owned(), n.clone()),
+                None => (0, String::default()),
+            };
+            self.sockets.push(Socket {
+                proto: proto.clone(),
+                local: NetStat::parse_address(fields[1]),
+                remote: NetStat::parse_address(fields[2]),
+                state,
+                inode,
+                pid,
+                name,
+            });
+        }
+    }
+
+    /// Convert a kernel "ADDRESS:PORT" hex pair, the address is printed as 32 bit words in host byte order
+    pub fn parse_address(hex: &str) -> String {
+        let mut split = hex.split(':');
+        let (addr, port) = match (split.next(), split.next()) {
+            (Some(a), Some(p)) => (a, u16::from_str_radix(p, 16).unwrap_or(0)),
+            _ => return hex.to_owned(),
+        };
+
+        let words: Vec<u32> = (0..addr.len() / 8)
+            .map(|i| u32::from_str_radix(&addr[i * 8..i * 8 + 8], 16).unwrap_or(0))
+            .collect();
+        let mut bytes: Vec<u8> = Vec::<u8>::new();
+        for word in words {
+            bytes.extend_from_slice(&word.to_ne_bytes());
+        }
+
+        match bytes.len() {
+            4 => format!(
+                "{}:{}",
+                Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3]),
+                port
+            ),
+            16 => {
+                let mut octets: [u8; 16] = [0; 16];
+                octets.copy_from_slice(&bytes);
+                format!("[{}]:{}", Ipv6Addr::from(octets), port)
+            }
+            _ => hex.to_owned(),
+        }
+    }
+
+    /// Map socket inodes to the pid and name of the process holding them, only sockets of processes we are allowed to inspect are found
+    pub fn socket_owners() -> HashMap<u64, (u32, String)> {
+        let mut out: HashMap<u64, (u32, String)> = HashMap::<u64, (u32, String)>::new();
+        let proc_dir = match fs::read_dir("/proc") {
+            Ok(d) => d,
+            Err(_) => return out,
+        };
+
+        for entry in proc_dir.filter_map(|e| e.ok()) {
+            let pid: u32 = match entry.file_name().to_string_lossy().parse::<u32>() {
+                Ok(p) => p,
+                Err(_) => continue,
+            };
+            let fds = match fs::read_dir(entry.path().join("fd")) {
+                Ok(f) => f,
+                Err(_) => continue,
+            };
+            let mut name: Option<String> = None;
+
+            for fd in fds.filter_map(|f| f.ok()) {
+                let link: String = match fs::read_link(fd.path()) {
+                    Ok(l) => l.to_string_lossy().to_string(),
+                    Err(_) => continue,
+                };
+                if !link.starts_with("socket:[") {
+                    continue;
+                }
+                let inode: u64 = match link["socket:[".len()..link.len() - 1].parse::<u64>() {
+                    Ok(i) => i,
+                    Err(_) => continue,
+                };
+                if name.is_none() {
+                    name = Some(match File::open(entry.path().join("comm")) {
+                        Ok(f) => readfile(f).unwrap_or(String::default()).trim().to_owned(),
+                        Err(_) => String::default(),
+                    });
+                }
+                out.insert(inode, (pid, name.clone().unwrap_or(String::default())));
+            }
+        }
+
+        out
+    }
+
+    /// Read /proc/net/snmp where every protocol has a header line followed by a value line, keys are stored as "Proto.Field"
+    pub fn parse_snmp() -> HashMap<String, u64> {
+        match File::open("/proc/net/snmp") {
+            Ok(f) => match readfile(f) {
+                Some(s) => NetStat::parse_snmp_str(&s),
+                None => HashMap::<String, u64>::new(),
+            },
+            Err(_) => HashMap::<String, u64>::new(),
+        }
+    }
+
+    /// Parse the contents of /proc/net/snmp
+    pub fn parse_snmp_str(content: &str) -> HashMap<String, u64> {
+        let mut out: HashMap<String, u64> = HashMap::<String, u64>::new();
+        let lines: Vec<&str> = content.lines().collect();
+        for pair in lines.chunks(2) {
+            if pair.len() < 2 {
+                continue;
+            }
+            let header: Vec<&str> = pair[0].split_whitespace().collect();
+            let values: Vec<&str> = pair[1].split_whitespace().collect();
+            if header.len() == 0 || header.len() != values.len() || header[0] != values[0] {
+                continue;
+            }
+            let proto: &str = header[0].trim_end_matches(':');
+            for (field, value) in header.iter().zip(values.iter()).skip(1) {
+                out.insert(
+                    format!("{}.{}", proto, field),
+                    value.parse::<u64>().unwrap_or(0),
+                );
+            }
+        }
+
+        out
+    }
+
+    /// Read the per interface counters of /proc/net/dev
+    pub fn parse_dev() -> HashMap<String, HashMap<String, u64>> {
+        match File::open("/proc/net/dev") {
+            Ok(f) => match readfile(f) {
+                Some(s) => NetStat::parse_dev_str(&s),
+                None => HashMap::<String, HashMap<String, u64>>::new(),
+            },
+            Err(_) => HashMap::<String, HashMap<String, u64>>::new(),
+        }
+    }
+
+    /// Parse the contents of /proc/net/dev
+    pub fn parse_dev_str(content: &str) -> HashMap<String, HashMap<String, u64>> {
+        let mut out: HashMap<String, HashMap<String, u64>> =
+            HashMap::<String, HashMap<String, u64>>::new();
+        let names: [&str; 16] = [
+            "rx_bytes",
+            "rx_packets",
+            "rx_errs",
+            "rx_drop",
+            "rx_fifo",
+            "rx_frame",
+            "rx_compressed",
+            "rx_multicast",
+            "tx_bytes",
+            "tx_packets",
+            "tx_errs",
+            "tx_drop",
+            "tx_fifo",
+            "tx_colls",
+            "tx_carrier",
+            "tx_compressed",
+        ];
+
+        // * The first two lines are headers
+        for line in content.lines().skip(2) {
+            let mut split = line.splitn(2, ':');
+            let (nic, counters) = match (split.next(), split.next()) {
+                (Some(n), Some(c)) => (n.trim().to_owned(), c),
+                _ => continue,
+            };
+            out.insert(
+                nic,
+                names
+                    .iter()
+                    .zip(counters.split_whitespace())
+                    .map(|(n, v)| (n.to_owned().to_owned(), v.parse::<u64>().unwrap_or(0)))
+                    .collect::<HashMap<String, u64>>(),
+            );
+        }
+
+        out
+    }
+
+    pub fn get_tcp_states(&self) -> HashMap<String, u64> {
+        self.tcp_states.clone()
+    }
+
+    pub fn set_tcp_states(&mut self, tcp_states: HashMap<String, u64>) {
+        self.tcp_states = tcp_states.clone()
+    }
+
+    pub fn get_sockets(&self) -> Vec<Socket> {
+        self.sockets.clone()
+    }
+
+    pub fn set_sockets(&mut self, sockets: Vec<Socket>) {
+        self.sockets = sockets.clone()
+    }
+
+    pub fn get_snmp(&self) -> HashMap<String, u64> {
+        self.snmp.clone()
+    }
+
+    pub fn set_snmp(&mut self, snmp: HashMap<String, u64>) {
+        self.snmp = snmp.clone()
+    }
+
+    pub fn get_dev(&self) -> HashMap<String, HashMap<String, u64>> {
+        self.dev.clone()
+    }
+
+    pub fn set_dev(&mut self, dev: HashMap<String, HashMap<String, u64>>) {
+        self.dev = dev.clone()
+    }
+
+    pub fn get_dev_index(&self, index: String) -> Option<HashMap<String, u64>> {
+        match self.dev.get(&index) {
+            Some(d) => Some(d.clone()),
+            None => None,
+        }
+    }
+
+    pub fn get_rates(&self) -> HashMap<String, Vec<u64>> {
+        self.rates.clone()
+    }
+
+    pub fn set_rates(&mut self, rates: HashMap<String, Vec<u64>>) {
+        self.rates = rates.clone()
+    }
+
+    pub fn get_rates_index(&self, index: String) -> Option<Vec<u64>> {
+        match self.rates.get(&index) {
+            Some(r) => Some(r.clone()),
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_addresses() {
+        let v4: String =
+            NetStat::parse_address(&format!("{:08X}:0050", u32::from_ne_bytes([127, 0, 0, 1])));
+        assert_eq!(v4, "127.0.0.1:80");
+
+        let mut v6: String = String::new();
+        for word in [0x2001_0db8u32.to_be(), 0, 0, 1u32.to_be()].iter() {
+            v6.push_str(&format!("{:08X}", word));
+        }
+        assert_eq!(NetStat::parse_address(&(v6 + ":01BB")), "[2001:db8::1]:443");
+
+        assert_eq!(NetStat::parse_address("garbage"), "garbage");
+        assert_eq!(NetStat::parse_address("0100:0016"), "0100:0016");
+    }
+
+    #[test]
+    fn parses_snmp() {
+        let snmp: HashMap<String, u64> = NetStat::parse_snmp_str(
+            "Ip: Forwarding DefaultTTL\n\
+             Ip: 1 64\n\
+             Tcp: RtoAlgorithm ActiveOpens RetransSegs\n\
+             Tcp: 1 42 7\n\
+             Udp: InDatagrams NoPorts\n\
+             Udp: 100\n",
+        );
+        assert_eq!(snmp.get("Ip.DefaultTTL"), Some(&64));
+        assert_eq!(snmp.get("Tcp.ActiveOpens"), Some(&42));
+        assert_eq!(snmp.get("Tcp.RetransSegs"), Some(&7));
+        // * Header and value lines of different length are skipped
+        assert_eq!(snmp.get("Udp.InDatagrams"), None);
+        assert_eq!(snmp.len(), 5);
+    }
+
+    #[test]
+    fn parses_dev() {
+        let dev: HashMap<String, HashMap<String, u64>> = NetStat::parse_dev_str(
+            "Inter-|   Receive                                                |  Transmit\n\
+             \x20face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed\n\
+             \x20   lo: 1000      10    0    0    0     0          0         0     1000      10    0    0    0     0       0          0\n\
+             \x20 eth0:2000      20    3    4    0     0          0         0     3000      30    5    6    0     0       0          0\n",
+        );
+        assert_eq!(dev.len(), 2);
+        assert_eq!(dev["lo"]["rx_bytes"], 1000);
+        assert_eq!(dev["eth0"]["rx_bytes"], 2000);
+        assert_eq!(dev["eth0"]["rx_errs"], 3);
+        assert_eq!(dev["eth0"]["rx_drop"], 4);
+        assert_eq!(dev["eth0"]["tx_bytes"], 3000);
+        assert_eq!(dev["eth0"]["tx_errs"], 5);
+        assert_eq!(dev["eth0"]["tx_drop"], 6);
+        assert_eq!(dev["eth0"].len(), 16);
+    }
+}
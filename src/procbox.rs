@@ -104,20 +104,30 @@ impl<'a> ProcBox {
             height_p = 80;
         }
 
-        self.parent.set_width((term.get_width() as f64 * width_p as f64 / 100.0).round() as u32);
-        self.parent.set_width((term.get_height() as f64 * height_p as f64 / 100.0).round() as u32);
-        if self.parent.get_height() + _b_cpu_h as u32 > term.get_height() as u32 {
-            self.parent.set_height(u32::try_from(term.get_height() as i32 - _b_cpu_h as i32).unwrap_or(0));
+        match self.parent.get_layout_rect() {
+            Some((x, y, w, h)) => {
+                self.parent.set_x(x);
+                self.parent.set_y(y);
+                self.parent.set_width(w);
+                self.parent.set_height(h);
+            }
+            None => {
+                self.parent.set_width((term.get_width() as f64 * width_p as f64 / 100.0).round() as u32);
+                self.parent.set_height((term.get_height() as f64 * height_p as f64 / 100.0).round() as u32);
+                if self.parent.get_height() + _b_cpu_h as u32 > term.get_height() as u32 {
+                    self.parent.set_height(u32::try_from(term.get_height() as i32 - _b_cpu_h as i32).unwrap_or(0));
+                }
+                self.parent.set_x(u32::try_from(term.get_width() as i32 - self.parent.get_width() as i32 + 1).unwrap_or(0));
+                self.parent.set_y(_b_cpu_h as u32 + 1);
+            }
         }
-        self.parent.set_x(u32::try_from(term.get_width() as i32 - self.parent.get_width() as i32 + 1).unwrap_or(0));
-        self.parent.set_y(_b_cpu_h as u32 + 1);
         self.select_max = usize::try_from(self.parent.get_height() as i32 - 3).unwrap_or(0);
         self.redraw = true;
         self.parent.set_resized(true);
     }
 
     pub fn draw_bg(&self, theme: &Theme, term : &Term) -> String {
-        if self.parent.get_stat_mode() {
+        if self.parent.get_stat_mode() || self.parent.get_hidden() {
             return String::default();
         }
 
@@ -128,7 +138,7 @@ impl<'a> ProcBox {
             0,
             None,
             None,
-            Some(theme.colors.proc_box.clone()),
+            Some(if self.parent.is_focused() { theme.colors.hi_fg.clone() } else { theme.colors.proc_box.clone() }),
             None,
             true,
             Some(Boxes::ProcBox),
@@ -283,7 +293,7 @@ impl<'a> ProcBox {
         proc : &ProcCollector,
         menu : &Menu,
     ) {
-        if self.parent.get_stat_mode() {
+        if self.parent.get_stat_mode() || self.parent.get_hidden() {
             return;
         }
 
@@ -441,28 +451,28 @@ impl<'a> ProcBox {
                             "{}{}{}{}{}{}{}{}{}{}{}{}",
                             mv::to(dy + 7, x - 1),
                             THEME.colors.proc_box,
-                            symbol::title_right,
-                            symbol::h_line.repeat(w as usize),
-                            symbol::title_left,
+                            symbol::title_right(),
+                            symbol::h_line().repeat(w as usize),
+                            symbol::title_left(),
                             mv::to(dy + 7, x + 1),
                             THEME
                                 .colors
                                 .proc_box
-                                .call(symbol::title_left.to_owned(), term),
+                                .call(symbol::title_left().to_owned(), term),
                             fx::b,
                             THEME.colors.title.call(self.get_parent().get_name().clone(), term),
                             fx::ub,
                             THEME
                                 .colors
                                 .proc_box
-                                .call(symbol::title_right.to_owned(), term),
+                                .call(symbol::title_right().to_owned(), term),
                             THEME.colors.div_line,
                         )
                         .as_str(),
                     );
                     for i in 0..7 as u32 {
                         out_misc.push_str(
-                            format!("{}{}", mv::to(dy + i, dgx + dgw + 1), symbol::v_line,)
+                            format!("{}{}", mv::to(dy + i, dgx + dgw + 1), symbol::v_line(),)
                                 .as_str(),
                         );
                     }
@@ -473,16 +483,16 @@ impl<'a> ProcBox {
                         "{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}",
                         mv::to(dy - 1, x - 1),
                         THEME.colors.proc_box,
-                        symbol::left_up,
-                        symbol::h_line.repeat(w as usize),
-                        symbol::right_up,
+                        symbol::left_up(),
+                        symbol::h_line().repeat(w as usize),
+                        symbol::right_up(),
                         mv::to(dy - 1, dgx + dgw + 1),
-                        symbol::div_up,
+                        symbol::div_up(),
                         mv::to(dy - 1, x + 1),
                         THEME
                             .colors
                             .proc_box
-                            .call(symbol::title_left.to_owned(), term),
+                            .call(symbol::title_left().to_owned(), term),
                         fx::b,
                         THEME
                             .colors
@@ -492,11 +502,11 @@ impl<'a> ProcBox {
                         THEME
                             .colors
                             .proc_box
-                            .call(symbol::title_right.to_owned(), term),
+                            .call(symbol::title_right().to_owned(), term),
                         THEME
                             .colors
                             .proc_box
-                            .call(symbol::title_left.to_owned(), term),
+                            .call(symbol::title_left().to_owned(), term),
                         fx::b,
                         THEME.colors.title.call(
                             proc.details[&"name".to_owned()].to_string()[..dgw as usize - 11].to_owned(),
@@ -506,17 +516,19 @@ impl<'a> ProcBox {
                         THEME
                             .colors
                             .proc_box
-                            .call(symbol::title_right.to_owned(), term),
+                            .call(symbol::title_right().to_owned(), term),
                     )
                     .as_str(),
                 );
 
+                // * The ascii enter symbol is wider than one cell, close starts further left
+                let enter_pad: u32 = symbol::enter().chars().count() as u32 - 1;
                 if self.selected == 0 {
                     let mut top: Vec<Vec<i32>> = Vec::<Vec<i32>>::new();
 
-                    for i in 0..7 {
+                    for i in 0..7 + enter_pad as i32 {
                         let mut pusher: Vec<i32> = Vec::<i32>::new();
-                        pusher.push((dx + dw) as i32 - 10 + i);
+                        pusher.push((dx + dw - 10 - enter_pad) as i32 + i);
                         pusher.push(dy as i32 - 1);
                         top.push(pusher);
                     }
@@ -540,11 +552,11 @@ impl<'a> ProcBox {
                 out_misc.push_str(
                     format!(
                         "{}{}{}{}close{} {}{}{}{}{}{}{}t{}erminate{}{}",
-                        mv::to(dy - 1, dx + dw - 11),
+                        mv::to(dy - 1, dx + dw - 11 - enter_pad),
                         THEME
                             .colors
                             .proc_box
-                            .call(symbol::title_left.to_owned(), term),
+                            .call(symbol::title_left().to_owned(), term),
                         fx::b,
                         if self.selected > 0 {
                             title
@@ -557,16 +569,16 @@ impl<'a> ProcBox {
                         } else {
                             THEME.colors.main_fg
                         },
-                        symbol::enter,
+                        symbol::enter(),
                         THEME
                             .colors
                             .proc_box
-                            .call(symbol::title_right.to_owned(), term),
+                            .call(symbol::title_right().to_owned(), term),
                         mv::to(dy - 1, dx + 1),
                         THEME
                             .colors
                             .proc_box
-                            .call(symbol::title_left.to_owned(), term),
+                            .call(symbol::title_left().to_owned(), term),
                         fx::b,
                         hi,
                         title,
@@ -574,7 +586,7 @@ impl<'a> ProcBox {
                         THEME
                             .colors
                             .proc_box
-                            .call(symbol::title_right.to_owned(), term),
+                            .call(symbol::title_right().to_owned(), term),
                     )
                     .as_str(),
                 );
@@ -597,7 +609,7 @@ impl<'a> ProcBox {
                             THEME
                                 .colors
                                 .proc_box
-                                .call(symbol::title_left.to_owned(), term),
+                                .call(symbol::title_left().to_owned(), term),
                             fx::b,
                             hi,
                             title,
@@ -605,7 +617,7 @@ impl<'a> ProcBox {
                             THEME
                                 .colors
                                 .proc_box
-                                .call(symbol::title_right.to_owned(), term),
+                                .call(symbol::title_right().to_owned(), term),
                         )
                         .as_str(),
                     );
@@ -630,7 +642,7 @@ impl<'a> ProcBox {
                             THEME
                                 .colors
                                 .proc_box
-                                .call(symbol::title_left.to_owned(), term),
+                                .call(symbol::title_left().to_owned(), term),
                             fx::b,
                             hi,
                             title,
@@ -638,7 +650,7 @@ impl<'a> ProcBox {
                             THEME
                                 .colors
                                 .proc_box
-                                .call(symbol::title_right.to_owned(), term),
+                                .call(symbol::title_right().to_owned(), term),
                         )
                         .as_str(),
                     );
@@ -680,19 +692,19 @@ impl<'a> ProcBox {
                     out_misc.push_str(format!("{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}",
                             mv::to(y - 1, x - 1),
                             THEME.colors.proc_box,
-                            symbol::left_up,
-                            symbol::h_line.repeat(w as usize),
-                            symbol::right_up,
+                            symbol::left_up(),
+                            symbol::h_line().repeat(w as usize),
+                            symbol::right_up(),
                             mv::to(y - 1, x + 1),
-                            THEME.colors.proc_box.call(symbol::title_left.to_owned(), term),
+                            THEME.colors.proc_box.call(symbol::title_left().to_owned(), term),
                             fx::b,
                             THEME.colors.title.call(self.get_parent().get_name().clone(), term),
                             fx::ub,
-                            THEME.colors.proc_box.call(symbol::title_right.to_owned(), term),
+                            THEME.colors.proc_box.call(symbol::title_right().to_owned(), term),
                             mv::to(y + 7, x - 1),
-                            THEME.colors.proc_box.call(symbol::v_line.to_owned(), term),
+                            THEME.colors.proc_box.call(symbol::v_line().to_owned(), term),
                             mv::right(w),
-                            THEME.colors.proc_box.call(symbol::v_line.to_owned(), term),
+                            THEME.colors.proc_box.call(symbol::v_line().to_owned(), term),
                         )
                         .as_str()
                     );
@@ -727,23 +739,23 @@ impl<'a> ProcBox {
 
             out_misc.push_str(format!("{}{}{}{}{}{}{} {} {}{}{}",
                     mv::to(y - 1, x + 8),
-                    THEME.colors.proc_box.call(symbol::h_line.repeat(w as usize - 9).to_owned(), term),
+                    THEME.colors.proc_box.call(symbol::h_line().repeat(w as usize - 9).to_owned(), term),
                     if !proc.detailed {
                         "".to_owned()
                     } else {
                         format!("{}{}", 
                             mv::to(dy + 7, dgx + dgw + 1),
-                            THEME.colors.proc_box.call(symbol::div_down.to_owned(), term)
+                            THEME.colors.proc_box.call(symbol::div_down().to_owned(), term)
                         )
                     },
                     mv::to(y - 1, sort_pos as u32),
-                    THEME.colors.proc_box.call(symbol::title_left.to_owned(), term),
+                    THEME.colors.proc_box.call(symbol::title_left().to_owned(), term),
                     fx::b,
                     THEME.colors.hi_fg.call("<".to_owned(), term),
                     THEME.colors.title.call(CONFIG.proc_sorting.to_string(), term),
                     THEME.colors.hi_fg.call(">".to_owned(), term),
                     fx::ub,
-                    THEME.colors.proc_box.call(symbol::title_right.to_owned(), term),
+                    THEME.colors.proc_box.call(symbol::title_right().to_owned(), term),
                 )
                 .as_str()
             );
@@ -763,7 +775,7 @@ impl<'a> ProcBox {
                 }
                 out_misc.push_str(format!("{}{}{}{}{}{}{}",
                         mv::to(y - 1, sort_pos as u32 - 6),
-                        THEME.colors.proc_box.call(symbol::title_left.to_owned(), term),
+                        THEME.colors.proc_box.call(symbol::title_left().to_owned(), term),
                         if CONFIG.proc_tree {
                             fx::b
                         } else {
@@ -772,7 +784,7 @@ impl<'a> ProcBox {
                         THEME.colors.title.call("tre".to_owned(), term),
                         THEME.colors.hi_fg.call("e".to_owned(), term),
                         fx::ub,
-                        THEME.colors.proc_box.call(symbol::title_right.to_owned(), term),
+                        THEME.colors.proc_box.call(symbol::title_right().to_owned(), term),
                     )
                     .as_str()
                 );
@@ -793,7 +805,7 @@ impl<'a> ProcBox {
                 }
                 out_misc.push_str(format!("{}{}{}{}{}{}{}",
                         mv::to(y - 1, sort_pos as u32 - 15),
-                        THEME.colors.proc_box.call(symbol::title_left.to_owned(), term),
+                        THEME.colors.proc_box.call(symbol::title_left().to_owned(), term),
                         if CONFIG.proc_reversed {
                             fx::b
                         } else {
@@ -802,7 +814,7 @@ impl<'a> ProcBox {
                         THEME.colors.hi_fg.call("r".to_owned(), term),
                         THEME.colors.title.call("everse".to_owned(), term),
                         fx::ub,
-                        THEME.colors.proc_box.call(symbol::title_right.to_owned(), term),
+                        THEME.colors.proc_box.call(symbol::title_right().to_owned(), term),
                     )
                     .as_str()
                 );
@@ -823,7 +835,7 @@ impl<'a> ProcBox {
                 }
                 out_misc.push_str(format!("{}{}{}{}{}{}{}{}",
                         mv::to(y - 1, sort_pos as u32 - 25),
-                        THEME.colors.proc_box.call(symbol::title_left.to_owned(), term),
+                        THEME.colors.proc_box.call(symbol::title_left().to_owned(), term),
                         if CONFIG.proc_per_core {
                             fx::b
                         } else {
@@ -833,7 +845,7 @@ impl<'a> ProcBox {
                         THEME.colors.hi_fg.call("c".to_owned(), term),
                         THEME.colors.title.call("ore".to_owned(), term),
                         fx::ub,
-                        THEME.colors.proc_box.call(symbol::title_right.to_owned(), term),
+                        THEME.colors.proc_box.call(symbol::title_right().to_owned(), term),
                     )
                     .as_str()
                 );
@@ -870,7 +882,7 @@ impl<'a> ProcBox {
 
             out_misc.push_str(format!("{}{}{}{}{}{}{}",
                     mv::to(y - 1, x + 7),
-                    THEME.colors.proc_box.call(symbol::title_left.to_owned(), term),
+                    THEME.colors.proc_box.call(symbol::title_left().to_owned(), term),
                     if self.filtering || proc.search_filter.len() > 0 {
                         fx::b
                     } else {
@@ -892,7 +904,7 @@ impl<'a> ProcBox {
                             }
                         )
                     },
-                    THEME.colors.proc_box.call(symbol::title_right.to_owned(), term),
+                    THEME.colors.proc_box.call(symbol::title_right().to_owned(), term),
                 )
                 .as_str()
             );
@@ -912,15 +924,17 @@ impl<'a> ProcBox {
             } else {
                 THEME.colors.title
             };
+            // * The ascii enter symbol is wider than one cell, the items after it move along
+            let enter_pad : u32 = symbol::enter().chars().count() as u32 - 1;
 
             out_misc.push_str(format!("{}{}{}{}{}{}{} {}{} {}{}{}{}{}{}{}info {}{}{}{}",
                     mv::to(y + h, x + 1),
                     THEME.colors.proc_box,
-                    symbol::h_line.repeat(w as usize - 4),
+                    symbol::h_line().repeat(w as usize - 4),
                     mv::to(y + h, x + 1),
-                    THEME.colors.proc_box.call(symbol::title_left.to_owned(), term),
+                    THEME.colors.proc_box.call(symbol::title_left().to_owned(), term),
                     main,
-                    symbol::up,
+                    symbol::up(),
                     fx::b,
                     THEME.colors.main_fg.call("select".to_owned(), term),
                     fx::ub,
@@ -929,22 +943,22 @@ impl<'a> ProcBox {
                     } else {
                         THEME.colors.main_fg
                     },
-                    symbol::down,
-                    THEME.colors.proc_box.call(symbol::title_right.to_owned(), term),
-                    THEME.colors.proc_box.call(symbol::title_left.to_owned(), term),
+                    symbol::down(),
+                    THEME.colors.proc_box.call(symbol::title_right().to_owned(), term),
+                    THEME.colors.proc_box.call(symbol::title_left().to_owned(), term),
                     title,
                     fx::b,
                     fx::ub,
                     main,
-                    symbol::enter,
-                    THEME.colors.proc_box.call(symbol::title_right.to_owned(), term),
+                    symbol::enter(),
+                    THEME.colors.proc_box.call(symbol::title_right().to_owned(), term),
                 )
                 .as_str()
             );
             if !key.mouse.contains_key(&"enter".to_owned()) {
                 let mut top: Vec<Vec<i32>> = Vec::<Vec<i32>>::new();
 
-                for i in 0..6 {
+                for i in 0..6 + enter_pad as i32 {
                     let mut pusher: Vec<i32> = Vec::<i32>::new();
                     pusher.push((x + 14) as i32 + i);
                     pusher.push((y + h) as i32);
@@ -953,13 +967,13 @@ impl<'a> ProcBox {
 
                 key.mouse.insert("enter".to_owned(), top.clone());
             }
-            if w - loc_string.len() as u32 > 34 {
+            if w - loc_string.len() as u32 > 34 + enter_pad {
                 if !key.mouse.contains_key(&"t".to_owned()) {
                     let mut top: Vec<Vec<i32>> = Vec::<Vec<i32>>::new();
 
                     for i in 0..9 {
                         let mut pusher: Vec<i32> = Vec::<i32>::new();
-                        pusher.push(x as i32 + 22 + i);
+                        pusher.push((x + 22 + enter_pad) as i32 + i);
                         pusher.push((y + h) as i32);
                         top.push(pusher);
                     }
@@ -967,23 +981,23 @@ impl<'a> ProcBox {
                     key.mouse.insert("t".to_owned(), top.clone());
                 }
                 out_misc.push_str(format!("{}{}{}t{}erminate{}{}",
-                        THEME.colors.proc_box.call(symbol::title_left.to_owned(), term),
+                        THEME.colors.proc_box.call(symbol::title_left().to_owned(), term),
                         fx::b,
                         hi,
                         title,
                         fx::ub,
-                        THEME.colors.proc_box.call(symbol::title_right.to_owned(), term),
+                        THEME.colors.proc_box.call(symbol::title_right().to_owned(), term),
                     )
                     .as_str()
                 );
             }
-            if w - loc_string.len() as u32 > 40 {
+            if w - loc_string.len() as u32 > 40 + enter_pad {
                 if !key.mouse.contains_key(&"k".to_owned()) {
                     let mut top: Vec<Vec<i32>> = Vec::<Vec<i32>>::new();
 
                     for i in 0..4 {
                         let mut pusher: Vec<i32> = Vec::<i32>::new();
-                        pusher.push(x as i32 + 33 + i);
+                        pusher.push((x + 33 + enter_pad) as i32 + i);
                         pusher.push((y + h) as i32);
                         top.push(pusher);
                     }
@@ -991,23 +1005,23 @@ impl<'a> ProcBox {
                     key.mouse.insert("k".to_owned(), top.clone());
                 }
                 out_misc.push_str(format!("{}{}{}k{}ill{}{}",
-                        THEME.colors.proc_box.call(symbol::title_left.to_owned(), term),
+                        THEME.colors.proc_box.call(symbol::title_left().to_owned(), term),
                         fx::b,
                         hi,
                         title,
                         fx::ub,
-                        THEME.colors.proc_box.call(symbol::title_right.to_owned(), term),
+                        THEME.colors.proc_box.call(symbol::title_right().to_owned(), term),
                     )
                     .as_str()
                 );
             }
-            if w - loc_string.len() as u32 > 51 {
+            if w - loc_string.len() as u32 > 51 + enter_pad {
                 if !key.mouse.contains_key(&"i".to_owned()) {
                     let mut top: Vec<Vec<i32>> = Vec::<Vec<i32>>::new();
 
                     for i in 0..9 {
                         let mut pusher: Vec<i32> = Vec::<i32>::new();
-                        pusher.push(x as i32 + 39 + i);
+                        pusher.push((x + 39 + enter_pad) as i32 + i);
                         pusher.push((y + h) as i32);
                         top.push(pusher);
                     }
@@ -1015,24 +1029,24 @@ impl<'a> ProcBox {
                     key.mouse.insert("i".to_owned(), top.clone());
                 }
                 out_misc.push_str(format!("{}{}{}i{}terrupt{}{}",
-                        THEME.colors.proc_box.call(symbol::title_left.to_owned(), term),
+                        THEME.colors.proc_box.call(symbol::title_left().to_owned(), term),
                         fx::b,
                         hi,
                         title,
                         fx::ub,
-                        THEME.colors.proc_box.call(symbol::title_right.to_owned(), term),
+                        THEME.colors.proc_box.call(symbol::title_right().to_owned(), term),
                     )
                     .as_str()
                 );
             }
-            if CONFIG.proc_tree && w - loc_string.len() as u32 > 65 {
-                if w - loc_string.len() as u32 > 40 {
+            if CONFIG.proc_tree && w - loc_string.len() as u32 > 65 + enter_pad {
+                if w - loc_string.len() as u32 > 40 + enter_pad {
                     if !key.mouse.contains_key(&" ".to_owned()) {
                         let mut top: Vec<Vec<i32>> = Vec::<Vec<i32>>::new();
     
                         for i in 0..12 {
                             let mut pusher: Vec<i32> = Vec::<i32>::new();
-                            pusher.push(x as i32 + 50 + i);
+                            pusher.push((x + 50 + enter_pad) as i32 + i);
                             pusher.push((y + h) as i32);
                             top.push(pusher);
                         }
@@ -1040,12 +1054,12 @@ impl<'a> ProcBox {
                         key.mouse.insert(" ".to_owned(), top.clone());
                     }
                     out_misc.push_str(format!("{}{}{}spc {}collapse{}{}",
-                            THEME.colors.proc_box.call(symbol::title_left.to_owned(), term),
+                            THEME.colors.proc_box.call(symbol::title_left().to_owned(), term),
                             fx::b,
                             hi,
                             title,
                             fx::ub,
-                            THEME.colors.proc_box.call(symbol::title_right.to_owned(), term),
+                            THEME.colors.proc_box.call(symbol::title_right().to_owned(), term),
                         )
                         .as_str()
                     );
@@ -1137,6 +1151,36 @@ impl<'a> ProcBox {
                 }
             }
 
+            // * Column labels are clickable to sort by that column
+            let header: String = match label.split(mv::to(y, x).as_str()).nth(1) {
+                Some(s) => s.split(fx::ub).next().unwrap_or("").to_owned(),
+                None => String::default(),
+            };
+            key.mouse.retain(|k, _| !k.starts_with("sortcol_"));
+            for (column, sorting) in [
+                ("Pid:", "pid"),
+                ("Program:", "program"),
+                ("Prg:", "program"),
+                ("Tree:", "program"),
+                ("Arguments:", "arguments"),
+                ("Threads:", "threads"),
+                ("Tr:", "threads"),
+                ("User:", "user"),
+                ("Mem%", "memory"),
+                ("Cpu%", "cpu lazy"),
+            ]
+            .iter()
+            {
+                if let Some(i) = header.find(column) {
+                    key.mouse.insert(
+                        format!("sortcol_{}", sorting),
+                        (0..column.len())
+                            .map(|c| vec![(x as usize + i + c) as i32, y as i32])
+                            .collect(),
+                    );
+                }
+            }
+
             selected = selected.split(" ").map(|s| s.to_owned().to_owned()).collect::<Vec<String>>()[0].to_title_case();
             if CONFIG.proc_mem_bytes {
                 label = label.replace("Mem%", "MemB");
@@ -1165,6 +1209,7 @@ impl<'a> ProcBox {
             let iw : u32 = (dw - 3) / (4 + expand);
             let iw2 : u32 = iw - 1;
 
+            graphs.detailed_cpu.place(dgx, dy);
             out.push_str(format!("{}{}{}{}{}%{}{}{}",
                     mv::to(dy, dgx),
                     graphs.detailed_cpu.call(
@@ -1225,6 +1270,8 @@ impl<'a> ProcBox {
 
             let inserter : String = proc.details[&"terminal".to_owned()].to_string()[(proc.details[&"terminal".to_owned()].to_string().len() - 1 - iw2 as usize)..].to_owned();
             let expand_4 = format!("{:^first$.second$}", inserter, first = iw as usize, second = iw2 as usize);
+            // * The memory graph is drawn over the end of the dotted line after the memory percent
+            graphs.detailed_mem.place(dx + 2 * (dw / 3) - 1, dy + 3);
             
             
             out.push_str(format!("{} {}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{}{} {}{}{}{} {}{}{}{}{}{}{}{}{}{}",
@@ -1828,12 +1875,12 @@ impl<'a> ProcBox {
         out.push_str(format!("{}{}{}{}{}{}{}{}",
                 mv::to(y + h, x + w - 3 - loc_string.len() as u32),
                 THEME.colors.proc_box,
-                symbol::title_left,
+                symbol::title_left(),
                 THEME.colors.title,
                 fx::b,
                 loc_string,
                 fx::ub,
-                THEME.colors.proc_box.call(symbol::title_right.to_owned(), term),
+                THEME.colors.proc_box.call(symbol::title_right().to_owned(), term),
             )
             .as_str()
         );
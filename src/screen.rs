@@ -0,0 +1,557 @@
+use {
+    crate::mv,
+    terminal_size::{terminal_size, Height, Width},
+};
+
+/// Unchanged cells in a gap shorter than this are rewritten instead of moving the cursor
+const MAX_REWRITE: usize = 4;
+
+/// Text attributes of a cell, colors are kept as their SGR parameters
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Style {
+    pub fg: Option<String>,
+    pub bg: Option<String>,
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub blink: bool,
+    pub reverse: bool,
+    pub strike: bool,
+}
+impl Style {
+    /// Apply the parameters of an SGR sequence
+    pub fn apply(&mut self, params: &str) {
+        let codes: Vec<&str> = params.split(';').collect();
+        let mut i: usize = 0;
+        while i < codes.len() {
+            match codes[i].parse::<u32>().unwrap_or(0) {
+                0 => *self = Style::default(),
+                1 => self.bold = true,
+                2 => self.dim = true,
+                3 => self.italic = true,
+                4 => self.underline = true,
+                5 => self.blink = true,
+                7 => self.reverse = true,
+                9 => self.strike = true,
+                21 | 22 => {
+                    self.bold = false;
+                    self.dim = false;
+                }
+                23 => self.italic = false,
+                24 => self.underline = false,
+                25 => self.blink = false,
+                27 => self.reverse = false,
+                29 => self.strike = false,
+                c @ 30..=37 | c @ 90..=97 => self.fg = Some(c.to_string()),
+                39 => self.fg = None,
+                c @ 40..=47 | c @ 100..=107 => self.bg = Some(c.to_string()),
+                49 => self.bg = None,
+                c @ 38 | c @ 48 => {
+                    // * Extended colors take 2 (256 colors) or 4 (truecolor) more parameters
+                    let len: usize = match codes.get(i + 1) {
+                        Some(&"5") => 2,
+                        Some(&"2") => 4,
+                        _ => 0,
+                    };
+                    let end: usize = (i + 1 + len).min(codes.len());
+                    let color: String = format!("{};{}", c, codes[i + 1..end].join(";"));
+                    if c == 38 {
+                        self.fg = Some(color);
+                    } else {
+                        self.bg = Some(color);
+                    }
+                    i = end - 1;
+                }
+                _ => (),
+            }
+            i += 1;
+        }
+    }
+
+    /// SGR sequence that sets this style from any previous one
+    pub fn sgr(&self) -> String {
+        let mut params: Vec<String> = vec!["0".to_owned()];
+        for (set, code) in [
+            (self.bold, "1"),
+            (self.dim, "2"),
+            (self.italic, "3"),
+            (self.underline, "4"),
+            (self.blink, "5"),
+            (self.reverse, "7"),
+            (self.strike, "9"),
+        ]
+        .iter()
+        {
+            if *set {
+                params.push(code.to_string());
+            }
+        }
+        if let Some(fg) = &self.fg {
+            params.push(fg.clone());
+        }
+        if let Some(bg) = &self.bg {
+            params.push(bg.clone());
+        }
+        format!("\x1b[{}m", params.join(";"))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cell {
+    pub ch: char,
+    pub style: Style,
+}
+impl Default for Cell {
+    fn default() -> Self {
+        Cell {
+            ch: ' ',
+            style: Style::default(),
+        }
+    }
+}
+
+/// Cell grid of what has been drawn, only the cells that changed since the last frame are written
+pub struct Screen {
+    width: usize,
+    height: usize,
+    front: Vec<Cell>,
+    back: Vec<Cell>,
+    valid: bool,
+    row: usize,
+    col: usize,
+    saved_pos: (usize, usize),
+    style: Style,
+    sync: (String, String),
+}
+impl Screen {
+    pub fn new() -> Self {
+        Screen {
+            width: 0,
+            height: 0,
+            front: Vec::<Cell>::new(),
+            back: Vec::<Cell>::new(),
+            valid: false,
+            row: 1,
+            col: 1,
+            saved_pos: (1, 1),
+            style: Style::default(),
+            sync: (String::default(), String::default()),
+        }
+    }
+
+    /// Sequences wrapped around each frame so the terminal shows it at once, empty if unsupported
+    pub fn set_sync(&mut self, begin: String, end: String) {
+        self.sync = (begin, end);
+    }
+
+    /// Draw strings onto the grid and return the escape sequences that bring the terminal up to date
+    pub fn render(&mut self, args: &Vec<String>) -> String {
+        match terminal_size() {
+            Some((Width(w), Height(h))) => self.resize(w as usize, h as usize),
+            None => {
+                // * Without a known size the strings are written as they are
+                self.invalidate();
+                return args.concat();
+            }
+        }
+        let mut out: String = String::default();
+        for s in args {
+            out.push_str(self.parse(s).as_str());
+        }
+        out.push_str(self.diff().as_str());
+        if out.len() > 0 && self.sync.0.len() > 0 {
+            out = format!("{}{}{}", self.sync.0, out, self.sync.1);
+        }
+        out
+    }
+
+    /// Forget what is on the terminal, the next frame is written in full
+    pub fn invalidate(&mut self) {
+        self.valid = false;
+    }
+
+    fn resize(&mut self, width: usize, height: usize) {
+        if width != self.width || height != self.height {
+            self.width = width;
+            self.height = height;
+            self.back = vec![Cell::default(); width * height];
+            self.valid = false;
+        }
+    }
+
+    /// Interpret a string onto the back grid, returns sequences that don't draw cells
+    fn parse(&mut self, s: &str) -> String {
+        let mut passthrough: String = String::default();
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\x1b' => match chars.next() {
+                    Some('[') => {
+                        let mut params: String = String::default();
+                        let mut fin: char = '\0';
+                        while let Some(p) = chars.next() {
+                            if ('\x40'..='\x7e').contains(&p) {
+                                fin = p;
+                                break;
+                            }
+                            params.push(p);
+                        }
+                        if !self.csi(&params, fin) {
+                            passthrough.push_str(format!("\x1b[{}{}", params, fin).as_str());
+                        }
+                    }
+                    Some(']') => {
+                        // * Operating system commands end with BEL or ST
+                        passthrough.push_str("\x1b]");
+                        while let Some(p) = chars.next() {
+                            passthrough.push(p);
+                            if p == '\x07' {
+                                break;
+                            } else if p == '\x1b' && chars.peek() == Some(&'\\') {
+                                passthrough.push(chars.next().unwrap());
+                                break;
+                            }
+                        }
+                    }
+                    Some('7') => self.saved_pos = (self.row, self.col),
+                    Some('8') => {
+                        self.row = self.saved_pos.0;
+                        self.col = self.saved_pos.1;
+                    }
+                    Some(p) => {
+                        passthrough.push('\x1b');
+                        passthrough.push(p);
+                        match p {
+                            // * Keypad modes don't draw, charset designations take one more character
+                            '=' | '>' => (),
+                            '(' | ')' => {
+                                if let Some(set) = chars.next() {
+                                    passthrough.push(set);
+                                }
+                            }
+                            // * Resets, index and reverse index change cells the grid doesn't track
+                            _ => self.invalidate(),
+                        }
+                    }
+                    None => passthrough.push('\x1b'),
+                },
+                '\n' => {
+                    self.row += 1;
+                    self.col = 1;
+                }
+                '\r' => self.col = 1,
+                '\x08' => self.col = self.col.saturating_sub(1).max(1),
+                c if c < ' ' => (),
+                c => {
+                    if let Some(i) = self.index(self.row, self.col) {
+                        self.back[i] = Cell {
+                            ch: c,
+                            style: self.style.clone(),
+                        };
+                    }
+                    self.col += 1;
+                }
+            }
+        }
+        passthrough
+    }
+
+    /// Apply a control sequence to the grid, returns false if it is passed on to the terminal.
+    /// Sequences that move cells around are passed on and the next frame is written in full
+    fn csi(&mut self, params: &str, fin: char) -> bool {
+        let private: bool = params.starts_with(|c| "?<=>".contains(c));
+        let nums: Vec<usize> = params
+            .trim_start_matches(|c| "?<=>".contains(c))
+            .split(';')
+            .map(|p| p.parse::<usize>().unwrap_or(0))
+            .collect();
+        let n: usize = nums[0].max(1);
+        let cursor: usize = self.cursor_index();
+        match fin {
+            'h' | 'l' if params == "?1049" => {
+                // * A switched screen starts out empty and nothing is drawn on the normal screen
+                self.back = vec![Cell::default(); self.width * self.height];
+                self.front = self.back.clone();
+                self.valid = true;
+                return false;
+            }
+            'J' if nums[0] == 2 || (nums[0] == 0 && self.row == 1 && self.col == 1) => {
+                // * The clear is passed on, cells with a background color are filled in by the diff
+                let blank: Cell = Cell {
+                    ch: ' ',
+                    style: self.style.clone(),
+                };
+                self.back = vec![blank; self.width * self.height];
+                self.front = vec![Cell::default(); self.width * self.height];
+                self.valid = true;
+                return false;
+            }
+            'J' if nums[0] == 0 => self.erase(cursor, self.back.len()),
+            'J' if nums[0] == 1 => self.erase(0, cursor + 1),
+            // * Clearing the scrollback doesn't change the screen
+            'J' => return false,
+            'K' => {
+                let line: usize = cursor - cursor % self.width.max(1);
+                match nums[0] {
+                    0 => self.erase(cursor, line + self.width),
+                    1 => self.erase(line, cursor + 1),
+                    _ => self.erase(line, line + self.width),
+                }
+            }
+            // * Private modes, queries and reports don't draw
+            _ if private => return false,
+            'm' => self.style.apply(params),
+            'f' | 'H' => {
+                self.row = nums[0].max(1);
+                self.col = nums.get(1).copied().unwrap_or(1).max(1);
+            }
+            'A' => self.row = self.row.saturating_sub(n).max(1),
+            'B' => self.row += n,
+            'C' => self.col += n,
+            'D' => self.col = self.col.saturating_sub(n).max(1),
+            'E' => {
+                self.row += n;
+                self.col = 1;
+            }
+            'F' => {
+                self.row = self.row.saturating_sub(n).max(1);
+                self.col = 1;
+            }
+            'G' | '`' => self.col = n,
+            'd' => self.row = n,
+            'X' => {
+                let line_end: usize = cursor - cursor % self.width.max(1) + self.width;
+                self.erase(cursor, (cursor + n).min(line_end));
+            }
+            's' => self.saved_pos = (self.row, self.col),
+            'u' => {
+                self.row = self.saved_pos.0;
+                self.col = self.saved_pos.1;
+            }
+            'h' | 'l' | 'n' | 'c' | 'q' | 't' => return false,
+            _ => {
+                self.invalidate();
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Index of the cursor cell, clamped to the grid
+    fn cursor_index(&self) -> usize {
+        let row: usize = self.row.max(1).min(self.height.max(1));
+        let col: usize = self.col.max(1).min(self.width.max(1));
+        ((row - 1) * self.width + col - 1).min(self.back.len())
+    }
+
+    /// Blank the cells from index start up to end with the current style
+    fn erase(&mut self, start: usize, end: usize) {
+        let end: usize = end.min(self.back.len());
+        for i in start.min(end)..end {
+            self.back[i] = Cell {
+                ch: ' ',
+                style: self.style.clone(),
+            };
+        }
+    }
+
+    fn index(&self, row: usize, col: usize) -> Option<usize> {
+        if row >= 1 && col >= 1 && row <= self.height && col <= self.width {
+            Some((row - 1) * self.width + col - 1)
+        } else {
+            None
+        }
+    }
+
+    /// Write the cells that differ from the last frame with as few moves and style changes as possible
+    fn diff(&mut self) -> String {
+        let mut out: String = String::default();
+        if !self.valid {
+            out.push_str("\x1b[0m\x1b[2J");
+            self.front = vec![Cell::default(); self.width * self.height];
+            self.valid = true;
+        }
+        // * Position and style of the terminal cursor, None until something has been written
+        let mut cursor: Option<(usize, usize)> = None;
+        let mut style: Option<Style> = None;
+        for row in 1..=self.height {
+            let mut col: usize = 1;
+            while col <= self.width {
+                let i: usize = (row - 1) * self.width + col - 1;
+                if self.front[i] == self.back[i] {
+                    col += 1;
+                    continue;
+                }
+                match cursor {
+                    Some((r, c)) if r == row && c == col => (),
+                    Some((r, c)) if r == row && c < col => {
+                        let gap: usize = col - c;
+                        let start: usize = i - gap;
+                        if gap < MAX_REWRITE
+                            && self.back[start..i]
+                                .iter()
+                                .all(|cell| Some(&cell.style) == style.as_ref())
+                        {
+                            for cell in &self.back[start..i] {
+                                out.push(cell.ch);
+                            }
+                        } else {
+                            out.push_str(mv::right(gap as u32).as_str());
+                        }
+                    }
+                    _ => out.push_str(mv::to(row as u32, col as u32).as_str()),
+                }
+                let cell: Cell = self.back[i].clone();
+                if style.as_ref() != Some(&cell.style) {
+                    out.push_str(cell.style.sgr().as_str());
+                    style = Some(cell.style.clone());
+                }
+                out.push(cell.ch);
+                self.front[i] = cell.clone();
+                col += 1;
+                // * Wide characters move the cursor by two cells, so its position is no longer known
+                cursor = if cell.ch < '\u{1100}' || ('\u{2000}'..'\u{2c00}').contains(&cell.ch) {
+                    Some((row, col))
+                } else {
+                    None
+                };
+            }
+        }
+        if style.is_some() {
+            out.push_str("\x1b[0m");
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn screen(width: usize, height: usize) -> Screen {
+        let mut screen: Screen = Screen::new();
+        screen.resize(width, height);
+        screen
+    }
+
+    /// Draw a string and return what would be written to the terminal
+    fn frame(screen: &mut Screen, s: &str) -> String {
+        let passthrough: String = screen.parse(s);
+        passthrough + screen.diff().as_str()
+    }
+
+    fn row(screen: &Screen, row: usize) -> String {
+        screen.back[(row - 1) * screen.width..row * screen.width]
+            .iter()
+            .map(|c| c.ch)
+            .collect()
+    }
+
+    #[test]
+    fn style_applies_sgr_parameters() {
+        let mut style: Style = Style::default();
+        style.apply("1;4;38;2;10;20;30;48;5;100");
+        assert!(style.bold && style.underline);
+        assert_eq!(style.fg, Some("38;2;10;20;30".to_owned()));
+        assert_eq!(style.bg, Some("48;5;100".to_owned()));
+        style.apply("22;39;97");
+        assert!(!style.bold && style.underline);
+        assert_eq!(style.fg, Some("97".to_owned()));
+        style.apply("0");
+        assert_eq!(style, Style::default());
+    }
+
+    #[test]
+    fn style_sgr_round_trips() {
+        let mut style: Style = Style::default();
+        style.apply("2;3;7;9;31;48;2;1;2;3");
+        let sgr: String = style.sgr();
+        let mut parsed: Style = Style::default();
+        parsed.apply(&sgr[2..sgr.len() - 1]);
+        assert_eq!(parsed, style);
+        assert_eq!(Style::default().sgr(), "\x1b[0m");
+    }
+
+    #[test]
+    fn first_frame_is_written_in_full_then_only_changes() {
+        let mut screen: Screen = screen(10, 3);
+        let first: String = frame(&mut screen, "\x1b[2;3fab");
+        assert!(first.starts_with("\x1b[0m\x1b[2J"));
+        assert!(first.ends_with("\x1b[2;3f\x1b[0mab\x1b[0m"));
+        assert_eq!(frame(&mut screen, "\x1b[2;3fab"), "");
+        assert_eq!(frame(&mut screen, "\x1b[2;4fc"), "\x1b[2;4f\x1b[0mc\x1b[0m");
+    }
+
+    #[test]
+    fn short_gaps_are_rewritten_and_long_ones_skipped() {
+        let mut screen: Screen = screen(20, 1);
+        frame(&mut screen, "abcdefghijklmnop");
+        assert_eq!(
+            frame(&mut screen, "\x1b[1;1fX\x1b[1;4fY"),
+            "\x1b[1;1f\x1b[0mXbcY\x1b[0m"
+        );
+        assert_eq!(
+            frame(&mut screen, "\x1b[1;1fZ\x1b[1;10fW"),
+            "\x1b[1;1f\x1b[0mZ\x1b[8CW\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn style_changes_are_written_once() {
+        let mut screen: Screen = screen(10, 1);
+        frame(&mut screen, "");
+        assert_eq!(
+            frame(&mut screen, "\x1b[1;1f\x1b[1mab\x1b[0mc"),
+            "\x1b[1;1f\x1b[0;1mab\x1b[0mc\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn erase_in_line_is_applied_to_the_grid() {
+        let mut screen: Screen = screen(6, 2);
+        frame(&mut screen, "abcdef\x1b[2;1fghijkl");
+        let out: String = frame(&mut screen, "\x1b[1;3f\x1b[1K\x1b[2;3f\x1b[2K");
+        assert!(!out.contains("K"));
+        assert_eq!(row(&screen, 1), "   def");
+        assert_eq!(row(&screen, 2), "      ");
+        assert_eq!(
+            frame(&mut screen, "\x1b[1;5f\x1b[K"),
+            "\x1b[1;5f\x1b[0m  \x1b[0m"
+        );
+    }
+
+    #[test]
+    fn erase_in_display_and_characters_are_applied_to_the_grid() {
+        let mut screen: Screen = screen(4, 3);
+        frame(&mut screen, "abcd\x1b[2;1fefgh\x1b[3;1fijkl");
+        frame(&mut screen, "\x1b[2;3f\x1b[J");
+        assert_eq!(row(&screen, 2), "ef  ");
+        assert_eq!(row(&screen, 3), "    ");
+        frame(&mut screen, "\x1b[1;2f\x1b[2X\x1b[2;1f\x1b[1J");
+        assert_eq!(row(&screen, 1), "    ");
+        assert_eq!(row(&screen, 2), " f  ");
+        frame(&mut screen, "\x1b[1;1fwxyz\x1b[1;2f\x1b[2X");
+        assert_eq!(row(&screen, 1), "w  z");
+    }
+
+    #[test]
+    fn untracked_sequences_redraw_everything() {
+        let mut screen: Screen = screen(4, 2);
+        frame(&mut screen, "abcd");
+        let out: String = frame(&mut screen, "\x1b[1L");
+        assert!(out.starts_with("\x1b[1L\x1b[0m\x1b[2J"));
+        assert!(out.contains("abcd"));
+    }
+
+    #[test]
+    fn private_modes_pass_through_without_a_redraw() {
+        let mut screen: Screen = screen(4, 2);
+        frame(&mut screen, "abcd");
+        assert_eq!(
+            frame(&mut screen, "\x1b[?25l\x1b]0;title\x07"),
+            "\x1b[?25l\x1b]0;title\x07"
+        );
+        assert_eq!(frame(&mut screen, "\x1b(Bab"), "\x1b(B");
+    }
+}
@@ -1,8 +1,48 @@
 use crate::theme::Color;
 use lazy_static::lazy_static;
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Mutex};
+
+pub const SYMBOL_SETS: [&str; 3] = ["braille", "block", "ascii"];
+
+/// Characters graphs, meters and box outlines are drawn with
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SymbolSet {
+    Braille,
+    Block,
+    Ascii,
+}
+impl SymbolSet {
+    pub fn from_config(s: &String) -> Self {
+        match s.as_str() {
+            "block" => SymbolSet::Block,
+            "ascii" => SymbolSet::Ascii,
+            _ => SymbolSet::Braille,
+        }
+    }
+
+    pub fn get() -> Self {
+        *SYMBOL_SET.lock().unwrap()
+    }
+
+    pub fn set(set: SymbolSet) {
+        *SYMBOL_SET.lock().unwrap() = set;
+    }
+}
+
+/// Graph map with the same keys as the braille ones, left * 10 + right for values 0-4
+/// A single cell shows the sum of both values as one of 9 levels
+fn level_map(levels: [&'static str; 9]) -> HashMap<u32, &'static str> {
+    let mut map = HashMap::new();
+    for l in 0..5 {
+        for r in 0..5 {
+            map.insert(l * 10 + r, levels[(l + r) as usize]);
+        }
+    }
+    map
+}
 
 lazy_static! {
+    static ref SYMBOL_SET: Mutex<SymbolSet> = Mutex::new(SymbolSet::Braille);
     static ref __graph_up: HashMap<u32, &'static str> = {
         let mut map = HashMap::new();
         map.insert(0, " ");
@@ -32,11 +72,6 @@ lazy_static! {
         map.insert(44, "⣿");
         map
     };
-    static ref __graph_up_small: HashMap<u32, &'static str> = {
-        let mut map = __graph_up.clone();
-        map.insert(0, "\x1b[1C");
-        map
-    };
     static ref __graph_down: HashMap<u32, &'static str> = {
         let mut map = HashMap::new();
         map.insert(00, " ");
@@ -66,11 +101,14 @@ lazy_static! {
         map.insert(44, "⣿");
         map
     };
-    static ref __graph_down_small: HashMap<u32, &'static str> = {
-        let mut map = __graph_down.clone();
-        map.insert(0, "\x1b[1C");
-        map
-    };
+    static ref __graph_up_block: HashMap<u32, &'static str> =
+        level_map([" ", "▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"]);
+    static ref __graph_down_block: HashMap<u32, &'static str> =
+        level_map([" ", "▔", "▔", "▀", "▀", "▀", "█", "█", "█"]);
+    static ref __graph_up_ascii: HashMap<u32, &'static str> =
+        level_map([" ", "_", "_", "-", "-", "=", "=", "#", "#"]);
+    static ref __graph_down_ascii: HashMap<u32, &'static str> =
+        level_map([" ", "'", "'", "\"", "\"", "=", "=", "#", "#"]);
     static ref __ok: String = format!(
         "{}√{}",
         Color::fg("#30ff50").unwrap(),
@@ -81,39 +119,109 @@ lazy_static! {
         Color::fg("#ff3050").unwrap(),
         Color::fg("#cc").unwrap()
     );
+    static ref __ok_ascii: String = format!(
+        "{}[OK]{}",
+        Color::fg("#30ff50").unwrap(),
+        Color::fg("#cc").unwrap()
+    );
+    static ref __fail_ascii: String = format!(
+        "{}[X]{}",
+        Color::fg("#ff3050").unwrap(),
+        Color::fg("#cc").unwrap()
+    );
+}
+
+/// The unicode symbol, or the ascii one when the ascii set is used
+fn pick(unicode: &'static str, ascii: &'static str) -> &'static str {
+    if SymbolSet::get() == SymbolSet::Ascii {
+        ascii
+    } else {
+        unicode
+    }
+}
+
+pub fn up() -> &'static str {
+    pick("↑", "^")
+}
+pub fn down() -> &'static str {
+    pick("↓", "v")
+}
+pub fn left() -> &'static str {
+    pick("←", "<")
+}
+pub fn right() -> &'static str {
+    pick("→", ">")
+}
+pub fn enter() -> &'static str {
+    pick("↲", "<-'")
+}
+pub fn meter() -> &'static str {
+    pick("■", "#")
+}
+pub fn h_line() -> &'static str {
+    pick("─", "-")
+}
+pub fn v_line() -> &'static str {
+    pick("│", "|")
+}
+pub fn left_up() -> &'static str {
+    pick("┌", "+")
+}
+pub fn right_up() -> &'static str {
+    pick("┐", "+")
+}
+pub fn left_down() -> &'static str {
+    pick("└", "+")
+}
+pub fn right_down() -> &'static str {
+    pick("┘", "+")
+}
+pub fn title_left() -> &'static str {
+    pick("┤", "[")
+}
+pub fn title_right() -> &'static str {
+    pick("├", "]")
+}
+pub fn div_up() -> &'static str {
+    pick("┬", "+")
+}
+pub fn div_down() -> &'static str {
+    pick("┴", "+")
 }
-pub const meter: &'static str = "■";
-pub const up: &'static str = "↑";
-pub const down: &'static str = "↓";
-pub const left: &'static str = "←";
-pub const right: &'static str = "→";
-pub const enter: &'static str = "↲";
-pub const h_line: &'static str = "─";
-pub const v_line: &'static str = "│";
-pub const left_up: &'static str = "┌";
-pub const right_up: &'static str = "┐";
-pub const left_down: &'static str = "└";
-pub const right_down: &'static str = "┘";
-pub const title_left: &'static str = "┤";
-pub const title_right: &'static str = "├";
-pub const div_up: &'static str = "┬";
-pub const div_down: &'static str = "┴";
 
 pub fn graph_up() -> HashMap<u32, &'static str> {
-    __graph_up.clone()
+    match SymbolSet::get() {
+        SymbolSet::Braille => __graph_up.clone(),
+        SymbolSet::Block => __graph_up_block.clone(),
+        SymbolSet::Ascii => __graph_up_ascii.clone(),
+    }
 }
 pub fn graph_up_small() -> HashMap<u32, &'static str> {
-    __graph_up_small.clone()
+    let mut map = graph_up();
+    map.insert(0, "\x1b[1C");
+    map
 }
 pub fn graph_down() -> HashMap<u32, &'static str> {
-    __graph_down.clone()
+    match SymbolSet::get() {
+        SymbolSet::Braille => __graph_down.clone(),
+        SymbolSet::Block => __graph_down_block.clone(),
+        SymbolSet::Ascii => __graph_down_ascii.clone(),
+    }
 }
 pub fn graph_down_small() -> HashMap<u32, &'static str> {
-    __graph_down_small.clone()
+    let mut map = graph_down();
+    map.insert(0, "\x1b[1C");
+    map
 }
 pub fn ok() -> String {
-    __ok.clone()
+    match SymbolSet::get() {
+        SymbolSet::Ascii => __ok_ascii.clone(),
+        _ => __ok.clone(),
+    }
 }
 pub fn fail() -> String {
-    __fail.clone()
+    match SymbolSet::get() {
+        SymbolSet::Ascii => __fail_ascii.clone(),
+        _ => __fail.clone(),
+    }
 }
@@ -11,28 +11,43 @@ use {
         event::{Event, EventEnum},
         fx,
         init::Init,
-        key::Key,
+        key::{Key, TTY_READER},
         membox::MemBox,
         menu::Menu,
         mv,
         netbox::NetBox,
         procbox::ProcBox,
+        terminfo::TermInfo,
         theme::{Color, Theme},
         timer::Timer,
     },
+    lazy_static::lazy_static,
+    regex::Regex,
     std::{
         collections::HashMap,
         convert::TryFrom,
-        io,
+        fs::OpenOptions,
+        io::{self, Read, Write},
         mem::drop,
         ops::{Deref, DerefMut},
         os::unix::io::AsRawFd,
         sync::{Mutex, MutexGuard},
+        time::{Duration, Instant},
     },
     terminal_size::{terminal_size, Height, Width},
     termios::*,
 };
 
+lazy_static! {
+    static ref OSC_COLOR: Regex =
+        Regex::new(r"\x1b\](\d+(?:;\d+)?);rgb:([0-9a-fA-F]+)/([0-9a-fA-F]+)/([0-9a-fA-F]+)")
+            .unwrap();
+    static ref DEVICE_ATTRIBUTES: Regex = Regex::new(r"\x1b\[\?[0-9;]*c").unwrap();
+}
+
+/// Longest time to wait for the terminal to answer color queries
+const QUERY_TIMEOUT: Duration = Duration::from_millis(500);
+
 #[derive(Clone)]
 pub struct Term {
     width: u16,
@@ -51,10 +66,66 @@ pub struct Term {
     mouse_off: String,
     mouse_direct_on: String,
     mouse_direct_off: String,
+    paste_on: String,
+    paste_off: String,
+    focus_on: String,
+    focus_off: String,
+    scheme_on: String,
+    scheme_off: String,
+    light: bool,
+    sync_begin: String,
+    sync_end: String,
     winch: Event,
 }
 impl Term {
     pub fn new() -> Self {
+        // * Sequences come from terminfo when the entry has them, xterm ones are used as fallbacks
+        // * A terminal with an entry but without a mouse key gets no paste, focus or scheme reporting,
+        // * mouse reporting is still asked for since terminals like the linux console lack the key
+        let info: Option<TermInfo> = TermInfo::load();
+        let cap = |name: &str, fallback: &str| -> String {
+            match info.as_ref().and_then(|i| i.get(name)) {
+                Some(s) => s,
+                None => fallback.to_owned(),
+            }
+        };
+        let xterm_like: bool = match &info {
+            Some(i) => i.has("kmous"),
+            None => true,
+        };
+        let xterm = |seq: &str| -> String {
+            if xterm_like {
+                seq.to_owned()
+            } else {
+                String::default()
+            }
+        };
+        let extra = |name: &str, fallback: &str| -> String {
+            if xterm_like {
+                cap(name, fallback)
+            } else {
+                String::default()
+            }
+        };
+        let sync: bool = match &info {
+            Some(i) if i.has("Sync") => true,
+            _ => {
+                let term: String = std::env::var("TERM").unwrap_or_default();
+                let program: String = std::env::var("TERM_PROGRAM").unwrap_or_default();
+                ["kitty", "foot", "wezterm", "contour", "alacritty"]
+                    .iter()
+                    .any(|t| term.contains(t))
+                    || ["WezTerm", "iTerm.app", "ghostty"].contains(&program.as_str())
+            }
+        };
+        let synced = |seq: &str| -> String {
+            if sync {
+                seq.to_owned()
+            } else {
+                String::default()
+            }
+        };
+
         Term {
             width: 1,
             height: 1,
@@ -63,17 +134,29 @@ impl Term {
             _h: 1,
             fg: Color::Default(),                    // Default foreground color,
             bg: Color::Default(),                    // Default background color,
-            hide_cursor: String::from("\x1b[?25l"),  // Hide terminal cursor,
-            show_cursor: String::from("\x1b[?25h"),  // Show terminal cursor,
-            alt_screen: String::from("\x1b[?1049h"), // Switch to alternate screen,
-            normal_screen: String::from("\x1b[?1049l"), // Switch to normal screen,
-            clear: String::from("\x1b[2J\x1b[0;0f"), // Clear screen and set cursor to position 0,0,
+            hide_cursor: cap("civis", "\x1b[?25l"),  // Hide terminal cursor,
+            show_cursor: cap("cnorm", "\x1b[?25h"),  // Show terminal cursor,
+            alt_screen: cap("smcup", "\x1b[?1049h"), // Switch to alternate screen,
+            normal_screen: cap("rmcup", "\x1b[?1049l"), // Switch to normal screen,
+            clear: cap("clear", "\x1b[2J\x1b[0;0f"), // Clear screen and set cursor to position 0,0,
             // Enable reporting of mouse position on click and release,
             mouse_on: String::from("\x1b[?1002h\x1b[?1015h\x1b[?1006h"),
-            mouse_off: String::from("\x1b[?1002l"), // Disable mouse reporting,
+            // Disable mouse reporting,
+            mouse_off: String::from("\x1b[?1002l"),
             // Enable reporting of mouse position at any movement,
             mouse_direct_on: String::from("\x1b[?1003h"),
-            mouse_direct_off: String::from("\x1b[?1003l"), // Disable direct mouse reporting,
+            // Disable direct mouse reporting,
+            mouse_direct_off: String::from("\x1b[?1003l"),
+            paste_on: extra("BE", "\x1b[?2004h"), // Enable bracketed paste,
+            paste_off: extra("BD", "\x1b[?2004l"), // Disable bracketed paste,
+            focus_on: extra("fe", "\x1b[?1004h"), // Enable focus in and out reporting,
+            focus_off: extra("fd", "\x1b[?1004l"), // Disable focus in and out reporting,
+            scheme_on: xterm("\x1b[?2031h"),      // Enable color scheme change reports,
+            scheme_off: xterm("\x1b[?2031l"),     // Disable color scheme change reports,
+            light: false,                         // If the terminal background is light,
+            // Begin and end a synchronized update, the terminal shows the frame at once,
+            sync_begin: synced("\x1b[?2026h"),
+            sync_end: synced("\x1b[?2026l"),
             winch: Event {
                 t: EventEnum::Flag(false),
             },
@@ -291,6 +374,92 @@ impl Term {
         timer.finish(key, config);
     }
 
+    /// Ask the terminal for colors with OSC queries like "4;1", "10" or "11", returns the colors it answered
+    pub fn query_colors(queries: &Vec<String>) -> HashMap<String, (u8, u8, u8)> {
+        let mut colors: HashMap<String, (u8, u8, u8)> = HashMap::<String, (u8, u8, u8)>::new();
+        // * Keep the key reader off the terminal until the replies are read
+        let _reading = TTY_READER.lock().unwrap();
+        let mut tty = match OpenOptions::new().read(true).write(true).open("/dev/tty") {
+            Ok(f) => f,
+            Err(e) => {
+                error::errlog(format!(
+                    "Unable to open the terminal for color queries ({})",
+                    e
+                ));
+                return colors;
+            }
+        };
+        let fd = tty.as_raw_fd();
+        let original: Termios = match Termios::from_fd(fd) {
+            Ok(t) => t,
+            Err(e) => {
+                error::errlog(format!("Error getting Termios data... (error {})", e));
+                return colors;
+            }
+        };
+        let mut raw: Termios = original.clone();
+        raw.c_lflag &= !(ICANON | ECHO);
+        raw.c_cc[VMIN] = 0;
+        raw.c_cc[VTIME] = 1;
+        if let Err(e) = tcsetattr(fd, TCSANOW, &raw) {
+            error::errlog(format!("Error setting Termios data... (error {})", e));
+            return colors;
+        }
+
+        // * Every terminal answers the device attributes query, so its answer marks the end of the replies
+        let mut request: String = queries
+            .iter()
+            .map(|q| format!("\x1b]{};?\x07", q))
+            .collect::<String>();
+        request.push_str("\x1b[c");
+        let mut response: Vec<u8> = Vec::<u8>::new();
+        if tty
+            .write_all(request.as_bytes())
+            .and_then(|_| tty.flush())
+            .is_ok()
+        {
+            let start: Instant = Instant::now();
+            let mut buffer: [u8; 256] = [0; 256];
+            while start.elapsed() < QUERY_TIMEOUT {
+                match tty.read(&mut buffer) {
+                    Ok(n) => response.extend_from_slice(&buffer[..n]),
+                    Err(_) => break,
+                }
+                if DEVICE_ATTRIBUTES.is_match(&String::from_utf8_lossy(&response)) {
+                    break;
+                }
+            }
+        }
+        if let Err(e) = tcsetattr(fd, TCSANOW, &original) {
+            error::errlog(format!("Error setting Termios data... (error {})", e));
+        }
+
+        // * Components are 1 to 4 hex digits, scaled to 0-255
+        let scale = |s: &str| -> u8 {
+            let digits: &str = &s[..s.len().min(4)];
+            let max: u32 = 16u32.pow(digits.len() as u32) - 1;
+            (u32::from_str_radix(digits, 16).unwrap_or(0) * 255 / max) as u8
+        };
+        for captures in OSC_COLOR.captures_iter(&String::from_utf8_lossy(&response)) {
+            colors.insert(
+                captures[1].to_owned(),
+                (
+                    scale(&captures[2]),
+                    scale(&captures[3]),
+                    scale(&captures[4]),
+                ),
+            );
+        }
+        colors
+    }
+
+    /// Ask the terminal for its background color with OSC 11, true if it is light
+    pub fn query_light() -> Option<bool> {
+        let (r, g, b) = *Self::query_colors(&vec!["11".to_owned()]).get("11")?;
+        // * Luminance with the sRGB weights, light backgrounds are above the middle
+        Some(0.2126 * r as f64 + 0.7152 * g as f64 + 0.0722 * b as f64 > 127.5)
+    }
+
     /// Toggle input echo
     pub fn echo(on: bool) {
         let fd = io::stdin().as_raw_fd().clone();
@@ -461,6 +630,46 @@ impl Term {
         self.mouse_direct_off = mouse_direct_off.clone()
     }
 
+    pub fn get_paste_on(&self) -> String {
+        self.paste_on.clone()
+    }
+
+    pub fn get_paste_off(&self) -> String {
+        self.paste_off.clone()
+    }
+
+    pub fn get_focus_on(&self) -> String {
+        self.focus_on.clone()
+    }
+
+    pub fn get_focus_off(&self) -> String {
+        self.focus_off.clone()
+    }
+
+    pub fn get_scheme_on(&self) -> String {
+        self.scheme_on.clone()
+    }
+
+    pub fn get_scheme_off(&self) -> String {
+        self.scheme_off.clone()
+    }
+
+    pub fn get_light(&self) -> bool {
+        self.light.clone()
+    }
+
+    pub fn set_light(&mut self, light: bool) {
+        self.light = light.clone()
+    }
+
+    pub fn get_sync_begin(&self) -> String {
+        self.sync_begin.clone()
+    }
+
+    pub fn get_sync_end(&self) -> String {
+        self.sync_end.clone()
+    }
+
     pub fn get_winch(&self) -> Event {
         self.winch.clone()
     }
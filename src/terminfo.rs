@@ -0,0 +1,392 @@
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Magic numbers of compiled terminfo files with 16 bit and 32 bit numbers
+const MAGIC_16: i16 = 0o432;
+const MAGIC_32: i16 = 0o1036;
+
+/// Indexes of the predefined string capabilities that are used, in the order of term.h
+const STRING_NAMES: [(usize, &str); 6] = [
+    (5, "clear"),
+    (13, "civis"),
+    (16, "cnorm"),
+    (28, "smcup"),
+    (40, "rmcup"),
+    (355, "kmous"),
+];
+
+/// Index of the "colors" number capability
+const COLORS_INDEX: usize = 13;
+
+/// String and number capabilities of the current terminal read from the terminfo database
+#[derive(Clone, Debug, Default)]
+pub struct TermInfo {
+    pub name: String,
+    pub strings: HashMap<String, String>,
+    pub numbers: HashMap<String, i32>,
+    pub bools: Vec<String>,
+}
+impl TermInfo {
+    /// Load the entry for $TERM, None if there is no readable entry
+    pub fn load() -> Option<Self> {
+        let name: String = env::var("TERM").ok()?;
+        let data: Vec<u8> = Self::find(&name).and_then(|path| fs::read(path).ok())?;
+        Self::parse(&name, &data)
+    }
+
+    /// Search the terminfo directories in the same order as ncurses
+    fn find(name: &String) -> Option<PathBuf> {
+        let first: char = name.chars().next()?;
+        let mut dirs: Vec<PathBuf> = Vec::new();
+        if let Ok(dir) = env::var("TERMINFO") {
+            dirs.push(PathBuf::from(dir));
+        }
+        if let Ok(home) = env::var("HOME") {
+            dirs.push(Path::new(&home).join(".terminfo"));
+        }
+        if let Ok(list) = env::var("TERMINFO_DIRS") {
+            dirs.extend(list.split(':').filter(|d| d.len() > 0).map(PathBuf::from));
+        }
+        for dir in &[
+            "/etc/terminfo",
+            "/lib/terminfo",
+            "/usr/share/terminfo",
+            "/usr/lib/terminfo",
+        ] {
+            dirs.push(PathBuf::from(dir));
+        }
+
+        for dir in dirs {
+            // * Entries are in a directory named by the first letter, or its hex code on macOS
+            for sub in vec![first.to_string(), format!("{:x}", first as u32)] {
+                let path: PathBuf = dir.join(sub).join(name);
+                if path.is_file() {
+                    return Some(path);
+                }
+            }
+        }
+        None
+    }
+
+    /// Parse a compiled terminfo entry including the extended capabilities
+    pub fn parse(name: &String, data: &Vec<u8>) -> Option<Self> {
+        let short = |pos: usize| -> Option<i16> {
+            Some(i16::from_le_bytes([*data.get(pos)?, *data.get(pos + 1)?]))
+        };
+        let magic: i16 = short(0)?;
+        let num_size: usize = match magic {
+            MAGIC_16 => 2,
+            MAGIC_32 => 4,
+            _ => return None,
+        };
+        // * Counts and sizes are never negative in a valid entry
+        let count =
+            |pos: usize| -> Option<usize> { short(pos).filter(|n| *n >= 0).map(|n| n as usize) };
+        let number = |pos: usize| -> Option<i32> {
+            if num_size == 2 {
+                short(pos).map(|n| n as i32)
+            } else {
+                let b: &[u8] = data.get(pos..pos + 4)?;
+                Some(i32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            }
+        };
+        let string_at = |table: usize, offset: i16| -> Option<String> {
+            if offset < 0 {
+                return None;
+            }
+            let start: usize = table + offset as usize;
+            let end: usize = start + data.get(start..)?.iter().position(|b| *b == 0)?;
+            Some(String::from_utf8_lossy(&data[start..end]).into_owned())
+        };
+
+        let names_size: usize = count(2)?;
+        let bool_count: usize = count(4)?;
+        let num_count: usize = count(6)?;
+        let str_count: usize = count(8)?;
+        let table_size: usize = count(10)?;
+
+        let mut info: TermInfo = TermInfo {
+            name: name.clone(),
+            ..TermInfo::default()
+        };
+
+        let mut pos: usize = 12 + names_size + bool_count;
+        pos += pos % 2;
+        if let Some(colors) =
+            number(pos + COLORS_INDEX * num_size).filter(|_| COLORS_INDEX < num_count)
+        {
+            if colors >= 0 {
+                info.numbers.insert("colors".to_owned(), colors);
+            }
+        }
+        pos += num_count * num_size;
+        let table: usize = pos + str_count * 2;
+        for (index, cap) in STRING_NAMES.iter() {
+            if *index < str_count {
+                if let Some(s) = string_at(table, short(pos + index * 2)?) {
+                    info.strings.insert(cap.to_string(), s);
+                }
+            }
+        }
+
+        // * Extended capabilities follow the string table, names come after all the values
+        pos = table + table_size;
+        pos += pos % 2;
+        let ext_bools: usize = match short(pos) {
+            Some(n) if n >= 0 => n as usize,
+            _ => return Some(info),
+        };
+        let ext_nums: usize = count(pos + 2)?;
+        let ext_strs: usize = count(pos + 4)?;
+        pos += 10;
+        let bools_pos: usize = pos;
+        pos += ext_bools;
+        pos += pos % 2;
+        let nums_pos: usize = pos;
+        pos += ext_nums * num_size;
+        let offsets_pos: usize = pos;
+        let names_pos: usize = offsets_pos + ext_strs * 2;
+        let ext_table: usize = names_pos + (ext_bools + ext_nums + ext_strs) * 2;
+
+        let mut values: Vec<Option<String>> = Vec::new();
+        let mut names_table: usize = ext_table;
+        for i in 0..ext_strs {
+            let offset: i16 = short(offsets_pos + i * 2)?;
+            let value: Option<String> = string_at(ext_table, offset);
+            if let Some(v) = &value {
+                names_table = names_table.max(ext_table + offset as usize + v.len() + 1);
+            }
+            values.push(value);
+        }
+        let ext_name =
+            |i: usize| -> Option<String> { string_at(names_table, short(names_pos + i * 2)?) };
+
+        for i in 0..ext_bools {
+            if data.get(bools_pos + i) == Some(&1) {
+                info.bools.push(ext_name(i)?);
+            }
+        }
+        for i in 0..ext_nums {
+            let n: i32 = number(nums_pos + i * num_size)?;
+            if n >= 0 {
+                info.numbers.insert(ext_name(ext_bools + i)?, n);
+            }
+        }
+        for (i, value) in values.into_iter().enumerate() {
+            if let Some(v) = value {
+                info.strings.insert(ext_name(ext_bools + ext_nums + i)?, v);
+            }
+        }
+        Some(info)
+    }
+
+    /// A string capability with delays like $<5> removed
+    pub fn get(&self, cap: &str) -> Option<String> {
+        let raw: &String = self.strings.get(cap)?;
+        let mut out: String = String::default();
+        let mut chars = raw.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '$' && chars.peek() == Some(&'<') {
+                while let Some(p) = chars.next() {
+                    if p == '>' {
+                        break;
+                    }
+                }
+            } else {
+                out.push(c);
+            }
+        }
+        Some(out)
+    }
+
+    pub fn has(&self, cap: &str) -> bool {
+        self.strings.contains_key(cap) || self.bools.iter().any(|b| b == cap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_short(out: &mut Vec<u8>, n: i16) {
+        out.extend_from_slice(&n.to_le_bytes());
+    }
+
+    fn pad(out: &mut Vec<u8>) {
+        if out.len() % 2 == 1 {
+            out.push(0);
+        }
+    }
+
+    /// Write a string table, returning the offsets of the strings and the table
+    fn table(strings: &[Option<&str>]) -> (Vec<i16>, Vec<u8>) {
+        let mut offsets: Vec<i16> = Vec::new();
+        let mut out: Vec<u8> = Vec::new();
+        for s in strings {
+            match s {
+                Some(s) => {
+                    offsets.push(out.len() as i16);
+                    out.extend_from_slice(s.as_bytes());
+                    out.push(0);
+                }
+                None => offsets.push(-1),
+            }
+        }
+        (offsets, out)
+    }
+
+    /// A compiled entry with the given numbers and predefined strings, and extended
+    /// bools, numbers and strings if any are given
+    fn entry(
+        num_size: usize,
+        numbers: &[i32],
+        strings: &[Option<&str>],
+        ext_bools: &[(&str, bool)],
+        ext_nums: &[(&str, i32)],
+        ext_strs: &[(&str, &str)],
+    ) -> Vec<u8> {
+        let names: &str = "test|fixture terminal\0";
+        let bools: [u8; 3] = [1, 0, 1];
+        let (offsets, strs) = table(strings);
+        let push_number = |out: &mut Vec<u8>, n: i32| {
+            if num_size == 2 {
+                push_short(out, n as i16);
+            } else {
+                out.extend_from_slice(&n.to_le_bytes());
+            }
+        };
+
+        let mut out: Vec<u8> = Vec::new();
+        push_short(&mut out, if num_size == 2 { MAGIC_16 } else { MAGIC_32 });
+        push_short(&mut out, names.len() as i16);
+        push_short(&mut out, bools.len() as i16);
+        push_short(&mut out, numbers.len() as i16);
+        push_short(&mut out, offsets.len() as i16);
+        push_short(&mut out, strs.len() as i16);
+        out.extend_from_slice(names.as_bytes());
+        out.extend_from_slice(&bools);
+        pad(&mut out);
+        for n in numbers {
+            push_number(&mut out, *n);
+        }
+        for o in &offsets {
+            push_short(&mut out, *o);
+        }
+        out.extend_from_slice(&strs);
+        if ext_bools.is_empty() && ext_nums.is_empty() && ext_strs.is_empty() {
+            return out;
+        }
+
+        pad(&mut out);
+        let (value_offsets, values) =
+            table(&ext_strs.iter().map(|(_, v)| Some(*v)).collect::<Vec<_>>());
+        let all_names: Vec<Option<&str>> = ext_bools
+            .iter()
+            .map(|(n, _)| Some(*n))
+            .chain(ext_nums.iter().map(|(n, _)| Some(*n)))
+            .chain(ext_strs.iter().map(|(n, _)| Some(*n)))
+            .collect();
+        let (name_offsets, name_table) = table(&all_names);
+        push_short(&mut out, ext_bools.len() as i16);
+        push_short(&mut out, ext_nums.len() as i16);
+        push_short(&mut out, ext_strs.len() as i16);
+        push_short(&mut out, (ext_strs.len() + all_names.len()) as i16);
+        push_short(&mut out, (values.len() + name_table.len()) as i16);
+        for (_, b) in ext_bools {
+            out.push(*b as u8);
+        }
+        pad(&mut out);
+        for (_, n) in ext_nums {
+            push_number(&mut out, *n);
+        }
+        for o in value_offsets.iter().chain(name_offsets.iter()) {
+            push_short(&mut out, *o);
+        }
+        out.extend_from_slice(&values);
+        out.extend_from_slice(&name_table);
+        out
+    }
+
+    /// 14 numbers so "colors" is included, and the strings up to rmcup
+    fn basic(num_size: usize, colors: i32) -> Vec<u8> {
+        let mut numbers: Vec<i32> = vec![-1; 14];
+        numbers[0] = 80;
+        numbers[COLORS_INDEX] = colors;
+        let mut strings: Vec<Option<&str>> = vec![None; 41];
+        strings[5] = Some("\x1b[H\x1b[2J$<50>");
+        strings[28] = Some("\x1b[?1049h");
+        strings[40] = Some("\x1b[?1049l");
+        entry(num_size, &numbers, &strings, &[], &[], &[])
+    }
+
+    #[test]
+    fn parses_legacy_numbers() {
+        let info: TermInfo = TermInfo::parse(&"test".to_owned(), &basic(2, 256)).unwrap();
+        assert_eq!(info.name, "test");
+        assert_eq!(info.numbers.get("colors"), Some(&256));
+        assert_eq!(info.get("clear").as_deref(), Some("\x1b[H\x1b[2J"));
+        assert_eq!(info.get("smcup").as_deref(), Some("\x1b[?1049h"));
+        assert_eq!(info.get("rmcup").as_deref(), Some("\x1b[?1049l"));
+        assert_eq!(info.get("civis"), None);
+        assert!(info.has("smcup"));
+        assert!(!info.has("kmous"));
+    }
+
+    #[test]
+    fn parses_32_bit_numbers() {
+        let info: TermInfo = TermInfo::parse(&"test".to_owned(), &basic(4, 0x100_0000)).unwrap();
+        assert_eq!(info.numbers.get("colors"), Some(&0x100_0000));
+        assert_eq!(info.get("rmcup").as_deref(), Some("\x1b[?1049l"));
+    }
+
+    #[test]
+    fn missing_colors_are_left_out() {
+        let info: TermInfo = TermInfo::parse(&"test".to_owned(), &basic(2, -1)).unwrap();
+        assert_eq!(info.numbers.get("colors"), None);
+    }
+
+    #[test]
+    fn parses_extended_capabilities() {
+        for num_size in [2, 4].iter() {
+            let data: Vec<u8> = entry(
+                *num_size,
+                &[80, 24],
+                &[],
+                &[("Tc", true), ("AX", false)],
+                &[("RGB", 8)],
+                &[("Ss", "\x1b[%p1%d q"), ("Setulc", "\x1b[58:2::%p1%dm")],
+            );
+            let info: TermInfo = TermInfo::parse(&"test".to_owned(), &data).unwrap();
+            assert!(info.has("Tc"));
+            assert!(!info.has("AX"));
+            assert_eq!(info.numbers.get("RGB"), Some(&8));
+            assert_eq!(info.get("Ss").as_deref(), Some("\x1b[%p1%d q"));
+            assert_eq!(info.get("Setulc").as_deref(), Some("\x1b[58:2::%p1%dm"));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_entries() {
+        let name: String = "test".to_owned();
+        assert!(TermInfo::parse(&name, &vec![]).is_none());
+        assert!(TermInfo::parse(&name, &vec![0x1a]).is_none());
+        // * Wrong magic
+        assert!(TermInfo::parse(&name, &vec![0x1e, 0x03, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]).is_none());
+
+        let data: Vec<u8> = basic(2, 256);
+        // * Truncated header
+        assert!(TermInfo::parse(&name, &data[..8].to_vec()).is_none());
+        // * Truncated in the string offsets
+        assert!(TermInfo::parse(&name, &data[..60].to_vec()).is_none());
+        // * Negative counts
+        let mut negative: Vec<u8> = data.clone();
+        negative[2..4].copy_from_slice(&(-2i16).to_le_bytes());
+        assert!(TermInfo::parse(&name, &negative).is_none());
+        let mut huge: Vec<u8> = data.clone();
+        huge[8..10].copy_from_slice(&i16::MAX.to_le_bytes());
+        assert!(TermInfo::parse(&name, &huge).is_none());
+    }
+}
@@ -1,15 +1,17 @@
 use {
-    crate::{error::errlog, term::Term, DEFAULT_THEME, THEME_DIR, USER_THEME_DIR},
+    crate::{
+        error::errlog, term::Term, terminfo::TermInfo, DEFAULT_LIGHT_THEME, DEFAULT_THEME,
+        THEME_DIR, USER_THEME_DIR, VERSION,
+    },
     from_map::{FromMap, FromMapDefault},
-    gradient::Gradient,
+    gradient::{Gradient, Space, Stop},
     lazy_static::lazy_static,
     regex::Regex,
     std::{
         collections::HashMap,
         ffi::OsString,
-        fs::File,
-        io::{self, BufRead, Read},
-        iter::FromIterator,
+        fs::{self, File},
+        io::{self, Read},
         path::{Path, PathBuf},
         sync::Mutex,
     },
@@ -19,7 +21,90 @@ lazy_static! {
     static ref SIX_DIGIT_HEX: Regex = Regex::new("^#([0-9a-fA-F]{6})$").unwrap();
     static ref TWO_DIGIT_HEX: Regex = Regex::new("^#([0-9a-fA-F]{2})$").unwrap();
     static ref DECIMAL: Regex = Regex::new(r"^(\d{1,3}) (\d{1,3}) (\d{1,3})$").unwrap();
-    static ref THEME_SELECTOR: Regex = Regex::new(r#"^theme\[(.+)\] *= *['"](.+)['"]$"#).unwrap();
+    static ref COLOR_DEPTH: Mutex<ColorDepth> = Mutex::new(ColorDepth::TrueColor);
+    static ref GRADIENT_SPACE: Mutex<Space> = Mutex::new(Space::Rgb);
+    /// Theme keys in the order of the Colors fields, used to list and save themes
+    pub static ref THEME_KEYS: Vec<&'static str> = Colors::keys();
+}
+
+/// Standard xterm values of the 16 ANSI colors
+pub const ANSI_16: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Levels of the 6x6x6 color cube in the xterm-256 palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+pub const COLOR_DEPTHS: [&str; 4] = ["auto", "truecolor", "256", "16"];
+
+/// Number of colors the terminal can show, theme colors are quantized to fit
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColorDepth {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+}
+impl ColorDepth {
+    /// Depth from the color_depth config value, "auto" detects it from the terminal
+    pub fn from_config(s: &String) -> Self {
+        match s.as_str() {
+            "truecolor" => ColorDepth::TrueColor,
+            "256" => ColorDepth::Ansi256,
+            "16" => ColorDepth::Ansi16,
+            _ => ColorDepth::detect(),
+        }
+    }
+
+    /// Check COLORTERM, then TERM and last the terminfo colors and RGB capabilities
+    pub fn detect() -> Self {
+        let colorterm: String = std::env::var("COLORTERM").unwrap_or_default();
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            return ColorDepth::TrueColor;
+        }
+        let term: String = std::env::var("TERM").unwrap_or_default();
+        if term.contains("direct") || term.contains("truecolor") || term.contains("24bit") {
+            return ColorDepth::TrueColor;
+        } else if term.contains("256color") {
+            return ColorDepth::Ansi256;
+        }
+        let (colors, rgb): (i32, bool) = match TermInfo::load() {
+            Some(info) => (
+                info.numbers.get("colors").copied().unwrap_or(0),
+                info.has("RGB") || info.has("Tc"),
+            ),
+            None => (0, false),
+        };
+        if rgb || colors >= 1 << 24 {
+            ColorDepth::TrueColor
+        } else if colors >= 256 {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
+
+    pub fn get() -> Self {
+        *COLOR_DEPTH.lock().unwrap()
+    }
+
+    pub fn set(depth: ColorDepth) {
+        *COLOR_DEPTH.lock().unwrap() = depth;
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -34,16 +119,65 @@ pub struct Color {
     g: u8,
     b: u8,
     depth: LayerDepth,
+    null: bool,
 }
 impl Color {
     pub fn escape(&self) -> String {
-        format!(
-            "\x1b[{};2;{};{};{}m",
-            if self.depth == LayerDepth::Fg { 38 } else { 48 },
-            self.r,
-            self.g,
-            self.b
-        )
+        if self.null {
+            return String::default();
+        }
+        let layer: u32 = if self.depth == LayerDepth::Fg { 38 } else { 48 };
+        match ColorDepth::get() {
+            ColorDepth::TrueColor => format!("\x1b[{};2;{};{};{}m", layer, self.r, self.g, self.b),
+            ColorDepth::Ansi256 => format!("\x1b[{};5;{}m", layer, self.to_256()),
+            ColorDepth::Ansi16 => {
+                // * Colors 8-15 use the bright codes 90-97 and 100-107
+                let n: u32 = self.to_16() as u32;
+                let base: u32 = if n < 8 { layer - 8 } else { layer + 52 - 8 };
+                format!("\x1b[{}m", base + n)
+            }
+        }
+    }
+
+    /// Nearest color of the xterm-256 palette, either from the color cube or the grayscale ramp
+    pub fn to_256(&self) -> u8 {
+        let level = |v: u8| -> usize {
+            CUBE_LEVELS
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, l)| (**l as i32 - v as i32).abs())
+                .map(|(i, _)| i)
+                .unwrap_or(0)
+        };
+        let (ri, gi, bi) = (level(self.r), level(self.g), level(self.b));
+        let cube: (u8, u8, u8) = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+
+        let average: u32 = (self.r as u32 + self.g as u32 + self.b as u32) / 3;
+        let gray_i: u32 = if average < 8 {
+            0
+        } else {
+            ((average - 8) / 10).min(23)
+        };
+        let gray_v: u8 = (8 + gray_i * 10) as u8;
+
+        if self.distance((gray_v, gray_v, gray_v)) < self.distance(cube) {
+            (232 + gray_i) as u8
+        } else {
+            (16 + 36 * ri + 6 * gi + bi) as u8
+        }
+    }
+
+    /// Nearest of the 16 ANSI colors
+    pub fn to_16(&self) -> u8 {
+        (0..16)
+            .min_by_key(|i| self.distance(ANSI_16[*i]))
+            .unwrap_or(7) as u8
+    }
+
+    /// Squared distance to another color, weighted for how sensitive the eye is to each channel
+    fn distance(&self, other: (u8, u8, u8)) -> u32 {
+        let d = |a: u8, b: u8| -> u32 { (a as i32 - b as i32).pow(2) as u32 };
+        2 * d(self.r, other.0) + 4 * d(self.g, other.1) + 3 * d(self.b, other.2)
     }
 
     pub fn bg<S: ToString>(s: S) -> Result<Self, String> {
@@ -78,6 +212,7 @@ impl Color {
         } else if let Some(captures) = DECIMAL.captures(&s) {
             let mut parts = captures
                 .iter()
+                .skip(1)
                 .take(3)
                 .map(|capture| capture.unwrap().as_str()); // Unwrap is safe, regex will only match if 3 decimal values exist
             r = u8::from_str_radix(&parts.next().unwrap(), 10).unwrap_or(0); // These unwraps are unreachable because of the regex
@@ -96,9 +231,44 @@ impl Color {
             g,
             b,
             depth: LayerDepth::Fg,
+            null: false,
         })
     }
 
+    /// Color of an index in the xterm-256 palette
+    pub fn from_256(n: u8) -> Self {
+        let (r, g, b): (u8, u8, u8) = if n < 16 {
+            ANSI_16[n as usize]
+        } else if n < 232 {
+            let i: usize = n as usize - 16;
+            (
+                CUBE_LEVELS[i / 36],
+                CUBE_LEVELS[i / 6 % 6],
+                CUBE_LEVELS[i % 6],
+            )
+        } else {
+            let v: u8 = 8 + (n - 232) * 10;
+            (v, v, v)
+        };
+        Self {
+            r,
+            g,
+            b,
+            depth: LayerDepth::Fg,
+            null: false,
+        }
+    }
+
+    /// If the string is a color `new` accepts, an empty string is no color
+    pub fn is_valid(s: &str) -> bool {
+        s.len() == 0
+            || SIX_DIGIT_HEX.is_match(s)
+            || TWO_DIGIT_HEX.is_match(s)
+            || DECIMAL
+                .captures(s)
+                .map_or(false, |c| (1..=3).all(|i| c[i].parse::<u8>().is_ok()))
+    }
+
     pub fn Default() -> Self {
         Self::new("#cc").unwrap()
     }
@@ -126,9 +296,14 @@ impl Color {
             g: 0,
             b: 0,
             depth: LayerDepth::Fg,
+            null: true,
         }
     }
 
+    pub fn is_null(&self) -> bool {
+        self.null
+    }
+
     pub fn call(&self, adder: String, term: &Term) -> String {
         if adder.len() < 1 {
             return String::default();
@@ -157,7 +332,10 @@ impl std::fmt::Display for Color {
 }
 impl std::fmt::UpperHex for Color {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "#{:X}{:X}{:X}", self.r, self.g, self.b)
+        if self.null {
+            return Ok(());
+        }
+        write!(f, "#{:02X}{:02X}{:02X}", self.r, self.g, self.b)
     }
 }
 impl From<String> for Color {
@@ -200,69 +378,69 @@ pub struct Colors {
     pub selected_fg: Color,
     #[default("#40")]
     pub inactive_fg: Color,
-    #[default("#60")]
+    #[default("#0de756")]
     pub proc_misc: Color,
-    #[default("#40")]
+    #[default("#3d7b46")]
     pub cpu_box: Color,
-    #[default("#0de756")]
+    #[default("#8a882e")]
     pub mem_box: Color,
-    #[default("#3d7b46")]
+    #[default("#423ba5")]
     pub net_box: Color,
-    #[default("#8a882e")]
+    #[default("#923535")]
     pub proc_box: Color,
-    #[default("#423ba5")]
+    #[default("#30")]
     pub div_line: Color,
-    #[default("#923535")]
+    #[default("#4897d4")]
     pub temp_start: Color,
-    #[default("#30")]
+    #[default("#5474e8")]
     pub temp_mid: Color,
-    #[default("#4897d4")]
+    #[default("#ff40b6")]
     pub temp_end: Color,
-    #[default("#5474e8")]
+    #[default("#50f095")]
     pub cpu_start: Color,
-    #[default("#ff40b6")]
+    #[default("#f2e266")]
     pub cpu_mid: Color,
-    #[default("#50f095")]
+    #[default("#fa1e1e")]
     pub cpu_end: Color,
-    #[default("#f2e266")]
+    #[default("#223014")]
     pub free_start: Color,
-    #[default("#fa1e1e")]
+    #[default("#b5e685")]
     pub free_mid: Color,
-    #[default("#223014")]
+    #[default("#dcff85")]
     pub free_end: Color,
-    #[default("#b5e685")]
+    #[default("#0b1a29")]
     pub cached_start: Color,
-    #[default("#dcff85")]
+    #[default("#74e6fc")]
     pub cached_mid: Color,
-    #[default("#0b1a29")]
+    #[default("#26c5ff")]
     pub cached_end: Color,
-    #[default("#74e6fc")]
+    #[default("#292107")]
     pub available_start: Color,
-    #[default("#26c5ff")]
+    #[default("#ffd77a")]
     pub available_mid: Color,
-    #[default("#292107")]
+    #[default("#ffb814")]
     pub available_end: Color,
-    #[default("#ffd77a")]
+    #[default("#3b1f1c")]
     pub used_start: Color,
-    #[default("#ffb814")]
+    #[default("#d9626d")]
     pub used_mid: Color,
-    #[default("#3b1f1c")]
+    #[default("#ff4769")]
     pub used_end: Color,
-    #[default("#d9626d")]
+    #[default("#231a63")]
     pub download_start: Color,
-    #[default("#ff4769")]
+    #[default("#4f43a3")]
     pub download_mid: Color,
-    #[default("#231a63")]
+    #[default("#b0a9de")]
     pub download_end: Color,
-    #[default("#4f43a3")]
+    #[default("#510554")]
     pub upload_start: Color,
-    #[default("#b0a9de")]
+    #[default("#7d4180")]
     pub upload_mid: Color,
-    #[default("#510554")]
+    #[default("#dcafde")]
     pub upload_end: Color,
-    #[default("#7d4180")]
+    #[default("#60")]
     pub graph_text: Color,
-    #[default("#dcafde")]
+    #[default("#40")]
     pub meter_bg: Color,
     #[default("#80d0a3")]
     pub process_start: Color,
@@ -273,83 +451,28 @@ pub struct Colors {
 }
 impl Colors {
     fn from_str<S: ToString>(s: S) -> Result<Self, String> {
-        /*let s = s.to_string();
-        let map: HashMap<String, Color> = HashMap::from_iter(
-            s.split('\n')
-                .filter(|line| !line.starts_with("#") && THEME_SELECTOR.is_match(line))
-                .map(|line: &str| -> Result<(String, Color), String> {
-                    let captures = match THEME_SELECTOR.captures(line) {
-                        Some(caps) => caps,
-                        None => unreachable!(),
-                    };
-
-                    let first = captures.get(1).unwrap().as_str();
-                    let second = captures.get(2).unwrap().as_str();
-                    println!("Adding {} with a color of {}", first, second);
-                    if second.is_empty() {
-                        Err(format!(
-                            "Attempted to add attribute {} with an empty string...",
-                            first
-                        ))
-                    } else {
-                        Ok((first.into(), Color::new(second)?))
-                    }
-                })
-                .filter(|result| {
-                    if let Err(msg) = result {
-                        // errlog(config_dir: &Path, message: String)
-                        false
-                    } else {
-                        true
-                    }
-                })
-                .map(|res| res.unwrap()),
-        );
-        Ok(Self::from_map_default(map))*/
-        Ok(Colors {
-            main_bg: Color::from("".to_owned()),
-            main_fg: Color::from("#cc".to_owned()),
-            title: Color::from("#ee".to_owned()),
-            hi_fg: Color::from("#969696".to_owned()),
-            selected_bg: Color::from("#7e2626".to_owned()),
-            selected_fg: Color::from("#ee".to_owned()),
-            inactive_fg: Color::from("#40".to_owned()),
-            proc_misc: Color::from("#60".to_owned()),
-            cpu_box: Color::from("#40".to_owned()),
-            mem_box: Color::from("#0de756".to_owned()),
-            net_box: Color::from("#3d7b46".to_owned()),
-            proc_box: Color::from("#8a882e".to_owned()),
-            div_line: Color::from("#423ba5".to_owned()),
-            temp_start: Color::from("#923535".to_owned()),
-            temp_mid: Color::from("#30".to_owned()),
-            temp_end: Color::from("#4897d4".to_owned()),
-            cpu_start: Color::from("#5474e8".to_owned()),
-            cpu_mid: Color::from("#ff40b6".to_owned()),
-            cpu_end: Color::from("#50f095".to_owned()),
-            free_start: Color::from("#f2e266".to_owned()),
-            free_mid: Color::from("#fa1e1e".to_owned()),
-            free_end: Color::from("#223014".to_owned()),
-            cached_start: Color::from("#b5e685".to_owned()),
-            cached_mid: Color::from("#dcff85".to_owned()),
-            cached_end: Color::from("#0b1a29".to_owned()),
-            available_start: Color::from("#74e6fc".to_owned()),
-            available_mid: Color::from("#26c5ff".to_owned()),
-            available_end: Color::from("#292107".to_owned()),
-            used_start: Color::from("#ffd77a".to_owned()),
-            used_mid: Color::from("#ffb814".to_owned()),
-            used_end: Color::from("#3b1f1c".to_owned()),
-            download_start: Color::from("#d9626d".to_owned()),
-            download_mid: Color::from("#ff4769".to_owned()),
-            download_end: Color::from("#231a63".to_owned()),
-            upload_start: Color::from("#4f43a3".to_owned()),
-            upload_mid: Color::from("#b0a9de".to_owned()),
-            upload_end: Color::from("#510554".to_owned()),
-            graph_text: Color::from("#7d4180".to_owned()),
-            meter_bg: Color::from("#dcafde".to_owned()),
-            process_start: Color::from("#80d0a3".to_owned()),
-            process_mid: Color::from("#dcd179".to_owned()),
-            process_end: Color::from("#d45454".to_owned()),
-        })
+        let (tdict, warnings) = Theme::parse(&s.to_string());
+        for warning in warnings {
+            errlog(format!("Theme {}", warning));
+        }
+        Ok(Self::from_dict(&tdict))
+    }
+
+    /// Colors for every key of DEFAULT_THEME, keys missing from the theme use the default value
+    pub fn from_dict(theme: &HashMap<String, String>) -> Self {
+        let tdict: HashMap<String, String> = Theme::complete(theme);
+        let map: HashMap<String, Color> = tdict
+            .iter()
+            .map(|(item, value)| {
+                let color: Result<Color, String> = if item == "main_bg" || item == "selected_bg" {
+                    Color::bg(value)
+                } else {
+                    Color::fg(value)
+                };
+                (item.clone(), color.unwrap_or(Color::Default()))
+            })
+            .collect();
+        Self::from_map_default(map)
     }
 
     pub fn new<R>(mut reader: R) -> Result<Self, String>
@@ -374,52 +497,8 @@ impl Colors {
     }
 
     pub fn default() -> Colors {
-        Colors {
-            main_bg: Color::from("".to_owned()),
-            main_fg: Color::from("#cc".to_owned()),
-            title: Color::from("#ee".to_owned()),
-            hi_fg: Color::from("#969696".to_owned()),
-            selected_bg: Color::from("#7e2626".to_owned()),
-            selected_fg: Color::from("#ee".to_owned()),
-            inactive_fg: Color::from("#40".to_owned()),
-            proc_misc: Color::from("#60".to_owned()),
-            cpu_box: Color::from("#40".to_owned()),
-            mem_box: Color::from("#0de756".to_owned()),
-            net_box: Color::from("#3d7b46".to_owned()),
-            proc_box: Color::from("#8a882e".to_owned()),
-            div_line: Color::from("#423ba5".to_owned()),
-            temp_start: Color::from("#923535".to_owned()),
-            temp_mid: Color::from("#30".to_owned()),
-            temp_end: Color::from("#4897d4".to_owned()),
-            cpu_start: Color::from("#5474e8".to_owned()),
-            cpu_mid: Color::from("#ff40b6".to_owned()),
-            cpu_end: Color::from("#50f095".to_owned()),
-            free_start: Color::from("#f2e266".to_owned()),
-            free_mid: Color::from("#fa1e1e".to_owned()),
-            free_end: Color::from("#223014".to_owned()),
-            cached_start: Color::from("#b5e685".to_owned()),
-            cached_mid: Color::from("#dcff85".to_owned()),
-            cached_end: Color::from("#0b1a29".to_owned()),
-            available_start: Color::from("#74e6fc".to_owned()),
-            available_mid: Color::from("#26c5ff".to_owned()),
-            available_end: Color::from("#292107".to_owned()),
-            used_start: Color::from("#ffd77a".to_owned()),
-            used_mid: Color::from("#ffb814".to_owned()),
-            used_end: Color::from("#3b1f1c".to_owned()),
-            download_start: Color::from("#d9626d".to_owned()),
-            download_mid: Color::from("#ff4769".to_owned()),
-            download_end: Color::from("#231a63".to_owned()),
-            upload_start: Color::from("#4f43a3".to_owned()),
-            upload_mid: Color::from("#b0a9de".to_owned()),
-            upload_end: Color::from("#510554".to_owned()),
-            graph_text: Color::from("#7d4180".to_owned()),
-            meter_bg: Color::from("#dcafde".to_owned()),
-            process_start: Color::from("#80d0a3".to_owned()),
-            process_mid: Color::from("#dcd179".to_owned()),
-            process_end: Color::from("#d45454".to_owned()),
-        }
+        Self::from_dict(&HashMap::new())
     }
-
 }
 
 #[derive(Clone, Default)]
@@ -429,56 +508,29 @@ pub struct Theme {
     pub current: String,
     pub gradient: HashMap<String, Vec<String>>,
     pub colors: Colors,
+    pub warnings: Vec<String>,
 }
 impl Theme {
+    /// Load the theme with the given name from the theme directories, "Default" and "Default Light" for the builtin themes
     pub fn from_str<S: ToString>(s: S) -> Result<Self, String> {
-        let colors_mut: Colors = match Colors::from_str(s) {
-            Ok(c) => c,
-            _ => {
-                errlog(String::from("Error in Color parsing"));
-                Colors::default()
-            },
-        };
-
-        let mut cached_mut: HashMap<String, HashMap<String, String>> =
-            HashMap::<String, HashMap<String, String>>::new();
-        cached_mut.insert("Default".to_owned(), DEFAULT_THEME.to_owned());
-
-        let mut gradient_mut: HashMap<String, Vec<String>> = HashMap::<String, Vec<String>>::new();
-        gradient_mut.insert("temp".to_owned(), Vec::<String>::new());
-        gradient_mut.insert("cpu".to_owned(), Vec::<String>::new());
-        gradient_mut.insert("free".to_owned(), Vec::<String>::new());
-        gradient_mut.insert("cached".to_owned(), Vec::<String>::new());
-        gradient_mut.insert("available".to_owned(), Vec::<String>::new());
-        gradient_mut.insert("used".to_owned(), Vec::<String>::new());
-        gradient_mut.insert("download".to_owned(), Vec::<String>::new());
-        gradient_mut.insert("upload".to_owned(), Vec::<String>::new());
-        gradient_mut.insert("proc".to_owned(), Vec::<String>::new());
-        gradient_mut.insert("proc_color".to_owned(), Vec::<String>::new());
-        gradient_mut.insert("process".to_owned(), Vec::<String>::new());
-
-        Ok(Theme {
-            themes: HashMap::<String, String>::new(),
-            cached: cached_mut,
-            current: String::default(),
-            gradient: gradient_mut,
-            colors: colors_mut,
-        })
+        let mut theme: Theme = Self::from_colors(Colors::default());
+        theme.refresh();
+        theme.load(s.to_string());
+        Ok(theme)
     }
 
-    pub fn new<R>(mut reader: R) -> Result<Self, String>
+    pub fn new<R>(reader: R) -> Result<Self, String>
     where
         R: Read,
     {
-        // let colors_mut: Colors = match Colors::new(reader) {
-        //     Ok(c) => c,
-        //     Err(e) => return Err(e),
-        // };
-        let colors_mut : Colors = Colors::new(reader).unwrap();
+        Ok(Self::from_colors(Colors::new(reader)?))
+    }
 
+    fn from_colors(colors: Colors) -> Self {
         let mut cached_mut: HashMap<String, HashMap<String, String>> =
             HashMap::<String, HashMap<String, String>>::new();
         cached_mut.insert("Default".to_owned(), DEFAULT_THEME.to_owned());
+        cached_mut.insert("Default Light".to_owned(), DEFAULT_LIGHT_THEME.to_owned());
 
         let mut gradient_mut: HashMap<String, Vec<String>> = HashMap::<String, Vec<String>>::new();
         gradient_mut.insert("temp".to_owned(), Vec::<String>::new());
@@ -493,13 +545,208 @@ impl Theme {
         gradient_mut.insert("proc_color".to_owned(), Vec::<String>::new());
         gradient_mut.insert("process".to_owned(), Vec::<String>::new());
 
-        Ok(Theme {
+        let mut theme: Theme = Theme {
             themes: HashMap::<String, String>::new(),
             cached: cached_mut,
             current: String::default(),
             gradient: gradient_mut,
-            colors: colors_mut,
-        })
+            colors: Colors::default(),
+            warnings: Vec::<String>::new(),
+        };
+        theme.set_colors(colors);
+        theme
+    }
+
+    /// Set the colors and make the 101 step gradients from their color stops
+    pub fn set_colors(&mut self, colors: Colors) {
+        let mut stops: HashMap<String, Vec<Stop>> = colors.gradient();
+        // * The process list fades from the text color, and from the inactive color when using cpu colors
+        let fade = |start: &Color, end: &Color| -> Vec<Stop> {
+            vec![
+                Stop {
+                    position: 0.0,
+                    color: format!("{:X}", start),
+                },
+                Stop {
+                    position: 1.0,
+                    color: format!("{:X}", end),
+                },
+            ]
+        };
+        stops.insert(
+            "proc".to_owned(),
+            fade(&colors.main_fg, &colors.inactive_fg),
+        );
+        stops.insert(
+            "proc_color".to_owned(),
+            fade(&colors.inactive_fg, &colors.process_start),
+        );
+        let space: Space = Self::gradient_space();
+        for (name, gradient_stops) in stops {
+            self.gradient
+                .insert(name, Self::make_gradient(&gradient_stops, space));
+        }
+        self.colors = colors;
+    }
+
+    /// Foreground escapes for 0-100 through the stops that are set, mixed in the given color space
+    fn make_gradient(stops: &Vec<Stop>, space: Space) -> Vec<String> {
+        let colors: Vec<(f64, (u8, u8, u8))> = stops
+            .iter()
+            .filter_map(|stop| match Color::new(&stop.color) {
+                Ok(c) if !c.null => Some((stop.position, (c.r, c.g, c.b))),
+                _ => None,
+            })
+            .collect();
+        if colors.len() == 0 {
+            return vec![String::default(); 101];
+        }
+
+        gradient::interpolate(&colors, space, 101)
+            .iter()
+            .map(|(r, g, b)| {
+                Color {
+                    r: *r,
+                    g: *g,
+                    b: *b,
+                    depth: LayerDepth::Fg,
+                    null: false,
+                }
+                .escape()
+            })
+            .collect()
+    }
+
+    /// Color space used to mix the colors of gradients
+    pub fn gradient_space() -> Space {
+        *GRADIENT_SPACE.lock().unwrap()
+    }
+
+    pub fn set_gradient_space(space: Space) {
+        *GRADIENT_SPACE.lock().unwrap() = space;
+    }
+    /// Set the colors from a cached or found theme, falls back to the default theme if there is none by that name
+    fn load(&mut self, name: String) {
+        let mut warnings: Vec<String> = Vec::<String>::new();
+        let mut current: String = name.clone();
+        let tdict: HashMap<String, String> = if let Some(cached) = self.cached.get(&name) {
+            cached.clone()
+        } else if let Some(path) = self.themes.get(&name).cloned() {
+            match Self::_load_file(path) {
+                Ok((tdict, file_warnings)) => {
+                    warnings = file_warnings;
+                    self.cached.insert(name.clone(), tdict.clone());
+                    tdict
+                }
+                Err(e) => {
+                    warnings.push(e);
+                    DEFAULT_THEME.to_owned()
+                }
+            }
+        } else {
+            warnings.push(format!(
+                "No theme named \"{}\" found, using the default",
+                name
+            ));
+            current = "Default".to_owned();
+            DEFAULT_THEME.to_owned()
+        };
+
+        for warning in warnings.iter() {
+            errlog(format!("Theme \"{}\": {}", name, warning));
+        }
+        self.current = current;
+        self.set_colors(Colors::from_dict(&tdict));
+        self.warnings = warnings;
+    }
+
+    /// A value for every theme key, keys missing from older themes are taken from the colors they
+    /// were split from and the rest from DEFAULT_THEME
+    pub fn complete(theme: &HashMap<String, String>) -> HashMap<String, String> {
+        let mut tdict: HashMap<String, String> = theme.clone();
+        if !tdict.contains_key("graph_text") && tdict.contains_key("inactive_fg") {
+            tdict.insert("graph_text".to_owned(), tdict["inactive_fg"].clone());
+        }
+        if !tdict.contains_key("meter_bg") && tdict.contains_key("inactive_fg") {
+            tdict.insert("meter_bg".to_owned(), tdict["inactive_fg"].clone());
+        }
+        if !tdict.contains_key("process_start") && tdict.contains_key("cpu_start") {
+            tdict.insert("process_start".to_owned(), tdict["cpu_start"].clone());
+            for part in vec!["mid", "end"] {
+                let value: String = tdict
+                    .get(&format!("cpu_{}", part))
+                    .cloned()
+                    .unwrap_or_default();
+                tdict.insert(format!("process_{}", part), value);
+            }
+        }
+        for (item, default) in DEFAULT_THEME.iter() {
+            if !tdict.contains_key(item) {
+                tdict.insert(item.clone(), default.clone());
+            }
+        }
+        tdict
+    }
+
+    /// Parse a theme in the `theme[key]="value"` format, returns the keys with valid colors and a
+    /// warning with the line number for every line or key that was left out
+    pub fn parse(s: &String) -> (HashMap<String, String>, Vec<String>) {
+        let mut tdict: HashMap<String, String> = HashMap::<String, String>::new();
+        let mut warnings: Vec<String> = Vec::<String>::new();
+        for (n, raw) in s.lines().enumerate() {
+            let line: &str = raw.trim();
+            if line.len() == 0 || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = match Self::parse_line(line) {
+                Ok(kv) => kv,
+                Err(e) => {
+                    warnings.push(format!("line {}: {}", n + 1, e));
+                    continue;
+                }
+            };
+            if !DEFAULT_THEME.contains_key(&key) {
+                warnings.push(format!("line {}: unknown key \"{}\"", n + 1, key));
+            } else if !Color::is_valid(&value) {
+                warnings.push(format!(
+                    "line {}: \"{}\" is not a color, {} uses the default",
+                    n + 1,
+                    value,
+                    key
+                ));
+            } else {
+                if tdict.contains_key(&key) {
+                    warnings.push(format!("line {}: {} is set again", n + 1, key));
+                }
+                tdict.insert(key, value);
+            }
+        }
+        (tdict, warnings)
+    }
+
+    /// Key and value of a single `theme[key]="value"` line, the value can be in single or double quotes
+    fn parse_line(line: &str) -> Result<(String, String), String> {
+        let rest: &str = match line.strip_prefix("theme[") {
+            Some(r) => r,
+            None => return Err("expected theme[key]=\"value\"".to_owned()),
+        };
+        let close: usize = match rest.find(']') {
+            Some(c) => c,
+            None => return Err("missing \"]\" after the key".to_owned()),
+        };
+        let key: String = rest[..close].trim().to_owned();
+        let rest: &str = match rest[close + 1..].trim_start().strip_prefix('=') {
+            Some(r) => r.trim_start(),
+            None => return Err(format!("missing \"=\" after theme[{}]", key)),
+        };
+        let quote: char = match rest.chars().next() {
+            Some(q) if q == '"' || q == '\'' => q,
+            _ => return Err(format!("the value of {} is not quoted", key)),
+        };
+        match rest[1..].find(quote) {
+            Some(end) => Ok((key, rest[1..end + 1].to_owned())),
+            None => Err(format!("missing closing quote after the value of {}", key)),
+        }
     }
 
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Result<Self, String>, io::Error> {
@@ -507,7 +754,7 @@ impl Theme {
     }
 
     pub fn refresh(&mut self) {
-        self.themes = vec![("Default", "Default")]
+        self.themes = vec![("Default", "Default"), ("Default Light", "Default Light")]
             .iter()
             .map(|(s1, s2)| (s1.clone().to_owned(), s2.clone().to_owned()))
             .collect();
@@ -546,11 +793,11 @@ impl Theme {
                                     } else {
                                         "+".to_owned()
                                     },
-                                    path_str[..path_str.len() - 7].to_owned()
+                                    path_str[..path_str.len() - 6].to_owned()
                                 );
                                 self.themes.insert(
                                     index.clone(),
-                                    format!("{}/{:?}", d.to_str().unwrap(), f_unwrap.file_name()),
+                                    format!("{}/{}", d.to_str().unwrap(), path_str),
                                 );
                             }
                         }
@@ -568,35 +815,91 @@ impl Theme {
         }
     }
 
-    pub fn _load_file<P: AsRef<Path>>(path: P) -> Result<HashMap<String, String>, String> {
-        let mut new_theme: HashMap<String, String> = HashMap::<String, String>::new();
-        let file = match File::open(path) {
-            Ok(f) => f,
-            Err(e) => {
-                let error_string = format!("Unable to open path provided ({})", e);
-                errlog(error_string.clone());
-                return Err(error_string.clone());
-            }
+    /// Read the colors and warnings of a theme in the theme list, None for the builtin themes
+    /// that have no file. A theme that can't be read or has no valid colors is an error
+    fn read(
+        &self,
+        name: &String,
+    ) -> Result<Option<(HashMap<String, String>, Vec<String>)>, String> {
+        let path: String = match self.themes.get(name) {
+            Some(p) => p.clone(),
+            None => return Err(format!("No theme named \"{}\" found", name)),
         };
-        let reader = io::BufReader::new(file).lines();
+        if path == *name {
+            return Ok(None);
+        }
+        let (tdict, warnings) = Self::_load_file(&path)?;
+        if tdict.len() == 0 {
+            return Err(format!(
+                "No valid colors in {}{}",
+                path,
+                warnings
+                    .first()
+                    .map_or(String::default(), |w| format!(", {}", w))
+            ));
+        }
+        Ok(Some((tdict, warnings)))
+    }
 
-        for liner in reader {
-            let line: String = match liner {
-                Ok(s) => s.clone(),
-                Err(_) => continue,
-            };
+    /// Read the file of the current theme again after it changed, the colors are kept if the
+    /// file can't be read or has no valid colors
+    pub fn reload(&mut self) -> Result<(), String> {
+        self.refresh();
+        let (tdict, warnings) = match self.read(&self.current)? {
+            Some(read) => read,
+            None => return Ok(()),
+        };
+        for warning in warnings.iter() {
+            errlog(format!("Theme \"{}\": {}", self.current, warning));
+        }
+        self.cached.insert(self.current.clone(), tdict.clone());
+        self.set_colors(Colors::from_dict(&tdict));
+        self.warnings = warnings;
+        Ok(())
+    }
 
-            if !line.starts_with("theme[") {
-                continue;
+    /// Check that a theme can be loaded without loading it, returns the warnings for its file
+    pub fn check(name: &String) -> Result<Vec<String>, String> {
+        let mut theme: Theme = Theme::default();
+        theme.refresh();
+        Ok(match theme.read(name)? {
+            Some((_, warnings)) => warnings,
+            None => Vec::<String>::new(),
+        })
+    }
+
+    /// Write a theme to name.theme in the user theme directory
+    pub fn save(name: &String, tdict: &HashMap<String, String>) -> Result<PathBuf, String> {
+        if let Err(e) = fs::create_dir_all(USER_THEME_DIR.as_path()) {
+            return Err(format!("Unable to create the user theme directory ({})", e));
+        }
+        let path: PathBuf = USER_THEME_DIR.join(format!("{}.theme", name));
+        let mut out: String = format!("#Theme saved by brshtop v. {}\n\n", VERSION.to_owned());
+        for key in THEME_KEYS.iter() {
+            if let Some(value) = tdict.get(*key) {
+                out.push_str(format!("theme[{}]=\"{}\"\n", key, value).as_str());
             }
-            let key: String = line[6..line.chars().position(|c| c == ']').unwrap()].to_owned();
-            let s: usize = line.chars().position(|c| c == '"').unwrap();
-            let value: String =
-                line[s + 1..line[s + 1..].chars().position(|c| c == '"').unwrap()].to_owned();
-            new_theme.insert(key, value);
         }
+        match fs::write(&path, out) {
+            Ok(_) => Ok(path),
+            Err(e) => Err(format!("Unable to write {} ({})", path.display(), e)),
+        }
+    }
 
-        Ok(new_theme)
+    /// Read and parse a theme file, a file that can't be read is an error, bad lines are warnings
+    pub fn _load_file<P: AsRef<Path>>(
+        path: P,
+    ) -> Result<(HashMap<String, String>, Vec<String>), String> {
+        let mut file = match File::open(path) {
+            Ok(f) => f,
+            Err(e) => return Err(format!("Unable to open path provided ({})", e)),
+        };
+        let mut buffer: String = String::new();
+        if let Err(e) = file.read_to_string(&mut buffer) {
+            return Err(format!("Unable to read theme file ({})", e));
+        }
+
+        Ok(Self::parse(&buffer))
     }
 
     pub fn replace_self(&mut self, theme: Theme) {
@@ -605,6 +908,7 @@ impl Theme {
         self.current = theme.current.clone();
         self.gradient = theme.gradient.clone();
         self.colors = theme.colors.clone();
+        self.warnings = theme.warnings.clone();
     }
 }
 
@@ -729,3 +1033,72 @@ class Theme:
 
         return new_theme
  */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_theme_files() {
+        let (tdict, warnings) = Theme::parse(
+            &"# comment\ntheme[main_bg]=\"#000000\"\ntheme[ hi_fg ] = '#ff0000'\ntheme[title]=\"\"\ntheme[main_fg]=\"nope\"\ntheme[unknown]=\"#ffffff\"\ntheme[cpu_box]=#00ff00\ntheme[main_bg]=\"#111111\"\n"
+                .to_owned(),
+        );
+        assert_eq!(tdict["main_bg"], "#111111");
+        assert_eq!(tdict["hi_fg"], "#ff0000");
+        assert_eq!(tdict["title"], "");
+        assert!(!tdict.contains_key("main_fg"));
+        assert!(!tdict.contains_key("cpu_box"));
+        assert_eq!(warnings.len(), 4);
+        assert!(warnings[0].starts_with("line 5:"));
+        assert!(warnings[1].starts_with("line 6:"));
+        assert!(warnings[2].starts_with("line 7:"));
+        assert!(warnings[3].starts_with("line 8:"));
+    }
+
+    #[test]
+    fn parses_theme_lines() {
+        let pair = |k: &str, v: &str| -> Result<(String, String), String> {
+            Ok((k.to_owned(), v.to_owned()))
+        };
+        assert_eq!(
+            Theme::parse_line("theme[main_bg]=\"#000000\""),
+            pair("main_bg", "#000000")
+        );
+        assert_eq!(
+            Theme::parse_line("theme[ title ] = 'a\"b' # comment"),
+            pair("title", "a\"b")
+        );
+        assert_eq!(
+            Theme::parse_line("main_bg=\"#000000\""),
+            Err("expected theme[key]=\"value\"".to_owned())
+        );
+        assert_eq!(
+            Theme::parse_line("theme[main_bg=\"#000000\""),
+            Err("missing \"]\" after the key".to_owned())
+        );
+        assert_eq!(
+            Theme::parse_line("theme[main_bg] \"#000000\""),
+            Err("missing \"=\" after theme[main_bg]".to_owned())
+        );
+        assert_eq!(
+            Theme::parse_line("theme[main_bg]=#000000"),
+            Err("the value of main_bg is not quoted".to_owned())
+        );
+        assert_eq!(
+            Theme::parse_line("theme[main_bg]=\"#000000"),
+            Err("missing closing quote after the value of main_bg".to_owned())
+        );
+    }
+
+    #[test]
+    fn validates_colors() {
+        assert!(Color::is_valid(""));
+        assert!(Color::is_valid("#1a2B3c"));
+        assert!(Color::is_valid("#ff"));
+        assert!(Color::is_valid("255 128 0"));
+        assert!(!Color::is_valid("256 0 0"));
+        assert!(!Color::is_valid("#fff"));
+        assert!(!Color::is_valid("red"));
+    }
+}
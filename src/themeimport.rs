@@ -0,0 +1,417 @@
+use {
+    crate::{
+        term::Term,
+        theme::{Theme, ANSI_16},
+    },
+    std::{collections::HashMap, fs, path::Path},
+};
+
+/// Formats a theme can be imported from, "auto" guesses it from the file name and contents
+pub const IMPORT_FORMATS: [&str; 5] = ["auto", "btop", "xresources", "alacritty", "kitty"];
+
+/// Names of the normal colors in alacritty configs, the bright section uses the same names
+const ALACRITTY_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// How much of the background is mixed into the dark start of gradients
+const DIM: u32 = 70;
+
+/// The 16 ANSI colors and the default foreground and background of a terminal color scheme
+#[derive(Clone, Debug, Default)]
+pub struct Palette {
+    pub fg: Option<(u8, u8, u8)>,
+    pub bg: Option<(u8, u8, u8)>,
+    pub colors: [Option<(u8, u8, u8)>; 16],
+}
+impl Palette {
+    /// Ask the running terminal for its palette with OSC 4, 10 and 11
+    pub fn from_terminal() -> Self {
+        let mut queries: Vec<String> = (0..16).map(|n| format!("4;{}", n)).collect();
+        queries.push("10".to_owned());
+        queries.push("11".to_owned());
+        let answers: HashMap<String, (u8, u8, u8)> = Term::query_colors(&queries);
+
+        let mut palette: Palette = Palette::default();
+        for n in 0..16 {
+            palette.colors[n] = answers.get(&format!("4;{}", n)).copied();
+        }
+        palette.fg = answers.get("10").copied();
+        palette.bg = answers.get("11").copied();
+        palette
+    }
+
+    /// Xresources with "*.color0: #282828" style resources, #define'd names are resolved
+    pub fn from_xresources(s: &String) -> Self {
+        let mut palette: Palette = Palette::default();
+        let mut defines: HashMap<String, String> = HashMap::<String, String>::new();
+        for raw in s.lines() {
+            let line: &str = raw.trim();
+            if line.starts_with("#define") {
+                let parts: Vec<&str> = line.split_whitespace().collect();
+                if parts.len() >= 3 {
+                    defines.insert(parts[1].to_owned(), parts[2].to_owned());
+                }
+                continue;
+            } else if line.starts_with('!') || line.starts_with('#') {
+                continue;
+            }
+            let (resource, value) = match line.find(':') {
+                Some(i) => (&line[..i], line[i + 1..].trim()),
+                None => continue,
+            };
+            // * Only the last part of the resource matters, "URxvt*color1" and "*.color1" are the same
+            let name: &str = resource
+                .rsplit(|c: char| c == '.' || c == '*')
+                .next()
+                .unwrap_or("")
+                .trim();
+            let value: &str = defines.get(value).map(|v| v.as_str()).unwrap_or(value);
+            palette.set(name, value);
+        }
+        palette
+    }
+
+    /// Kitty configs with "color0 #282828" lines
+    pub fn from_kitty(s: &String) -> Self {
+        let mut palette: Palette = Palette::default();
+        for raw in s.lines() {
+            let parts: Vec<&str> = raw.split_whitespace().collect();
+            if parts.len() >= 2 && !parts[0].starts_with('#') {
+                palette.set(parts[0], parts[1]);
+            }
+        }
+        palette
+    }
+
+    /// Alacritty configs, both the yaml format with a "colors:" block and the toml format with [colors.*] tables
+    pub fn from_alacritty(s: &String) -> Self {
+        let mut palette: Palette = Palette::default();
+        let mut section: String = String::default();
+        for raw in s.lines() {
+            let line: &str = raw.trim();
+            if line.len() == 0 || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') {
+                section = line
+                    .trim_matches(|c: char| c == '[' || c == ']')
+                    .rsplit('.')
+                    .next()
+                    .unwrap_or("")
+                    .to_owned();
+                continue;
+            }
+            let (name, value) = match line.find(|c: char| c == ':' || c == '=') {
+                Some(i) => (line[..i].trim(), line[i + 1..].trim()),
+                None => continue,
+            };
+            if value.len() == 0 {
+                section = name.to_owned();
+                continue;
+            }
+            let index: Option<usize> = ALACRITTY_NAMES.iter().position(|n| *n == name);
+            match (section.as_str(), index) {
+                ("primary", _) => palette.set(name, value),
+                ("normal", Some(i)) => palette.set(format!("color{}", i).as_str(), value),
+                ("bright", Some(i)) => palette.set(format!("color{}", i + 8).as_str(), value),
+                _ => (),
+            }
+        }
+        palette
+    }
+
+    /// Set "foreground", "background" or "colorN" if the value is a color
+    fn set(&mut self, name: &str, value: &str) {
+        let rgb: Option<(u8, u8, u8)> = parse_rgb(value);
+        if rgb.is_none() {
+            return;
+        }
+        if name == "foreground" {
+            self.fg = rgb;
+        } else if name == "background" {
+            self.bg = rgb;
+        } else if let Some(n) = name
+            .strip_prefix("color")
+            .and_then(|n| n.parse::<usize>().ok())
+        {
+            if n < 16 {
+                self.colors[n] = rgb;
+            }
+        }
+    }
+
+    /// Names of the colors the scheme didn't set
+    pub fn missing(&self) -> Vec<String> {
+        let mut missing: Vec<String> = (0..16)
+            .filter(|n| self.colors[*n].is_none())
+            .map(|n| format!("color{}", n))
+            .collect();
+        if self.fg.is_none() {
+            missing.push("foreground".to_owned());
+        }
+        if self.bg.is_none() {
+            missing.push("background".to_owned());
+        }
+        missing
+    }
+
+    /// Map the palette onto the theme keys, missing colors use the xterm defaults
+    pub fn to_theme(&self) -> HashMap<String, String> {
+        let color = |n: usize| -> (u8, u8, u8) { self.colors[n].unwrap_or(ANSI_16[n]) };
+        let bg: (u8, u8, u8) = self.bg.unwrap_or(color(0));
+        let fg: (u8, u8, u8) = self.fg.unwrap_or(color(7));
+        let hex = |c: (u8, u8, u8)| -> String { format!("#{:02x}{:02x}{:02x}", c.0, c.1, c.2) };
+        // * Dark gradient starts are the color mostly faded into the background
+        let dim = |c: (u8, u8, u8)| -> String {
+            let mix =
+                |a: u8, b: u8| -> u8 { ((a as u32 * (100 - DIM) + b as u32 * DIM) / 100) as u8 };
+            hex((mix(c.0, bg.0), mix(c.1, bg.1), mix(c.2, bg.2)))
+        };
+
+        let mut tdict: HashMap<String, String> = HashMap::<String, String>::new();
+        let mut set = |key: &str, value: String| {
+            tdict.insert(key.to_owned(), value);
+        };
+        set("main_bg", hex(bg));
+        set("main_fg", hex(fg));
+        set("title", hex(self.colors[15].unwrap_or(fg)));
+        set("hi_fg", hex(color(1)));
+        set("selected_bg", dim(color(1)));
+        set("selected_fg", hex(self.colors[15].unwrap_or(fg)));
+        set("inactive_fg", hex(color(8)));
+        set("graph_text", hex(color(8)));
+        set("meter_bg", hex(color(8)));
+        set("proc_misc", hex(color(2)));
+        set("cpu_box", hex(color(2)));
+        set("mem_box", hex(color(3)));
+        set("net_box", hex(color(4)));
+        set("proc_box", hex(color(1)));
+        set("div_line", hex(color(8)));
+        set("temp_start", hex(color(4)));
+        set("temp_mid", hex(color(5)));
+        set("temp_end", hex(color(1)));
+        set("cpu_start", hex(color(2)));
+        set("cpu_mid", hex(color(3)));
+        set("cpu_end", hex(color(1)));
+        set("process_start", hex(color(2)));
+        set("process_mid", hex(color(3)));
+        set("process_end", hex(color(1)));
+        for (name, n) in [
+            ("free", 2),
+            ("cached", 6),
+            ("available", 3),
+            ("used", 1),
+            ("download", 4),
+            ("upload", 5),
+        ]
+        .iter()
+        {
+            set(format!("{}_start", name).as_str(), dim(color(*n)));
+            set(format!("{}_mid", name).as_str(), hex(color(*n)));
+            set(format!("{}_end", name).as_str(), hex(color(*n + 8)));
+        }
+        tdict
+    }
+}
+
+/// A color as "#rrggbb", "0xrrggbb" or the "rgb:rr/gg/bb" form of Xresources, quotes are ignored
+pub fn parse_rgb(value: &str) -> Option<(u8, u8, u8)> {
+    let value: &str = value.trim().trim_matches(|c: char| c == '"' || c == '\'');
+    if let Some(rgb) = value.strip_prefix("rgb:") {
+        let parts: Vec<u8> = rgb
+            .split('/')
+            .filter_map(|p| u8::from_str_radix(p.get(..2).unwrap_or(p), 16).ok())
+            .collect();
+        return if parts.len() == 3 {
+            Some((parts[0], parts[1], parts[2]))
+        } else {
+            None
+        };
+    }
+    let digits: &str = value
+        .strip_prefix('#')
+        .or_else(|| value.strip_prefix("0x"))?;
+    if digits.len() != 6 || !digits.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| -> Option<u8> { u8::from_str_radix(&digits[i..i + 2], 16).ok() };
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Guess the format of a theme or color scheme from its file name and contents
+pub fn detect_format(path: &Path, s: &String) -> String {
+    let name: String = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let format: &str = if s.lines().any(|l| l.trim_start().starts_with("theme[")) {
+        "btop"
+    } else if name.contains("alacritty")
+        || name.ends_with(".yml")
+        || name.ends_with(".yaml")
+        || name.ends_with(".toml")
+    {
+        "alacritty"
+    } else if name.contains("kitty")
+        || s.lines().any(|l| {
+            l.trim_start().starts_with("foreground ") || l.trim_start().starts_with("color0 ")
+        })
+    {
+        "kitty"
+    } else {
+        "xresources"
+    };
+    format.to_owned()
+}
+
+/// Name for a theme imported from a source, the file name without extension or "terminal"
+pub fn theme_name(source: &String) -> String {
+    if source == "terminal" {
+        return source.clone();
+    }
+    Path::new(source)
+        .file_stem()
+        .map(|s| s.to_string_lossy().trim_start_matches('.').to_owned())
+        .filter(|s| s.len() > 0)
+        .unwrap_or("imported".to_owned())
+}
+
+/// Build a theme from a btop/bashtop theme file, a terminal color scheme or "terminal" for the
+/// palette of the running terminal. Returns every theme key and warnings about what was left out
+pub fn import(
+    source: &String,
+    format: &str,
+) -> Result<(HashMap<String, String>, Vec<String>), String> {
+    let palette: Palette = if source == "terminal" {
+        let palette: Palette = Palette::from_terminal();
+        if palette.colors.iter().all(|c| c.is_none()) {
+            return Err("The terminal didn't answer the color queries".to_owned());
+        }
+        palette
+    } else {
+        let path: &Path = Path::new(source);
+        let s: String = match fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(e) => return Err(format!("Unable to read {} ({})", source, e)),
+        };
+        let format: String = if format == "auto" {
+            detect_format(path, &s)
+        } else {
+            format.to_owned()
+        };
+        match format.as_str() {
+            "btop" => {
+                let (tdict, warnings) = Theme::parse(&s);
+                return Ok((Theme::complete(&tdict), warnings));
+            }
+            "xresources" => Palette::from_xresources(&s),
+            "alacritty" => Palette::from_alacritty(&s),
+            "kitty" => Palette::from_kitty(&s),
+            _ => return Err(format!("Unknown format \"{}\"", format)),
+        }
+    };
+
+    let missing: Vec<String> = palette.missing();
+    if missing.len() == 18 {
+        return Err(format!("No colors found in {}", source));
+    }
+    let warnings: Vec<String> = missing
+        .iter()
+        .map(|name| format!("{} not set, using the default", name))
+        .collect();
+    Ok((Theme::complete(&palette.to_theme()), warnings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_color_values() {
+        assert_eq!(parse_rgb("#1a2B3c"), Some((0x1a, 0x2b, 0x3c)));
+        assert_eq!(parse_rgb("'0xff8000'"), Some((255, 128, 0)));
+        assert_eq!(parse_rgb("\"#000000\""), Some((0, 0, 0)));
+        assert_eq!(parse_rgb("rgb:ff/80/00"), Some((255, 128, 0)));
+        assert_eq!(parse_rgb("rgb:ffff/8080/0000"), Some((255, 128, 0)));
+        assert_eq!(parse_rgb("#fff"), None);
+        assert_eq!(parse_rgb("#gg0000"), None);
+        assert_eq!(parse_rgb("red"), None);
+    }
+
+    #[test]
+    fn parses_xresources() {
+        let palette = Palette::from_xresources(
+            &"! comment\n\
+              #define bg #101010\n\
+              *.background: bg\n\
+              *foreground:  #e0e0e0\n\
+              URxvt*color1: rgb:cc/00/00\n\
+              *.color15: #ffffff\n\
+              *.color16: #123456\n\
+              *.cursorColor: #ff0000\n"
+                .to_owned(),
+        );
+        assert_eq!(palette.bg, Some((0x10, 0x10, 0x10)));
+        assert_eq!(palette.fg, Some((0xe0, 0xe0, 0xe0)));
+        assert_eq!(palette.colors[1], Some((0xcc, 0, 0)));
+        assert_eq!(palette.colors[15], Some((0xff, 0xff, 0xff)));
+        assert_eq!(palette.colors[0], None);
+    }
+
+    #[test]
+    fn parses_kitty() {
+        let palette = Palette::from_kitty(
+            &"# colors\nforeground #dddddd\nbackground   #222222\ncolor4 #0000ff\n#color5 #ff00ff\ncursor #ffffff\n"
+                .to_owned(),
+        );
+        assert_eq!(palette.fg, Some((0xdd, 0xdd, 0xdd)));
+        assert_eq!(palette.bg, Some((0x22, 0x22, 0x22)));
+        assert_eq!(palette.colors[4], Some((0, 0, 0xff)));
+        assert_eq!(palette.colors[5], None);
+    }
+
+    #[test]
+    fn parses_alacritty_yaml_and_toml() {
+        let yaml = Palette::from_alacritty(
+            &"colors:\n  primary:\n    background: '0x1d1f21'\n    foreground: '0xc5c8c6'\n  normal:\n    red: '0xcc6666'\n  bright:\n    red: '0xd54e53'\n"
+                .to_owned(),
+        );
+        let toml = Palette::from_alacritty(
+            &"[colors.primary]\nbackground = \"#1d1f21\"\nforeground = \"#c5c8c6\"\n\n[colors.normal]\nred = \"#cc6666\"\n\n[colors.bright]\nred = \"#d54e53\"\n"
+                .to_owned(),
+        );
+        for palette in [yaml, toml].iter() {
+            assert_eq!(palette.bg, Some((0x1d, 0x1f, 0x21)));
+            assert_eq!(palette.fg, Some((0xc5, 0xc8, 0xc6)));
+            assert_eq!(palette.colors[1], Some((0xcc, 0x66, 0x66)));
+            assert_eq!(palette.colors[9], Some((0xd5, 0x4e, 0x53)));
+            assert_eq!(palette.missing().len(), 14);
+        }
+    }
+
+    #[test]
+    fn palette_fills_every_key_it_maps() {
+        let mut palette = Palette::default();
+        palette.bg = Some((0, 0, 0));
+        palette.colors[2] = Some((0, 200, 0));
+        let tdict = palette.to_theme();
+        assert_eq!(tdict["main_bg"], "#000000");
+        assert_eq!(tdict["cpu_box"], "#00c800");
+        // * 30% of the color mixed into the black background
+        assert_eq!(tdict["free_start"], "#003c00");
+        assert_eq!(palette.missing().len(), 16);
+    }
+
+    #[test]
+    fn detects_formats() {
+        let detect = |name: &str, s: &str| detect_format(Path::new(name), &s.to_owned());
+        assert_eq!(detect("nord.theme", "theme[main_bg]=\"#000000\""), "btop");
+        assert_eq!(detect("alacritty.yml", "colors:"), "alacritty");
+        assert_eq!(detect("nord.toml", "[colors.primary]"), "alacritty");
+        assert_eq!(detect("nord.conf", "foreground #ffffff"), "kitty");
+        assert_eq!(detect(".Xresources", "*.color0: #000000"), "xresources");
+        assert_eq!(theme_name(&"/tmp/.Xresources".to_owned()), "Xresources");
+        assert_eq!(theme_name(&"terminal".to_owned()), "terminal");
+    }
+}
@@ -0,0 +1,571 @@
+use std::fmt::{self, Display, Formatter};
+
+/// A value in the TOML subset the config file uses, no inline tables, dates or multi-line strings
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    Array(Vec<Value>),
+}
+impl Value {
+    /// The value as the text an option is parsed from, arrays are joined with commas
+    pub fn to_config_string(&self) -> String {
+        match self {
+            Value::String(s) => s.clone(),
+            Value::Integer(i) => i.to_string(),
+            Value::Float(f) => f.to_string(),
+            Value::Boolean(b) => b.to_string(),
+            Value::Array(a) => a
+                .iter()
+                .map(|v| v.to_config_string())
+                .collect::<Vec<String>>()
+                .join(", "),
+        }
+    }
+
+    /// The value as written in a TOML file
+    pub fn to_toml(&self) -> String {
+        match self {
+            Value::String(s) => quote(s),
+            Value::Array(a) => format!(
+                "[{}]",
+                a.iter()
+                    .map(|v| v.to_toml())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            other => other.to_config_string(),
+        }
+    }
+}
+
+/// A key and value with the table it is in and where the value starts, lines and columns count from 1
+#[derive(Clone, Debug)]
+pub struct Entry {
+    pub table: Vec<String>,
+    pub key: String,
+    pub value: Value,
+    pub line: usize,
+    pub column: usize,
+    /// Where an entry that isn't from a file came from, i.e. "--set update_ms=1000"
+    pub source: String,
+}
+impl Entry {
+    /// Where the entry is, for warnings
+    pub fn position(&self) -> String {
+        if self.source.len() > 0 {
+            self.source.clone()
+        } else {
+            format!("Line {}, column {}", self.line, self.column)
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/// Characters of one line and the position of the next one to read
+struct Cursor {
+    chars: Vec<char>,
+    pos: usize,
+}
+impl Cursor {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_space(&mut self) {
+        while self.peek() == Some(' ') || self.peek() == Some('\t') {
+            self.pos += 1;
+        }
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Nothing but a comment is left
+    fn at_end(&mut self) -> bool {
+        self.skip_space();
+        self.peek().map_or(true, |c| c == '#')
+    }
+
+    /// Bare or quoted keys separated by dots
+    fn keys(&mut self) -> Result<Vec<String>, String> {
+        let mut keys: Vec<String> = Vec::<String>::new();
+        loop {
+            self.skip_space();
+            let key: String = match self.peek() {
+                Some('"') | Some('\'') => self.string()?,
+                _ => {
+                    let start: usize = self.pos;
+                    while self
+                        .peek()
+                        .map_or(false, |c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+                    {
+                        self.pos += 1;
+                    }
+                    if self.pos == start {
+                        return Err("Expected a key".to_owned());
+                    }
+                    self.chars[start..self.pos].iter().collect()
+                }
+            };
+            keys.push(key);
+            self.skip_space();
+            if !self.eat('.') {
+                return Ok(keys);
+            }
+        }
+    }
+
+    /// A "basic" string with escapes or a 'literal' string
+    fn string(&mut self) -> Result<String, String> {
+        let quote: char = self.peek().unwrap();
+        self.pos += 1;
+        if self.peek() == Some(quote) && self.chars.get(self.pos + 1) == Some(&quote) {
+            return Err("Multi-line strings are not supported".to_owned());
+        }
+        let mut out: String = String::default();
+        loop {
+            let c: char = match self.peek() {
+                Some(c) => c,
+                None => return Err("Missing closing quote".to_owned()),
+            };
+            self.pos += 1;
+            if c == quote {
+                return Ok(out);
+            } else if c == '\\' && quote == '"' {
+                let escaped: char = match self.peek() {
+                    Some(e) => e,
+                    None => return Err("Missing closing quote".to_owned()),
+                };
+                self.pos += 1;
+                match escaped {
+                    'n' => out.push('\n'),
+                    't' => out.push('\t'),
+                    'r' => out.push('\r'),
+                    '"' => out.push('"'),
+                    '\\' => out.push('\\'),
+                    'u' | 'U' => {
+                        let len: usize = if escaped == 'u' { 4 } else { 8 };
+                        let digits: String = self.chars.iter().skip(self.pos).take(len).collect();
+                        self.pos += len;
+                        match u32::from_str_radix(&digits, 16)
+                            .ok()
+                            .and_then(std::char::from_u32)
+                        {
+                            Some(u) => out.push(u),
+                            None => {
+                                return Err(format!(
+                                    "Invalid unicode escape \\{}{}",
+                                    escaped, digits
+                                ))
+                            }
+                        }
+                    }
+                    other => return Err(format!("Invalid escape \\{}", other)),
+                }
+            } else {
+                out.push(c);
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip_space();
+        match self.peek() {
+            Some('"') | Some('\'') => return Ok(Value::String(self.string()?)),
+            Some('[') => {
+                self.pos += 1;
+                let mut items: Vec<Value> = Vec::<Value>::new();
+                loop {
+                    self.skip_space();
+                    if self.eat(']') {
+                        return Ok(Value::Array(items));
+                    }
+                    items.push(self.value()?);
+                    self.skip_space();
+                    if !self.eat(',') && self.peek() != Some(']') {
+                        return Err("Expected \",\" or \"]\" in array".to_owned());
+                    }
+                }
+            }
+            Some('{') => return Err("Inline tables are not supported".to_owned()),
+            None => return Err("Missing value".to_owned()),
+            _ => (),
+        }
+
+        let start: usize = self.pos;
+        while self.peek().map_or(false, |c| {
+            c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '+' || c == '.'
+        }) {
+            self.pos += 1;
+        }
+        let word: String = self.chars[start..self.pos].iter().collect();
+        let number: String = word.replace('_', "");
+        if word == "true" || word == "false" {
+            Ok(Value::Boolean(word == "true"))
+        } else if let Ok(i) = number.parse::<i64>() {
+            Ok(Value::Integer(i))
+        } else if let (true, Ok(f)) = (
+            number.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+'),
+            number.parse::<f64>(),
+        ) {
+            Ok(Value::Float(f))
+        } else {
+            Err(format!("Invalid value {:?}, strings need quotes", word))
+        }
+    }
+}
+
+/// Parse every line it can, a line with an error is left out and the rest of the file still parses
+pub fn parse(s: &str) -> (Vec<Entry>, Vec<ParseError>) {
+    let mut entries: Vec<Entry> = Vec::<Entry>::new();
+    let mut errors: Vec<ParseError> = Vec::<ParseError>::new();
+    let mut table: Vec<String> = Vec::<String>::new();
+
+    for (n, line) in s.lines().enumerate() {
+        let mut cursor = Cursor {
+            chars: line.chars().collect(),
+            pos: 0,
+        };
+        if cursor.at_end() {
+            continue;
+        }
+        let start: usize = cursor.pos;
+        // * A table header gives no entry, the table only changes if the whole line parses
+        let result: Result<Option<Entry>, String> = if cursor.eat('[') {
+            if cursor.peek() == Some('[') {
+                Err("Arrays of tables are not supported".to_owned())
+            } else {
+                cursor.keys().and_then(|keys| {
+                    if cursor.eat(']') {
+                        table = keys;
+                        Ok(None)
+                    } else {
+                        Err("Expected \"]\" after the table name".to_owned())
+                    }
+                })
+            }
+        } else {
+            cursor.keys().and_then(|mut keys| {
+                if !cursor.eat('=') {
+                    return Err("Expected \"=\" after the key".to_owned());
+                }
+                cursor.skip_space();
+                let column: usize = cursor.pos + 1;
+                let value: Value = cursor.value()?;
+                let key: String = keys.pop().unwrap();
+                let mut full: Vec<String> = table.clone();
+                full.append(&mut keys);
+                if entries.iter().any(|e| e.table == full && e.key == key) {
+                    cursor.pos = start;
+                    return Err(format!("Duplicate key {:?}", key));
+                }
+                Ok(Some(Entry {
+                    table: full,
+                    key,
+                    value,
+                    line: n + 1,
+                    column,
+                    source: String::default(),
+                }))
+            })
+        };
+        let result: Result<Option<Entry>, String> = result.and_then(|entry| {
+            if cursor.at_end() {
+                Ok(entry)
+            } else {
+                Err("Unexpected characters after the value".to_owned())
+            }
+        });
+        match result {
+            Ok(Some(entry)) => entries.push(entry),
+            Ok(None) => (),
+            Err(message) => errors.push(ParseError {
+                line: n + 1,
+                column: cursor.pos + 1,
+                message,
+            }),
+        }
+    }
+    (entries, errors)
+}
+
+/// Parse a single "key = value" like a line of a file, a value that isn't valid TOML is taken as
+/// a string so values given on the command line don't need quotes
+pub fn parse_assignment(s: &str) -> Result<(Vec<String>, Value), String> {
+    let mut cursor = Cursor {
+        chars: s.chars().collect(),
+        pos: 0,
+    };
+    let keys: Vec<String> = cursor.keys()?;
+    if !cursor.eat('=') {
+        return Err("Expected \"=\" after the key".to_owned());
+    }
+    cursor.skip_space();
+    let start: usize = cursor.pos;
+    let value: Value = match cursor.value() {
+        Ok(v) if cursor.at_end() => v,
+        _ => Value::String(
+            cursor.chars[start..]
+                .iter()
+                .collect::<String>()
+                .trim()
+                .to_owned(),
+        ),
+    };
+    Ok((keys, value))
+}
+
+/// A string as a TOML basic string with quotes
+pub fn quote(s: &str) -> String {
+    let mut out: String = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(format!("\\u{:04X}", c as u32).as_str()),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Keys joined with dots for a table header or a key, keys that aren't bare are quoted
+pub fn dotted_key(keys: &[&str]) -> String {
+    keys.iter()
+        .map(|k| {
+            if k.len() > 0
+                && k.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                k.to_string()
+            } else {
+                quote(k)
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(s: &str) -> Vec<Value> {
+        let (entries, errors) = parse(s);
+        assert!(errors.is_empty(), "{:?}", errors);
+        entries.into_iter().map(|e| e.value).collect()
+    }
+
+    fn errors(s: &str) -> Vec<(usize, usize, String)> {
+        parse(s)
+            .1
+            .into_iter()
+            .map(|e| (e.line, e.column, e.message))
+            .collect()
+    }
+
+    fn string(s: &str) -> Value {
+        Value::String(s.to_owned())
+    }
+
+    #[test]
+    fn parses_strings_and_escapes() {
+        assert_eq!(
+            values(
+                "a = \"tab\\there \\\"q\\\" \\\\ \\u00e9\\U0001F600\"\nb = 'C:\\path # not a comment'\nc = \"\" # comment"
+            ),
+            vec![
+                string("tab\there \"q\" \\ \u{e9}\u{1F600}"),
+                string("C:\\path # not a comment"),
+                string(""),
+            ]
+        );
+        let errors = errors("a = \"bad \\x\"\nb = \"open\nc = \"\"\"long\"\"\"\nd = \"\\uZZZZ\"");
+        assert_eq!(errors[0].2, "Invalid escape \\x");
+        assert_eq!(errors[1].2, "Missing closing quote");
+        assert_eq!(errors[2].2, "Multi-line strings are not supported");
+        assert_eq!(errors[3].2, "Invalid unicode escape \\uZZZZ");
+    }
+
+    #[test]
+    fn parses_numbers_and_booleans() {
+        assert_eq!(
+            values("a = 1_000\nb = -1.5\nc = +3\nd = true\ne = false"),
+            vec![
+                Value::Integer(1000),
+                Value::Float(-1.5),
+                Value::Integer(3),
+                Value::Boolean(true),
+                Value::Boolean(false),
+            ]
+        );
+        assert_eq!(
+            errors("a = word"),
+            vec![(
+                1,
+                9,
+                "Invalid value \"word\", strings need quotes".to_owned()
+            )]
+        );
+    }
+
+    #[test]
+    fn parses_arrays() {
+        assert_eq!(
+            values("a = [1, \"two\", [true]]\nb = []\nc = [ 1 , 2, ]"),
+            vec![
+                Value::Array(vec![
+                    Value::Integer(1),
+                    string("two"),
+                    Value::Array(vec![Value::Boolean(true)]),
+                ]),
+                Value::Array(vec![]),
+                Value::Array(vec![Value::Integer(1), Value::Integer(2)]),
+            ]
+        );
+        assert_eq!(errors("a = [1 2]")[0].2, "Expected \",\" or \"]\" in array");
+        assert_eq!(errors("a = [1, 2")[0].2, "Expected \",\" or \"]\" in array");
+        assert_eq!(errors("a = [1,")[0].2, "Missing value");
+        assert_eq!(
+            errors("a = {b = 1}")[0].2,
+            "Inline tables are not supported"
+        );
+        assert_eq!(
+            Value::Array(vec![Value::Integer(1), string("two")]).to_config_string(),
+            "1, two"
+        );
+        assert_eq!(
+            Value::Array(vec![Value::Integer(1), string("two")]).to_toml(),
+            "[1, \"two\"]"
+        );
+    }
+
+    #[test]
+    fn parses_tables_and_dotted_and_quoted_keys() {
+        let (entries, errors) = parse(
+            "top = 1\n[cpu]\ngraph . upper = \"x\"\n[host.\"my.box\"]\n\"key with space\" = 2\n'lit' = 3\n",
+        );
+        assert!(errors.is_empty());
+        let keys: Vec<(Vec<String>, String)> = entries
+            .iter()
+            .map(|e| (e.table.clone(), e.key.clone()))
+            .collect();
+        let table = |t: &[&str]| -> Vec<String> { t.iter().map(|s| s.to_string()).collect() };
+        assert_eq!(
+            keys,
+            vec![
+                (table(&[]), "top".to_owned()),
+                (table(&["cpu", "graph"]), "upper".to_owned()),
+                (table(&["host", "my.box"]), "key with space".to_owned()),
+                (table(&["host", "my.box"]), "lit".to_owned()),
+            ]
+        );
+        assert_eq!(
+            dotted_key(&["host", "my.box", "", "a_b-c"]),
+            "host.\"my.box\".\"\".a_b-c"
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_keys() {
+        assert_eq!(
+            errors("[cpu]\na = 1\n  a = 2\n[mem]\na = 3\n[cpu]\nb.c = 1\n[cpu.b]\nc = 2"),
+            vec![
+                (3, 3, "Duplicate key \"a\"".to_owned()),
+                (9, 1, "Duplicate key \"c\"".to_owned()),
+            ]
+        );
+        // * The first value is kept
+        let (entries, errors) = parse("a = 1\na = 2 # again");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].value, Value::Integer(1));
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn reports_positions() {
+        let (entries, _) = parse("# comment\n\n  x =   \"v\"\n");
+        assert_eq!((entries[0].line, entries[0].column), (3, 9));
+        assert_eq!(entries[0].position(), "Line 3, column 9");
+        assert_eq!(
+            errors("a = 1 extra\n[table\n= 1\n[[arr]]\nb 1\nc = 2 # fine"),
+            vec![
+                (1, 7, "Unexpected characters after the value".to_owned()),
+                (2, 7, "Expected \"]\" after the table name".to_owned()),
+                (3, 1, "Expected a key".to_owned()),
+                (4, 2, "Arrays of tables are not supported".to_owned()),
+                (5, 3, "Expected \"=\" after the key".to_owned()),
+            ]
+        );
+        assert_eq!(
+            parse("[bad\na = 1").1[0].to_string(),
+            "Line 1, column 5: Expected \"]\" after the table name"
+        );
+        // * A table header that doesn't parse leaves the table as it was
+        assert!(parse("[cpu]\n[bad\na = 1").0[0].table == vec!["cpu".to_owned()]);
+    }
+
+    #[test]
+    fn parses_assignments() {
+        let assignment = |s: &str| -> (Vec<String>, Value) { parse_assignment(s).unwrap() };
+        assert_eq!(
+            assignment("update_ms=1000"),
+            (vec!["update_ms".to_owned()], Value::Integer(1000))
+        );
+        assert_eq!(
+            assignment("net.download = 10M"),
+            (vec!["net".to_owned(), "download".to_owned()], string("10M"))
+        );
+        assert_eq!(
+            assignment("color_theme=Default Light").1,
+            string("Default Light")
+        );
+        assert_eq!(assignment("color_theme=\"Default\"").1, string("Default"));
+        assert_eq!(assignment("a = 1 2").1, string("1 2"));
+        assert_eq!(
+            assignment("a = [1, 2]").1,
+            Value::Array(vec![Value::Integer(1), Value::Integer(2)])
+        );
+        assert_eq!(assignment("a=").1, string(""));
+        assert!(parse_assignment("=1").is_err());
+        assert!(parse_assignment("a 1").is_err());
+    }
+
+    #[test]
+    fn quoted_strings_parse_back() {
+        let s: &str = "a \"b\" \\c\nd\te\u{1}é";
+        assert_eq!(quote("a\"b"), "\"a\\\"b\"");
+        assert_eq!(quote("\u{1}"), "\"\\u0001\"");
+        assert_eq!(
+            values(format!("k = {}", quote(s)).as_str()),
+            vec![string(s)]
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use {
+    crate::{error::errlog, event::EventEnum, key::Key},
+    std::{
+        ffi::CString,
+        io,
+        mem::size_of,
+        os::unix::ffi::OsStrExt,
+        path::{Path, PathBuf},
+        ptr,
+        sync::{Arc, Mutex},
+        thread,
+    },
+};
+
+/// Watches the config file and the theme files with inotify. The directories are watched instead
+/// of the files, so files that editors and dotfile tools replace with a rename are still followed
+pub struct Watcher {
+    fd: i32,
+    config_file: PathBuf,
+    /// Watch descriptors with true for theme directories
+    dirs: Vec<(i32, bool)>,
+}
+impl Watcher {
+    pub fn new(config_file: &PathBuf, theme_dirs: Vec<PathBuf>) -> Result<Self, String> {
+        let fd: i32 = unsafe { libc::inotify_init1(libc::IN_CLOEXEC) };
+        if fd < 0 {
+            return Err(format!(
+                "Unable to start inotify ({})",
+                io::Error::last_os_error()
+            ));
+        }
+        let mut watcher = Watcher {
+            fd,
+            config_file: config_file.clone(),
+            dirs: Vec::<(i32, bool)>::new(),
+        };
+        if let Some(dir) = config_file.parent() {
+            watcher.add(dir, false)?;
+        }
+        for dir in theme_dirs.iter().filter(|d| d.is_dir()) {
+            watcher.add(dir, true)?;
+        }
+        Ok(watcher)
+    }
+
+    fn add(&mut self, dir: &Path, themes: bool) -> Result<(), String> {
+        let path: CString = match CString::new(dir.as_os_str().as_bytes()) {
+            Ok(p) => p,
+            Err(e) => return Err(format!("Unable to watch {} ({})", dir.display(), e)),
+        };
+        let wd: i32 = unsafe {
+            libc::inotify_add_watch(
+                self.fd,
+                path.as_ptr(),
+                libc::IN_CLOSE_WRITE | libc::IN_MOVED_TO,
+            )
+        };
+        if wd < 0 {
+            return Err(format!(
+                "Unable to watch {} ({})",
+                dir.display(),
+                io::Error::last_os_error()
+            ));
+        }
+        self.dirs.push((wd, themes));
+        Ok(())
+    }
+
+    /// Block until files in the watched directories change, returns "config_changed" and
+    /// "theme_changed" at most once each for the changes that concern brshtop
+    pub fn wait(&self) -> Result<Vec<String>, String> {
+        let mut buffer: [u8; 4096] = [0; 4096];
+        let len: isize = unsafe {
+            libc::read(
+                self.fd,
+                buffer.as_mut_ptr() as *mut libc::c_void,
+                buffer.len(),
+            )
+        };
+        if len < 0 {
+            let error: io::Error = io::Error::last_os_error();
+            // * Signals for the other threads can interrupt the read
+            if error.kind() == io::ErrorKind::Interrupted {
+                return Ok(Vec::<String>::new());
+            }
+            return Err(format!("Unable to read inotify events ({})", error));
+        }
+
+        let config_name: Vec<u8> = match self.config_file.file_name() {
+            Some(name) => name.as_bytes().to_vec(),
+            None => Vec::<u8>::new(),
+        };
+        let header: usize = size_of::<libc::inotify_event>();
+        let mut keys: Vec<String> = Vec::<String>::new();
+        let mut offset: usize = 0;
+        // * Events are a fixed header followed by the nul padded name of the changed file
+        while offset + header <= len as usize {
+            let event: libc::inotify_event = unsafe {
+                ptr::read_unaligned(buffer.as_ptr().add(offset) as *const libc::inotify_event)
+            };
+            let end: usize = (offset + header + event.len as usize).min(len as usize);
+            let name: Vec<u8> = buffer[offset + header..end]
+                .iter()
+                .take_while(|b| **b != 0)
+                .cloned()
+                .collect();
+            offset = end;
+
+            for (_, themes) in self.dirs.iter().filter(|(wd, _)| *wd == event.wd) {
+                let key: &str = if *themes && name.ends_with(b".theme") {
+                    "theme_changed"
+                } else if !*themes && name == config_name {
+                    "config_changed"
+                } else {
+                    continue;
+                };
+                if !keys.iter().any(|k| k == key) {
+                    keys.push(key.to_owned());
+                }
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Wait for changes in a thread of its own and push them to the key list like key presses
+    pub fn start(self, key_mutex: Arc<Mutex<Key>>) {
+        thread::spawn(move || loop {
+            let keys: Vec<String> = match self.wait() {
+                Ok(k) => k,
+                Err(e) => {
+                    errlog(e);
+                    return;
+                }
+            };
+            if keys.len() == 0 {
+                continue;
+            }
+            let mut key = key_mutex.lock().unwrap();
+            for k in keys {
+                if !key.list.contains(&k) {
+                    key.list.push(k);
+                }
+            }
+            key.new.replace_self(EventEnum::Flag(true));
+        });
+    }
+}
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}